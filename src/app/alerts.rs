@@ -0,0 +1,455 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tokio::task::{AbortHandle, JoinSet};
+
+use crate::app::ansi::strip_ansi;
+use crate::app::parsers::ParserRegistry;
+use crate::app::query::{Query, QueryTarget};
+use crate::aws::{FetchLogsParams, LogEntry, fetch_recent_logs};
+use crate::local_logs::{LocalFileOptions, load_local_file, local_path_from_group};
+use crate::worker::{Limiter, Repaint, blocking, wake};
+
+/// How often the monitor checks which rules are due.
+const MONITOR_TICK: Duration = Duration::from_secs(5);
+
+/// How often each rule is evaluated.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Events fetched per evaluation; a count reaching it is reported as capped.
+const ALERT_FETCH_LIMIT: i32 = 10_000;
+
+/// "More than `threshold` events of `log_group` matching the filter pattern
+/// and the local query within the last `window_minutes`."
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: u64,
+    pub name: String,
+    pub enabled: bool,
+    pub profile: String,
+    pub region: String,
+    /// A CloudWatch log group, or a local file's pseudo group.
+    pub log_group: String,
+    /// CloudWatch filter pattern, applied by CloudWatch (not to local files).
+    pub filter_pattern: String,
+    /// Local query, applied to the fetched events.
+    pub query: String,
+    pub threshold: usize,
+    pub window_minutes: u64,
+    /// Formats parsed for the query, as configured in the tab the rule was
+    /// created from.
+    #[serde(default)]
+    pub parsers: ParserRegistry,
+}
+
+impl AlertRule {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id,
+            name: format!("Alert {}", id + 1),
+            enabled: true,
+            profile: String::new(),
+            region: String::new(),
+            log_group: String::new(),
+            filter_pattern: String::new(),
+            query: String::new(),
+            threshold: 0,
+            window_minutes: 5,
+            parsers: ParserRegistry::default(),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window_minutes.max(1) * 60)
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "more than {} in {} min",
+            self.threshold,
+            self.window_minutes.max(1)
+        )
+    }
+}
+
+/// Outcome of the last evaluation of a rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertStatus {
+    pub rule_id: u64,
+    pub matches: usize,
+    /// The fetch stopped at `ALERT_FETCH_LIMIT`, so `matches` is a lower
+    /// bound.
+    pub capped: bool,
+    pub firing: bool,
+    /// The evaluated `(start, end)` range in epoch millis.
+    pub range: (i64, i64),
+    pub error: Option<String>,
+}
+
+impl AlertStatus {
+    /// The match count, marked when it may be higher.
+    pub fn matches_label(&self) -> String {
+        if self.capped {
+            format!("{}+", self.matches)
+        } else {
+            self.matches.to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertEvent {
+    Status(AlertStatus),
+    /// The notification of this rule was clicked.
+    Open(u64),
+}
+
+/// Number of `entries` within `range` that match `query`, with fields
+/// parsed by `parsers`.
+pub fn count_matches(
+    entries: &[LogEntry],
+    query: Option<&Query>,
+    parsers: &ParserRegistry,
+    range: (i64, i64),
+) -> usize {
+    entries
+        .iter()
+        .filter(|e| (range.0..=range.1).contains(&e.timestamp_millis))
+        .filter(|entry| {
+            let Some(query) = query else {
+                return true;
+            };
+            let (parsed, level) = parsers.parse_with_level(&entry.message);
+            query.matches(&QueryTarget {
+                entry,
+                search_text: &strip_ansi(&entry.message).to_lowercase(),
+                level,
+                parsed: parsed.as_ref(),
+            })
+        })
+        .count()
+}
+
+/// The events of the rule's window, and whether the fetch stopped at
+/// `ALERT_FETCH_LIMIT`.
+async fn fetch_rule_entries(
+    rule: &AlertRule,
+    range: (i64, i64),
+) -> Result<(Vec<LogEntry>, bool), String> {
+    if let Some(path) = local_path_from_group(&rule.log_group) {
+        return blocking(move || load_local_file(&path, &LocalFileOptions::default()))
+            .await
+            .map(|entries| (entries, false))
+            .map_err(|e| e.to_string());
+    }
+    let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+    let profile = non_empty(&rule.profile);
+    let region = non_empty(&rule.region);
+    let params = FetchLogsParams {
+        profile: profile.as_deref(),
+        region: region.as_deref(),
+        log_group: &rule.log_group,
+        filter_pattern: Some(&rule.filter_pattern),
+        absolute_range: Some(range),
+        limit: ALERT_FETCH_LIMIT,
+        ..FetchLogsParams::default()
+    };
+    let entries = fetch_recent_logs(params).await.map_err(|e| e.to_string())?;
+    let capped = entries.len() >= ALERT_FETCH_LIMIT as usize;
+    Ok((entries, capped))
+}
+
+async fn evaluate_rule(rule: &AlertRule) -> AlertStatus {
+    let end = chrono::Utc::now().timestamp_millis();
+    let range = (end - rule.window().as_millis() as i64, end);
+    let mut status = AlertStatus {
+        rule_id: rule.id,
+        matches: 0,
+        capped: false,
+        firing: false,
+        range,
+        error: None,
+    };
+
+    let result = match Query::parse(&rule.query) {
+        Ok(query) => match fetch_rule_entries(rule, range).await {
+            Ok((entries, capped)) => {
+                // Parsing every entry would hold up the worker's other requests.
+                let parsers = rule.parsers.clone();
+                let matches =
+                    blocking(move || count_matches(&entries, query.as_ref(), &parsers, range))
+                        .await;
+                Ok((matches, capped))
+            }
+            Err(err) => Err(err),
+        },
+        Err(err) => Err(format!("invalid query: {err}")),
+    };
+    match result {
+        Ok((matches, capped)) => {
+            status.matches = matches;
+            status.capped = capped;
+            status.firing = matches > rule.threshold;
+        }
+        Err(err) => status.error = Some(err),
+    }
+    status
+}
+
+/// A rule as last evaluated.
+struct Checked {
+    rule: AlertRule,
+    at: Instant,
+    firing: bool,
+}
+
+/// The rules to evaluate, and where to send their statuses and
+/// notification clicks.
+pub type AlertSetup = (Vec<AlertRule>, Sender<AlertEvent>);
+
+/// Evaluate alert rules in the background, independently of the UI (which
+/// may be hidden to the tray), until the sender of `setup` is gone.
+///
+/// Each evaluation is a task waiting for one of `permits`, like any other
+/// worker request; evaluations of rules edited, disabled or removed in the
+/// meantime are aborted. Events go to the sender of the latest setup, and
+/// `repaint` is woken for each.
+pub async fn monitor_alerts(
+    mut setup: watch::Receiver<AlertSetup>,
+    repaint: Repaint,
+    permits: Arc<Limiter>,
+) {
+    let mut events = setup.borrow().1.clone();
+    let mut checked: HashMap<u64, Checked> = HashMap::new();
+    let mut running: HashMap<u64, (AlertRule, AbortHandle)> = HashMap::new();
+    let mut evaluations: JoinSet<(AlertRule, AlertStatus)> = JoinSet::new();
+    let mut ticks = tokio::time::interval(MONITOR_TICK);
+
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {}
+            changed = setup.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+            }
+            Some(done) = evaluations.join_next() => {
+                // Aborted evaluations, and those finished just before their
+                // rule changed, are dropped.
+                let Ok((rule, status)) = done else {
+                    continue;
+                };
+                if running.get(&rule.id).is_none_or(|(r, _)| *r != rule) {
+                    continue;
+                }
+                running.remove(&rule.id);
+
+                // An edited rule notifies afresh.
+                let was_firing = checked
+                    .get(&rule.id)
+                    .is_some_and(|last| last.rule == rule && last.firing);
+                checked.insert(
+                    rule.id,
+                    Checked {
+                        rule: rule.clone(),
+                        at: Instant::now(),
+                        firing: status.firing,
+                    },
+                );
+
+                if status.firing && !was_firing {
+                    let (status, events, repaint) =
+                        (status.clone(), events.clone(), Arc::clone(&repaint));
+                    tokio::task::spawn_blocking(move || notify(&rule, &status, events, repaint));
+                }
+                if events.send(AlertEvent::Status(status)).is_err() {
+                    return;
+                }
+                wake(&repaint);
+                continue;
+            }
+        }
+
+        let rules;
+        (rules, events) = setup.borrow_and_update().clone();
+        checked.retain(|id, _| rules.iter().any(|r| r.id == *id && r.enabled));
+        running.retain(|_, (rule, task)| {
+            let current = rules.iter().any(|r| r == rule && r.enabled);
+            if !current {
+                task.abort();
+            }
+            current
+        });
+
+        for rule in rules.iter().filter(|r| r.enabled) {
+            if running.contains_key(&rule.id) {
+                continue;
+            }
+            // An edited rule is due at once.
+            if let Some(last) = checked.get(&rule.id)
+                && last.rule == *rule
+                && last.at.elapsed() < CHECK_INTERVAL
+            {
+                continue;
+            }
+            let (evaluated, permits) = (rule.clone(), Arc::clone(&permits));
+            let task = evaluations.spawn(async move {
+                let _permit = permits.acquire().await;
+                let status = evaluate_rule(&evaluated).await;
+                (evaluated, status)
+            });
+            running.insert(rule.id, (rule.clone(), task));
+        }
+    }
+}
+
+/// Raise a desktop notification for a rule that started firing. Where the
+/// notification server supports actions, clicking it sends `Open`.
+fn notify(rule: &AlertRule, status: &AlertStatus, events: Sender<AlertEvent>, repaint: Repaint) {
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .appname("Lumberjack Axe")
+        .summary(&format!("Alert: {}", rule.name))
+        .body(&format!(
+            "{} matching events in {} in the last {} min",
+            status.matches_label(),
+            rule.log_group,
+            rule.window_minutes.max(1)
+        ));
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let rule_id = rule.id;
+        notification.action("default", "Open");
+        match notification.show() {
+            Ok(handle) => {
+                thread::spawn(move || {
+                    handle.wait_for_action(|action| {
+                        if action == "default" {
+                            let _ = events.send(AlertEvent::Open(rule_id));
+                            wake(&repaint);
+                        }
+                    });
+                });
+            }
+            Err(e) => eprintln!("[axe] Failed to show notification: {e}"),
+        }
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        let _ = (events, repaint);
+        if let Err(e) = notification.show() {
+            eprintln!("[axe] Failed to show notification: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::parsers::MessageFormat;
+
+    fn entry(ts: i64, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: ts,
+            message: message.to_string(),
+            log_stream_name: None,
+            log_group_name: None,
+            event_id: None,
+            ingestion_time_millis: None,
+        }
+    }
+
+    #[test]
+    fn counts_matches_within_window() {
+        let entries = vec![
+            entry(5, r#"{"level":"error","msg":"too early"}"#),
+            entry(10, r#"{"level":"error","status":500}"#),
+            entry(20, r#"{"level":"info","status":200}"#),
+            entry(30, "ERROR plain text failure"),
+            entry(50, r#"{"level":"error","msg":"too late"}"#),
+        ];
+        let query = Query::parse("level:error").unwrap();
+        let parsers = ParserRegistry::default();
+        assert_eq!(
+            count_matches(&entries, query.as_ref(), &parsers, (10, 40)),
+            2
+        );
+        assert_eq!(count_matches(&entries, None, &parsers, (10, 40)), 3);
+
+        let query = Query::parse("status>=500").unwrap();
+        assert_eq!(
+            count_matches(&entries, query.as_ref(), &parsers, (0, 100)),
+            1
+        );
+
+        // Fields of disabled formats are not there to match.
+        let mut parsers = ParserRegistry::default();
+        parsers.set_enabled(MessageFormat::Json, false);
+        assert_eq!(
+            count_matches(&entries, query.as_ref(), &parsers, (0, 100)),
+            0
+        );
+    }
+
+    #[test]
+    fn capped_counts_are_marked() {
+        let mut status = AlertStatus {
+            rule_id: 0,
+            matches: 10_000,
+            capped: false,
+            firing: true,
+            range: (0, 1),
+            error: None,
+        };
+        assert_eq!(status.matches_label(), "10000");
+        status.capped = true;
+        assert_eq!(status.matches_label(), "10000+");
+    }
+
+    #[test]
+    fn worker_evaluates_rules_and_reports_statuses() {
+        let path = std::env::temp_dir().join(format!("axe-alert-{}.log", std::process::id()));
+        let now = chrono::Utc::now().to_rfc3339();
+        std::fs::write(
+            &path,
+            format!("{now} {{\"status\":500}}\n{now} {{\"status\":200}}\n"),
+        )
+        .unwrap();
+
+        let mut rule = AlertRule::new(7);
+        rule.log_group = format!("file://{}", path.display());
+        rule.query = "status>=500".to_string();
+        rule.threshold = 5;
+
+        let worker = crate::worker::spawn_worker_with_concurrency(1);
+        let (events, rx) = std::sync::mpsc::channel();
+        worker.send(crate::worker::WorkerRequest::SetAlertRules {
+            rules: vec![rule],
+            events,
+        });
+
+        let event = rx.recv_timeout(Duration::from_secs(5));
+        let _ = std::fs::remove_file(&path);
+        let Ok(AlertEvent::Status(status)) = event else {
+            panic!("expected a status, got {event:?}");
+        };
+        assert_eq!((status.rule_id, status.matches), (7, 1));
+        assert!(!status.firing && !status.capped && status.error.is_none());
+    }
+
+    #[test]
+    fn rule_describes_threshold_and_window() {
+        let mut rule = AlertRule::new(0);
+        rule.threshold = 10;
+        rule.window_minutes = 0;
+        assert_eq!(rule.describe(), "more than 10 in 1 min");
+        assert_eq!(rule.window(), Duration::from_secs(60));
+    }
+}
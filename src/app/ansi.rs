@@ -0,0 +1,232 @@
+use std::borrow::Cow;
+
+use egui::Color32;
+
+const ESC: char = '\u{1b}';
+
+/// Escaped forms of ESC left in messages that were JSON- or shell-escaped
+/// once too often; treated like the real control character.
+const LITERAL_ESCAPES: [&str; 3] = ["\\u001b", "\\x1b", "\\033"];
+
+/// Text attributes set by ANSI SGR (`ESC [ … m`) sequences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    pub fg: Option<Color32>,
+    pub bg: Option<Color32>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+/// A run of text sharing one style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnsiSegment {
+    pub text: String,
+    pub style: AnsiStyle,
+}
+
+pub fn has_ansi(text: &str) -> bool {
+    text.contains(ESC) || LITERAL_ESCAPES.iter().any(|e| text.contains(e))
+}
+
+/// `text` without escape sequences.
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !has_ansi(text) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(parse_ansi(text).into_iter().map(|s| s.text).collect())
+}
+
+/// Split `text` into styled segments. SGR sequences update the style; other
+/// CSI and OSC sequences are dropped.
+pub fn parse_ansi(text: &str) -> Vec<AnsiSegment> {
+    let mut segments = Vec::new();
+    let mut style = AnsiStyle::default();
+    let mut current = String::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let Some((after_esc, consumed)) = strip_escape(rest) else {
+            let ch = rest.chars().next().unwrap_or_default();
+            current.push(ch);
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        };
+
+        if let Some(csi) = after_esc.strip_prefix('[') {
+            // Parameters and intermediates, then one final byte in `@`..=`~`.
+            let Some(end) = csi.find(|c: char| ('@'..='~').contains(&c)) else {
+                rest = "";
+                continue;
+            };
+            if csi[end..].starts_with('m') {
+                if !current.is_empty() {
+                    segments.push(AnsiSegment {
+                        text: std::mem::take(&mut current),
+                        style,
+                    });
+                }
+                apply_sgr(&mut style, &csi[..end]);
+            }
+            rest = &csi[end + 1..];
+        } else if let Some(osc) = after_esc.strip_prefix(']') {
+            // Terminated by BEL or ESC `\`.
+            let end = osc.find(['\u{7}', ESC]).unwrap_or(osc.len());
+            let terminator = osc[end..].chars().next().map_or(0, char::len_utf8);
+            rest = osc[end + terminator..]
+                .strip_prefix('\\')
+                .unwrap_or(&osc[end + terminator..]);
+        } else {
+            // Lone ESC or an unsupported sequence: drop the ESC itself.
+            rest = &rest[consumed..];
+        }
+    }
+
+    if !current.is_empty() {
+        segments.push(AnsiSegment {
+            text: current,
+            style,
+        });
+    }
+    segments
+}
+
+/// If `text` starts with ESC (real or literal), the text after it and the
+/// length of the ESC.
+fn strip_escape(text: &str) -> Option<(&str, usize)> {
+    if let Some(rest) = text.strip_prefix(ESC) {
+        return Some((rest, ESC.len_utf8()));
+    }
+    LITERAL_ESCAPES
+        .iter()
+        .find_map(|e| text.strip_prefix(e).map(|rest| (rest, e.len())))
+}
+
+fn apply_sgr(style: &mut AnsiStyle, params: &str) {
+    let codes: Vec<u32> = if params.is_empty() {
+        vec![0]
+    } else {
+        params.split(';').map(|p| p.parse().unwrap_or(0)).collect()
+    };
+
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => *style = AnsiStyle::default(),
+            1 => style.bold = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            22 => style.bold = false,
+            23 => style.italic = false,
+            24 => style.underline = false,
+            code @ 30..=37 => style.fg = Some(basic_color(code - 30)),
+            code @ 90..=97 => style.fg = Some(basic_color(code - 90 + 8)),
+            39 => style.fg = None,
+            code @ 40..=47 => style.bg = Some(basic_color(code - 40)),
+            code @ 100..=107 => style.bg = Some(basic_color(code - 100 + 8)),
+            49 => style.bg = None,
+            code @ (38 | 48) => {
+                let (color, used) = extended_color(&codes[i + 1..]);
+                if let Some(color) = color {
+                    if code == 38 {
+                        style.fg = Some(color);
+                    } else {
+                        style.bg = Some(color);
+                    }
+                }
+                i += used;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// `5;n` (256-color) or `2;r;g;b` (truecolor); returns the color and how
+/// many parameters were consumed.
+fn extended_color(params: &[u32]) -> (Option<Color32>, usize) {
+    match params {
+        [5, n, ..] => (Some(palette_256(*n as u8)), 2),
+        [2, r, g, b, ..] => (Some(Color32::from_rgb(*r as u8, *g as u8, *b as u8)), 4),
+        _ => (None, params.len()),
+    }
+}
+
+/// The 16 standard colors (xterm defaults).
+fn basic_color(idx: u32) -> Color32 {
+    const COLORS: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 49, 49),
+        (13, 188, 121),
+        (229, 229, 16),
+        (36, 114, 200),
+        (188, 63, 188),
+        (17, 168, 205),
+        (229, 229, 229),
+        (102, 102, 102),
+        (241, 76, 76),
+        (35, 209, 139),
+        (245, 245, 67),
+        (59, 142, 234),
+        (214, 112, 214),
+        (41, 184, 219),
+        (255, 255, 255),
+    ];
+    let (r, g, b) = COLORS[idx as usize % 16];
+    Color32::from_rgb(r, g, b)
+}
+
+fn palette_256(n: u8) -> Color32 {
+    match n {
+        0..=15 => basic_color(n as u32),
+        16..=231 => {
+            let n = n - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            Color32::from_rgb(level(n / 36), level((n / 6) % 6), level(n % 6))
+        }
+        232..=255 => {
+            let gray = 8 + (n - 232) * 10;
+            Color32::from_gray(gray)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_basic_sgr_colors_and_reset() {
+        let segments = parse_ansi("\u{1b}[31mred\u{1b}[0m plain \u{1b}[1;4;92mbright\u{1b}[m");
+        assert_eq!(segments.len(), 3);
+
+        assert_eq!(segments[0].text, "red");
+        assert_eq!(segments[0].style.fg, Some(basic_color(1)));
+        assert_eq!(segments[1].text, " plain ");
+        assert_eq!(segments[1].style, AnsiStyle::default());
+        assert_eq!(segments[2].text, "bright");
+        assert_eq!(segments[2].style.fg, Some(basic_color(10)));
+        assert!(segments[2].style.bold);
+        assert!(segments[2].style.underline);
+    }
+
+    #[test]
+    fn parses_256_and_truecolor() {
+        let segments = parse_ansi("\u{1b}[38;5;196ma\u{1b}[48;2;1;2;3;1mb");
+        assert_eq!(segments[0].style.fg, Some(Color32::from_rgb(255, 0, 0)));
+        assert_eq!(segments[1].style.bg, Some(Color32::from_rgb(1, 2, 3)));
+        assert!(segments[1].style.bold);
+        assert_eq!(palette_256(232), Color32::from_gray(8));
+    }
+
+    #[test]
+    fn strips_sgr_other_csi_osc_and_literal_escapes() {
+        assert_eq!(
+            strip_ansi("\u{1b}[2K\u{1b}]0;title\u{7}\u{1b}[33mWARN\u{1b}[0m done"),
+            "WARN done"
+        );
+        assert_eq!(strip_ansi("\\u001b[31mERROR\\u001b[0m x"), "ERROR x");
+        assert!(matches!(strip_ansi("plain"), Cow::Borrowed("plain")));
+        assert_eq!(strip_ansi("unterminated \u{1b}[31"), "unterminated ");
+    }
+}
@@ -0,0 +1,111 @@
+use crate::app::export::entry_to_json;
+use crate::app::state::format_timestamp_millis;
+use crate::aws::LogEntry;
+
+/// Formats offered by the copy / export actions of the log list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    RawText,
+    Json,
+    MarkdownTable,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 3] = [
+        CopyFormat::RawText,
+        CopyFormat::Json,
+        CopyFormat::MarkdownTable,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CopyFormat::RawText => "Raw text",
+            CopyFormat::Json => "JSON",
+            CopyFormat::MarkdownTable => "Markdown table",
+        }
+    }
+}
+
+/// Render entries in the given format, ready for the clipboard or a file.
+pub fn format_entries(entries: &[&LogEntry], format: CopyFormat, use_local_time: bool) -> String {
+    match format {
+        CopyFormat::RawText => entries
+            .iter()
+            .map(|e| e.message.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        CopyFormat::Json => {
+            let items: Vec<_> = entries.iter().map(|e| entry_to_json(e)).collect();
+            serde_json::to_string_pretty(&items).unwrap_or_default()
+        }
+        CopyFormat::MarkdownTable => {
+            let mut out = String::from("| Timestamp | Stream | Message |\n|---|---|---|\n");
+            for e in entries {
+                out.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    format_timestamp_millis(e.timestamp_millis, use_local_time),
+                    escape_markdown_cell(e.log_stream_name.as_deref().unwrap_or("")),
+                    escape_markdown_cell(&e.message),
+                ));
+            }
+            out
+        }
+    }
+}
+
+fn escape_markdown_cell(s: &str) -> String {
+    s.trim_end()
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: 1_700_000_000_000,
+            message: message.to_string(),
+            log_stream_name: Some("stream-1".to_string()),
+            log_group_name: Some("/aws/lambda/foo".to_string()),
+            event_id: Some("42".to_string()),
+            ingestion_time_millis: None,
+        }
+    }
+
+    #[test]
+    fn raw_text_joins_messages_by_line() {
+        let a = entry("first\n");
+        let b = entry("second");
+        assert_eq!(
+            format_entries(&[&a, &b], CopyFormat::RawText, false),
+            "first\nsecond"
+        );
+    }
+
+    #[test]
+    fn json_keeps_all_entry_fields() {
+        let a = entry("hello");
+        let out = format_entries(&[&a], CopyFormat::Json, false);
+        let v: serde_json::Value = serde_json::from_str(&out).expect("valid JSON");
+
+        assert_eq!(v[0]["message"], "hello");
+        assert_eq!(v[0]["logStreamName"], "stream-1");
+        assert_eq!(v[0]["logGroupName"], "/aws/lambda/foo");
+        assert_eq!(v[0]["eventId"], "42");
+        assert_eq!(v[0]["timestamp"], 1_700_000_000_000_i64);
+        assert!(v[0]["ingestionTime"].is_null());
+    }
+
+    #[test]
+    fn markdown_table_escapes_pipes_and_newlines() {
+        let a = entry("a | b\nc");
+        let out = format_entries(&[&a], CopyFormat::MarkdownTable, false);
+
+        assert!(out.starts_with("| Timestamp | Stream | Message |\n|---|---|---|\n"));
+        assert!(out.contains("| stream-1 | a \\| b<br>c |"));
+    }
+}
@@ -0,0 +1,384 @@
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use std::time::Instant;
+
+use regex::Regex;
+use serde_json::Value as JsonValue;
+
+use crate::app::json_tree::{PathSegment, flatten_json, parse_path, value_to_copy_text};
+use crate::app::state::try_parse_json;
+use crate::app::tab::FetchProgress;
+use crate::aws::{LogEntry, Progress, SearchResults};
+use crate::local_logs::local_path_from_group;
+use crate::worker::{RequestId, WorkerHandle, WorkerRequest};
+
+static LAMBDA_REQUEST_ID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"RequestId:\s*([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12})")
+        .expect("valid regex")
+});
+
+/// Lambda runtime text format: `<timestamp>\t<request id>\t<level>\t<message>`.
+static LAMBDA_TEXT_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\S+\t([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12})\t",
+    )
+    .expect("valid regex")
+});
+
+static XRAY_ROOT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Root=(1-[0-9a-fA-F]{8}-[0-9a-fA-F]{24})").expect("valid regex"));
+
+/// Field names (lowercased, punctuation removed) that usually hold an ID
+/// shared by every log line of one request.
+const ID_KEY_SUFFIXES: [&str; 4] = ["requestid", "traceid", "correlationid", "xrayid"];
+
+/// A value worth searching for, with where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId {
+    /// JSON path, or a description such as `Lambda RequestId`.
+    pub source: String,
+    pub value: String,
+}
+
+/// Suggest request / trace IDs found in a message: ID-like JSON fields,
+/// Lambda `RequestId:` prefixes and X-Ray `Root=` segments.
+pub fn suggest_ids(message: &str) -> Vec<CorrelationId> {
+    let mut found = Vec::new();
+
+    if let Some(json) = try_parse_json(message) {
+        for (path, value) in flatten_json(&json) {
+            if !matches!(value, JsonValue::String(_) | JsonValue::Number(_)) {
+                continue;
+            }
+            let is_id = match parse_path(&path).as_deref().and_then(<[_]>::last) {
+                Some(PathSegment::Key(key)) => is_id_key(key),
+                _ => false,
+            };
+            let value = value_to_copy_text(&value);
+            if is_id && !value.trim().is_empty() {
+                found.push(CorrelationId {
+                    source: path,
+                    value,
+                });
+            }
+        }
+    }
+
+    for re in [&*LAMBDA_REQUEST_ID, &*LAMBDA_TEXT_PREFIX] {
+        if let Some(caps) = re.captures(message) {
+            found.push(CorrelationId {
+                source: "Lambda RequestId".to_string(),
+                value: caps[1].to_string(),
+            });
+        }
+    }
+
+    for caps in XRAY_ROOT.captures_iter(message) {
+        found.push(CorrelationId {
+            source: "X-Ray Root".to_string(),
+            value: caps[1].to_string(),
+        });
+    }
+
+    let mut seen = BTreeSet::new();
+    found.retain(|id| seen.insert(id.value.clone()));
+    found
+}
+
+fn is_id_key(key: &str) -> bool {
+    let normalized: String = key
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    ID_KEY_SUFFIXES.iter().any(|s| normalized.ends_with(s))
+}
+
+/// State of the trace window: one ID searched across several log groups.
+pub struct TraceState {
+    pub open: bool,
+    pub term: String,
+    /// Timestamp of the event the trace started from.
+    pub center_millis: i64,
+    /// Minutes searched on each side of `center_millis`.
+    pub window_mins: i64,
+    pub profile: String,
+    pub region: String,
+    /// Log groups offered for the search.
+    pub candidate_groups: Vec<String>,
+    pub selected_groups: BTreeSet<String>,
+    pub suggestions: Vec<CorrelationId>,
+    /// Merged, time-ordered matches.
+    pub entries: Vec<LogEntry>,
+    /// Why each group that could not be searched failed.
+    pub failures: Vec<String>,
+    pub selected_entry: Option<usize>,
+    pub is_searching: bool,
+    pub last_error: Option<String>,
+    pub rx: Option<Receiver<SearchResults>>,
+    pub request: Option<RequestId>,
+    /// Pages and retries of the search in flight.
+    pub progress_rx: Option<Receiver<Progress>>,
+    pub progress: Option<FetchProgress>,
+}
+
+impl Default for TraceState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            term: String::new(),
+            center_millis: 0,
+            window_mins: 15,
+            profile: String::new(),
+            region: String::new(),
+            candidate_groups: Vec::new(),
+            selected_groups: BTreeSet::new(),
+            suggestions: Vec::new(),
+            entries: Vec::new(),
+            failures: Vec::new(),
+            selected_entry: None,
+            is_searching: false,
+            last_error: None,
+            rx: None,
+            request: None,
+            progress_rx: None,
+            progress: None,
+        }
+    }
+}
+
+impl TraceState {
+    /// Prepare a trace for `term` around `entry`, offering `groups` (the
+    /// entry's own group is always selected). Previously chosen groups that
+    /// are still offered stay selected.
+    pub fn open_for(
+        &mut self,
+        term: String,
+        entry: &LogEntry,
+        profile: &str,
+        region: &str,
+        groups: &[String],
+    ) {
+        let own_group = entry.log_group_name.clone().unwrap_or_default();
+
+        let mut candidates: Vec<String> = groups
+            .iter()
+            .chain(std::iter::once(&own_group))
+            .filter(|g| !g.is_empty() && local_path_from_group(g).is_none())
+            .cloned()
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        self.selected_groups.retain(|g| candidates.contains(g));
+        if candidates.contains(&own_group) {
+            self.selected_groups.insert(own_group);
+        }
+
+        self.open = true;
+        self.term = term;
+        self.center_millis = entry.timestamp_millis;
+        self.profile = profile.to_string();
+        self.region = region.to_string();
+        self.candidate_groups = candidates;
+        self.suggestions = suggest_ids(&entry.message);
+        self.entries.clear();
+        self.failures.clear();
+        self.selected_entry = None;
+        self.last_error = None;
+    }
+
+    /// Absolute `(start, end)` range searched.
+    pub fn range(&self) -> (i64, i64) {
+        let half = self.window_mins.max(1) * 60_000;
+        (self.center_millis - half, self.center_millis + half)
+    }
+
+    pub fn start_search(&mut self, worker: &WorkerHandle) {
+        let term = self.term.trim().to_string();
+        if term.is_empty() {
+            self.last_error = Some("Enter an ID to trace.".to_string());
+            return;
+        }
+        if self.selected_groups.is_empty() {
+            self.last_error = Some("Select at least one log group.".to_string());
+            return;
+        }
+
+        self.cancel(worker);
+        let (tx, rx) = channel::<SearchResults>();
+        let (progress_tx, progress_rx) = channel();
+        let id = worker.send(WorkerRequest::SearchLogGroups {
+            profile: Some(self.profile.clone()).filter(|p| !p.trim().is_empty()),
+            region: Some(self.region.clone()).filter(|r| !r.trim().is_empty()),
+            log_groups: self.selected_groups.iter().cloned().collect(),
+            term,
+            range: self.range(),
+            limit: 1_000,
+            respond_to: tx,
+            progress: Some(progress_tx),
+        });
+
+        self.rx = Some(rx);
+        self.progress_rx = Some(progress_rx);
+        self.progress = Some(FetchProgress::new(Instant::now()));
+        self.request = Some(id);
+        self.is_searching = true;
+        self.last_error = None;
+        self.failures.clear();
+    }
+
+    /// Abort the search in flight; its response, if any, is dropped.
+    pub fn cancel(&mut self, worker: &WorkerHandle) {
+        if let Some(id) = self.request.take() {
+            worker.cancel(id);
+        }
+        self.end_search();
+    }
+
+    fn end_search(&mut self) {
+        self.rx = None;
+        self.request = None;
+        self.progress_rx = None;
+        self.progress = None;
+        self.is_searching = false;
+    }
+
+    pub fn poll(&mut self) {
+        if let (Some(rx), Some(progress)) = (&self.progress_rx, &mut self.progress) {
+            for event in rx.try_iter() {
+                progress.record(&event);
+            }
+        }
+        let Some(rx) = self.rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(results) => {
+                self.entries = results.entries;
+                self.failures = results.failures.iter().map(|e| e.to_string()).collect();
+                self.selected_entry = None;
+                self.end_search();
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.last_error = Some("Trace search aborted".to_string());
+                self.end_search();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(message: &str) -> Vec<(String, String)> {
+        suggest_ids(message)
+            .into_iter()
+            .map(|id| (id.source, id.value))
+            .collect()
+    }
+
+    #[test]
+    fn suggests_id_like_json_fields() {
+        let found = values(
+            r#"{"level":"info","requestId":"abc-123","ctx":{"trace_id":42},"headers":{"X-Amzn-Trace-Id":"Root=1-5759e988-bd862e3fe1be46a994272793;Sampled=1"},"user":"bob"}"#,
+        );
+
+        assert_eq!(
+            found,
+            vec![
+                ("$.ctx.trace_id".to_string(), "42".to_string()),
+                (
+                    "$.headers.X-Amzn-Trace-Id".to_string(),
+                    "Root=1-5759e988-bd862e3fe1be46a994272793;Sampled=1".to_string()
+                ),
+                ("$.requestId".to_string(), "abc-123".to_string()),
+                (
+                    "X-Ray Root".to_string(),
+                    "1-5759e988-bd862e3fe1be46a994272793".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn suggests_lambda_request_ids_from_text() {
+        let id = "8f5f8f7a-1c2b-4d3e-9f00-0123456789ab";
+        assert_eq!(
+            values(&format!("END RequestId: {id}")),
+            vec![("Lambda RequestId".to_string(), id.to_string())]
+        );
+        assert_eq!(
+            values(&format!("2024-01-01T00:00:00.000Z\t{id}\tERROR\tboom")),
+            vec![("Lambda RequestId".to_string(), id.to_string())]
+        );
+        assert!(suggest_ids("nothing to see").is_empty());
+    }
+
+    #[test]
+    fn open_for_selects_entry_group_and_keeps_previous_choices() {
+        let entry = LogEntry {
+            timestamp_millis: 10 * 60_000,
+            message: "END RequestId: 8f5f8f7a-1c2b-4d3e-9f00-0123456789ab".to_string(),
+            log_stream_name: None,
+            log_group_name: Some("/aws/lambda/a".to_string()),
+            event_id: None,
+            ingestion_time_millis: None,
+        };
+        let groups = vec!["/aws/lambda/b".to_string(), "/aws/lambda/c".to_string()];
+
+        let mut trace = TraceState::default();
+        trace.selected_groups.insert("/aws/lambda/b".to_string());
+        trace.selected_groups.insert("/gone".to_string());
+        trace.open_for("x".to_string(), &entry, "p", "r", &groups);
+
+        assert!(trace.open);
+        assert_eq!(trace.candidate_groups.len(), 3);
+        assert_eq!(
+            trace.selected_groups,
+            BTreeSet::from(["/aws/lambda/a".to_string(), "/aws/lambda/b".to_string()])
+        );
+        assert_eq!(trace.suggestions.len(), 1);
+        assert_eq!(trace.range(), (-5 * 60_000, 25 * 60_000));
+    }
+
+    #[test]
+    fn failed_groups_are_reported_alongside_matches() {
+        use aws_sdk_cloudwatchlogs::types::error::ResourceNotFoundException;
+
+        let (tx, rx) = channel();
+        let mut trace = TraceState {
+            rx: Some(rx),
+            is_searching: true,
+            ..TraceState::default()
+        };
+        tx.send(SearchResults {
+            entries: vec![LogEntry {
+                timestamp_millis: 1,
+                message: "req-1 done".to_string(),
+                log_stream_name: None,
+                log_group_name: Some("/aws/lambda/api".to_string()),
+                event_id: None,
+                ingestion_time_millis: None,
+            }],
+            failures: vec![crate::aws::AwsLogError::CloudWatch {
+                log_group: "/aws/lambda/gone".to_string(),
+                source: aws_sdk_cloudwatchlogs::Error::ResourceNotFoundException(
+                    ResourceNotFoundException::builder()
+                        .message("The specified log group does not exist.")
+                        .build(),
+                ),
+            }],
+        })
+        .unwrap();
+
+        trace.poll();
+        assert!(!trace.is_searching && trace.last_error.is_none());
+        assert_eq!(trace.entries.len(), 1);
+        assert_eq!(trace.failures.len(), 1);
+        assert!(trace.failures[0].contains("/aws/lambda/gone"));
+    }
+}
@@ -0,0 +1,208 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::app::ansi::strip_ansi;
+use crate::app::correlation::suggest_ids;
+use crate::app::json_tree::{JsonTreeAction, flatten_json, show_json_tree, value_to_copy_text};
+use crate::app::state::{FieldFilter, format_timestamp_millis, try_parse_json, try_pretty_json};
+
+pub fn draw_detail_panel(app: &mut App, ctx: &egui::Context) {
+    let theme = app.theme;
+    let tab_idx = app.focused_tab_index();
+    let view = &mut app.tabs[tab_idx].logs_view;
+    let Some(entry) = view.selected().cloned() else {
+        view.selected_entry = None;
+        return;
+    };
+    let entry_idx = view.selected_entry.unwrap_or_default();
+    let level = view.level_of(entry_idx);
+    let parsed = view.parsed(entry_idx).cloned();
+    // Folded stack traces show all of their lines.
+    let message = view
+        .selected_entry
+        .map(|idx| strip_ansi(&view.logical_message(idx)).into_owned())
+        .unwrap_or_default();
+
+    let mut tree_actions = Vec::new();
+    let mut close = false;
+    let mut trace_term = None;
+    let suggestions = suggest_ids(&entry.message);
+
+    egui::SidePanel::right("entry_detail")
+        .resizable(true)
+        .default_width(380.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Entry");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("✕").on_hover_text("Close (Esc)").clicked() {
+                        close = true;
+                    }
+                });
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    egui::Grid::new("entry_detail_meta")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("Level");
+                            ui.colored_label(level.color(theme), level.label());
+                            ui.end_row();
+
+                            ui.label("Time (UTC)");
+                            ui.label(format_timestamp_millis(entry.timestamp_millis, false));
+                            ui.end_row();
+
+                            ui.label("Time (local)");
+                            ui.label(format_timestamp_millis(entry.timestamp_millis, true));
+                            ui.end_row();
+
+                            ui.label("Ingestion delay");
+                            ui.label(match entry.ingestion_time_millis {
+                                Some(ingested) => {
+                                    format_delay_millis(ingested - entry.timestamp_millis)
+                                }
+                                None => "-".to_string(),
+                            });
+                            ui.end_row();
+
+                            meta_row(ui, "Stream", entry.log_stream_name.as_deref());
+                            meta_row(ui, "Group", entry.log_group_name.as_deref());
+                            meta_row(ui, "Event ID", entry.event_id.as_deref());
+                        });
+
+                    if !suggestions.is_empty() {
+                        ui.separator();
+                        ui.label("Correlation IDs");
+                        egui::Grid::new("entry_detail_ids")
+                            .num_columns(3)
+                            .show(ui, |ui| {
+                                for id in &suggestions {
+                                    ui.small(&id.source);
+                                    ui.monospace(&id.value);
+                                    if ui
+                                        .small_button("Trace")
+                                        .on_hover_text("Search the log groups for this ID")
+                                        .clicked()
+                                    {
+                                        trace_term = Some(id.value.clone());
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    }
+
+                    ui.separator();
+
+                    ui.label("Message");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut message.as_str())
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY),
+                    );
+
+                    if let Some(parsed) = &parsed {
+                        ui.separator();
+                        ui.label(format!("Fields ({})", parsed.format.label()));
+                        show_json_tree(
+                            ui,
+                            ("detail_json", &entry.event_id, entry.timestamp_millis),
+                            &parsed.fields,
+                            &mut tree_actions,
+                        );
+
+                        egui::CollapsingHeader::new("Flattened fields")
+                            .id_salt("detail_flat_fields")
+                            .show(ui, |ui| {
+                                egui::Grid::new("entry_detail_fields")
+                                    .num_columns(2)
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for (path, value) in flatten_json(&parsed.fields) {
+                                            ui.monospace(path);
+                                            ui.label(value_to_copy_text(&value));
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                    }
+
+                    if try_parse_json(&entry.message).is_some() {
+                        egui::CollapsingHeader::new("Pretty JSON")
+                            .id_salt("detail_pretty_json")
+                            .show(ui, |ui| {
+                                let pretty = try_pretty_json(&entry.message).unwrap_or_default();
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut pretty.as_str())
+                                        .font(egui::TextStyle::Monospace)
+                                        .desired_width(f32::INFINITY),
+                                );
+                            });
+
+                        egui::CollapsingHeader::new("Raw JSON")
+                            .id_salt("detail_raw_json")
+                            .show(ui, |ui| {
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut entry.message.trim())
+                                        .font(egui::TextStyle::Monospace)
+                                        .desired_width(f32::INFINITY),
+                                );
+                            });
+                    }
+                });
+        });
+
+    let view = &mut app.tabs[tab_idx].logs_view;
+    if close {
+        view.selected_entry = None;
+    }
+
+    for action in tree_actions {
+        match action {
+            JsonTreeAction::FilterByValue { path, value } => {
+                view.add_field_filter(FieldFilter { path, value });
+            }
+            JsonTreeAction::TraceValue { value } => trace_term = Some(value),
+        }
+    }
+
+    if let Some(term) = trace_term {
+        app.open_trace(tab_idx, term);
+    }
+}
+
+fn meta_row(ui: &mut egui::Ui, label: &str, value: Option<&str>) {
+    ui.label(label);
+    ui.add(egui::Label::new(value.unwrap_or("-")).wrap());
+    ui.end_row();
+}
+
+/// Human-friendly delay such as `850ms`, `2.4s` or `3m 12s`.
+pub fn format_delay_millis(delay_millis: i64) -> String {
+    let sign = if delay_millis < 0 { "-" } else { "" };
+    let ms = delay_millis.unsigned_abs();
+    if ms < 1_000 {
+        format!("{sign}{ms}ms")
+    } else if ms < 60_000 {
+        format!("{sign}{:.1}s", ms as f64 / 1_000.0)
+    } else {
+        format!("{sign}{}m {}s", ms / 60_000, (ms % 60_000) / 1_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_delay_millis_picks_a_readable_unit() {
+        assert_eq!(format_delay_millis(850), "850ms");
+        assert_eq!(format_delay_millis(2_400), "2.4s");
+        assert_eq!(format_delay_millis(192_000), "3m 12s");
+        assert_eq!(format_delay_millis(-20), "-20ms");
+    }
+}
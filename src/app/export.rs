@@ -0,0 +1,325 @@
+#[cfg(feature = "gui")]
+use std::fs::File;
+#[cfg(feature = "gui")]
+use std::io::BufWriter;
+use std::io::{self, Write};
+#[cfg(feature = "gui")]
+use std::path::Path;
+
+use serde_json::{Value as JsonValue, json};
+#[cfg(feature = "gui")]
+use thiserror::Error;
+
+use crate::app::json_tree::lookup_path;
+use crate::app::state::{format_timestamp_millis, try_parse_json};
+use crate::aws::LogEntry;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    not(feature = "gui"),
+    allow(dead_code, reason = "only the app exports plain text")
+)]
+pub enum ExportFormat {
+    JsonLines,
+    Csv,
+    PlainText,
+}
+
+#[cfg(feature = "gui")]
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::JsonLines,
+        ExportFormat::Csv,
+        ExportFormat::PlainText,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::JsonLines => "JSON Lines",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::PlainText => "Plain text",
+        }
+    }
+
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            ExportFormat::JsonLines => "jsonl",
+            ExportFormat::Csv => "csv",
+            ExportFormat::PlainText => "log",
+        }
+    }
+}
+
+/// A CSV column: one of the entry fields, or a JSON path into the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(feature = "gui"),
+    allow(dead_code, reason = "the CLI writes a fixed set of columns")
+)]
+pub enum CsvColumn {
+    Timestamp,
+    LogStream,
+    LogGroup,
+    EventId,
+    IngestionTime,
+    Message,
+    Field(String),
+}
+
+impl CsvColumn {
+    /// The built-in columns, in their default order.
+    #[cfg(feature = "gui")]
+    pub const STANDARD: [CsvColumn; 6] = [
+        CsvColumn::Timestamp,
+        CsvColumn::LogStream,
+        CsvColumn::LogGroup,
+        CsvColumn::EventId,
+        CsvColumn::IngestionTime,
+        CsvColumn::Message,
+    ];
+
+    pub fn header(&self) -> String {
+        match self {
+            CsvColumn::Timestamp => "timestamp".to_string(),
+            CsvColumn::LogStream => "logStreamName".to_string(),
+            CsvColumn::LogGroup => "logGroupName".to_string(),
+            CsvColumn::EventId => "eventId".to_string(),
+            CsvColumn::IngestionTime => "ingestionTime".to_string(),
+            CsvColumn::Message => "message".to_string(),
+            CsvColumn::Field(path) => path.clone(),
+        }
+    }
+
+    fn value(&self, entry: &LogEntry, json: Option<&JsonValue>, use_local_time: bool) -> String {
+        match self {
+            CsvColumn::Timestamp => format_timestamp_millis(entry.timestamp_millis, use_local_time),
+            CsvColumn::LogStream => entry.log_stream_name.clone().unwrap_or_default(),
+            CsvColumn::LogGroup => entry.log_group_name.clone().unwrap_or_default(),
+            CsvColumn::EventId => entry.event_id.clone().unwrap_or_default(),
+            CsvColumn::IngestionTime => entry
+                .ingestion_time_millis
+                .map(|ts| format_timestamp_millis(ts, use_local_time))
+                .unwrap_or_default(),
+            CsvColumn::Message => entry.message.trim_end().to_string(),
+            CsvColumn::Field(path) => match json.and_then(|v| lookup_path(v, path)).as_deref() {
+                Some(JsonValue::String(s)) => s.clone(),
+                Some(JsonValue::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// Only used for CSV.
+    pub csv_columns: Vec<CsvColumn>,
+    pub use_local_time: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::JsonLines,
+            csv_columns: vec![
+                CsvColumn::Timestamp,
+                CsvColumn::LogStream,
+                CsvColumn::Message,
+            ],
+            use_local_time: false,
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("failed to write export to {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// JSON object with every `LogEntry` field, as written to JSON Lines.
+pub fn entry_to_json(entry: &LogEntry) -> JsonValue {
+    json!({
+        "timestamp": entry.timestamp_millis,
+        "message": entry.message,
+        "logStreamName": entry.log_stream_name,
+        "logGroupName": entry.log_group_name,
+        "eventId": entry.event_id,
+        "ingestionTime": entry.ingestion_time_millis,
+    })
+}
+
+/// Write entries one by one to `out`; returns the number written.
+#[cfg(feature = "gui")]
+pub fn write_entries<W: Write>(
+    out: &mut W,
+    entries: &[LogEntry],
+    options: &ExportOptions,
+) -> io::Result<usize> {
+    write_header(out, options)?;
+    for entry in entries {
+        write_entry(out, entry, options)?;
+    }
+    out.flush()?;
+    Ok(entries.len())
+}
+
+/// The CSV header row; other formats have none.
+pub fn write_header<W: Write>(out: &mut W, options: &ExportOptions) -> io::Result<()> {
+    if options.format == ExportFormat::Csv {
+        let header: Vec<_> = options.csv_columns.iter().map(|c| c.header()).collect();
+        write_csv_row(out, &header)?;
+    }
+    Ok(())
+}
+
+/// Write a single entry (one line, or one CSV row).
+pub fn write_entry<W: Write>(
+    out: &mut W,
+    entry: &LogEntry,
+    options: &ExportOptions,
+) -> io::Result<()> {
+    match options.format {
+        ExportFormat::JsonLines => {
+            serde_json::to_writer(&mut *out, &entry_to_json(entry))?;
+            out.write_all(b"\n")
+        }
+        ExportFormat::Csv => {
+            let needs_json = options
+                .csv_columns
+                .iter()
+                .any(|c| matches!(c, CsvColumn::Field(_)));
+            let json = if needs_json {
+                try_parse_json(&entry.message)
+            } else {
+                None
+            };
+            let row: Vec<_> = options
+                .csv_columns
+                .iter()
+                .map(|c| c.value(entry, json.as_ref(), options.use_local_time))
+                .collect();
+            write_csv_row(out, &row)
+        }
+        ExportFormat::PlainText => {
+            let ts = format_timestamp_millis(entry.timestamp_millis, options.use_local_time);
+            match &entry.log_stream_name {
+                Some(stream) => writeln!(out, "{ts} [{stream}] {}", entry.message.trim_end()),
+                None => writeln!(out, "{ts} {}", entry.message.trim_end()),
+            }
+        }
+    }
+}
+
+/// Create `path` and stream the export into it.
+#[cfg(feature = "gui")]
+pub fn export_to_path(
+    path: &Path,
+    entries: &[LogEntry],
+    options: &ExportOptions,
+) -> Result<usize, ExportError> {
+    let to_err = |source| ExportError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    let file = File::create(path).map_err(to_err)?;
+    let mut out = BufWriter::new(file);
+    write_entries(&mut out, entries, options).map_err(to_err)
+}
+
+/// One CSV row, quoting fields as needed.
+pub fn write_csv_row<W: Write>(out: &mut W, fields: &[String]) -> io::Result<()> {
+    let line: Vec<_> = fields.iter().map(|f| escape_csv_field(f)).collect();
+    writeln!(out, "{}", line.join(","))
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(all(test, feature = "gui"))]
+mod tests {
+    use super::*;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: 1_700_000_000_123,
+            message: message.to_string(),
+            log_stream_name: Some("stream-1".to_string()),
+            log_group_name: Some("/aws/lambda/foo".to_string()),
+            event_id: Some("42".to_string()),
+            ingestion_time_millis: Some(1_700_000_000_500),
+        }
+    }
+
+    fn export(entries: &[LogEntry], options: &ExportOptions) -> String {
+        let mut out = Vec::new();
+        let n = write_entries(&mut out, entries, options).expect("write to Vec");
+        assert_eq!(n, entries.len());
+        String::from_utf8(out).expect("utf8")
+    }
+
+    #[test]
+    fn json_lines_preserves_every_field() {
+        let out = export(&[entry("a"), entry("b")], &ExportOptions::default());
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let v: JsonValue = serde_json::from_str(lines[0]).expect("valid JSON");
+        assert_eq!(v["message"], "a");
+        assert_eq!(v["timestamp"], 1_700_000_000_123_i64);
+        assert_eq!(v["logStreamName"], "stream-1");
+        assert_eq!(v["logGroupName"], "/aws/lambda/foo");
+        assert_eq!(v["eventId"], "42");
+        assert_eq!(v["ingestionTime"], 1_700_000_000_500_i64);
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_extracts_json_paths() {
+        let options = ExportOptions {
+            format: ExportFormat::Csv,
+            csv_columns: vec![
+                CsvColumn::EventId,
+                CsvColumn::Field("$.level".to_string()),
+                CsvColumn::Field("$.ctx.status".to_string()),
+                CsvColumn::Message,
+            ],
+            use_local_time: false,
+        };
+        let out = export(
+            &[
+                entry(r#"{"level":"error","ctx":{"status":500}}"#),
+                entry("plain, \"quoted\""),
+            ],
+            &options,
+        );
+        let lines: Vec<_> = out.lines().collect();
+
+        assert_eq!(lines[0], "eventId,$.level,$.ctx.status,message");
+        assert_eq!(
+            lines[1],
+            r#"42,error,500,"{""level"":""error"",""ctx"":{""status"":500}}""#
+        );
+        assert_eq!(lines[2], r#"42,,,"plain, ""quoted""""#);
+    }
+
+    #[test]
+    fn plain_text_prefixes_formatted_timestamp_and_stream() {
+        let options = ExportOptions {
+            format: ExportFormat::PlainText,
+            ..ExportOptions::default()
+        };
+        let out = export(&[entry("hello\n")], &options);
+        assert_eq!(out, "2023-11-14 22:13:20.123Z [stream-1] hello\n");
+    }
+}
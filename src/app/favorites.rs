@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::tab::TabConfig;
+
+/// Favorites offered in the tray menu.
+pub const TRAY_FAVORITES: usize = 5;
+
+/// Saved queries, most recently used first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Favorites {
+    pub items: Vec<TabConfig>,
+}
+
+impl Favorites {
+    /// Index of the favorite querying the same source as `config`; titles
+    /// and display options do not matter.
+    pub fn position(&self, config: &TabConfig) -> Option<usize> {
+        self.items.iter().position(|f| {
+            f.profile == config.profile
+                && f.region == config.region
+                && f.log_group == config.log_group
+                && f.filter_text == config.filter_text
+                && f.query_text == config.query_text
+        })
+    }
+
+    pub fn contains(&self, config: &TabConfig) -> bool {
+        self.position(config).is_some()
+    }
+
+    /// Add `config` as the most recent favorite, or remove it if present.
+    pub fn toggle(&mut self, config: TabConfig) {
+        match self.position(&config) {
+            Some(idx) => {
+                self.items.remove(idx);
+            }
+            None => self.items.insert(0, config),
+        }
+    }
+
+    /// Mark the favorite at `idx` as just used and return it.
+    pub fn touch(&mut self, idx: usize) -> Option<TabConfig> {
+        if idx >= self.items.len() {
+            return None;
+        }
+        let config = self.items.remove(idx);
+        self.items.insert(0, config.clone());
+        Some(config)
+    }
+
+    /// Menu labels of the `n` most recent favorites.
+    pub fn recent_labels(&self, n: usize) -> Vec<String> {
+        self.items
+            .iter()
+            .take(n)
+            .map(|f| {
+                if f.log_group.is_empty() || f.title == f.log_group {
+                    f.title.clone()
+                } else {
+                    format!("{} ({})", f.title, f.log_group)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tab::Tab;
+
+    fn config(title: &str, group: &str) -> TabConfig {
+        let mut tab = Tab::new(0, title);
+        tab.logs_view.log_group = group.to_string();
+        tab.to_config()
+    }
+
+    #[test]
+    fn toggle_adds_most_recent_first_and_removes() {
+        let mut favorites = Favorites::default();
+        favorites.toggle(config("api", "/aws/lambda/api"));
+        favorites.toggle(config("Workers", "/ecs/workers"));
+        assert_eq!(
+            favorites.recent_labels(TRAY_FAVORITES),
+            vec!["Workers (/ecs/workers)", "api (/aws/lambda/api)"]
+        );
+
+        // Same source under another title is the same favorite.
+        assert!(favorites.contains(&config("renamed", "/ecs/workers")));
+        favorites.toggle(config("renamed", "/ecs/workers"));
+        assert_eq!(favorites.items.len(), 1);
+    }
+
+    #[test]
+    fn touch_moves_favorite_to_front() {
+        let mut favorites = Favorites::default();
+        favorites.toggle(config("a", "/a"));
+        favorites.toggle(config("b", "/b"));
+        assert_eq!(favorites.touch(1).map(|c| c.title), Some("a".to_string()));
+        assert_eq!(favorites.items[0].title, "a");
+        assert_eq!(favorites.touch(5), None);
+    }
+}
@@ -0,0 +1,398 @@
+use eframe::egui;
+
+use crate::app::level::LogLevel;
+use crate::app::patterns::PatternFilter;
+use crate::app::state::{FieldFilter, LogsViewState, Theme, format_timestamp_millis};
+use crate::app::tab::Tab;
+use crate::worker::WorkerHandle;
+
+/// Levels in stacking order, bottom to top.
+pub const STACK_ORDER: [LogLevel; 5] = [
+    LogLevel::Error,
+    LogLevel::Warn,
+    LogLevel::Info,
+    LogLevel::Debug,
+    LogLevel::Unknown,
+];
+
+/// Bucket widths we are willing to use, in milliseconds.
+const NICE_BUCKETS_MILLIS: [i64; 18] = [
+    1_000,
+    2_000,
+    5_000,
+    10_000,
+    15_000,
+    30_000,
+    60_000,
+    2 * 60_000,
+    5 * 60_000,
+    10 * 60_000,
+    15 * 60_000,
+    30 * 60_000,
+    3_600_000,
+    2 * 3_600_000,
+    3 * 3_600_000,
+    6 * 3_600_000,
+    12 * 3_600_000,
+    DAY_MILLIS,
+];
+
+const DAY_MILLIS: i64 = 24 * 3_600_000;
+
+const TARGET_BUCKETS: usize = 60;
+const HEIGHT: f32 = 70.0;
+
+/// Event counts per time bucket, split by level (indexed like `STACK_ORDER`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub start_millis: i64,
+    pub bucket_millis: i64,
+    pub buckets: Vec<[usize; 5]>,
+}
+
+impl Histogram {
+    pub fn bucket_range(&self, idx: usize) -> (i64, i64) {
+        // Saturating: buckets stretched over a bogus range can end past i64.
+        let start = self
+            .start_millis
+            .saturating_add((idx as i64).saturating_mul(self.bucket_millis));
+        (start, start.saturating_add(self.bucket_millis))
+    }
+
+    pub fn max_total(&self) -> usize {
+        self.buckets
+            .iter()
+            .map(|b| b.iter().sum::<usize>())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Bucket width giving at most `target` buckets over `range_millis`, one of
+/// them allowing for the aligned start: the smallest "nice" width, or whole
+/// days beyond the widest one (e.g. when a bogus timestamp stretches the
+/// range over centuries).
+pub fn nice_bucket_millis(range_millis: i64, target: usize) -> i64 {
+    let spans = (target.max(2) - 1) as i64;
+    NICE_BUCKETS_MILLIS
+        .iter()
+        .copied()
+        .find(|b| range_millis / b < spans)
+        .unwrap_or_else(|| (range_millis / spans / DAY_MILLIS + 1) * DAY_MILLIS)
+}
+
+/// Bucket `(timestamp, level)` pairs. Returns `None` when there is nothing to plot.
+pub fn build_histogram(
+    events: impl IntoIterator<Item = (i64, LogLevel)>,
+    target_buckets: usize,
+) -> Option<Histogram> {
+    let events: Vec<_> = events.into_iter().filter(|(ts, _)| *ts > 0).collect();
+    let min = events.iter().map(|(ts, _)| *ts).min()?;
+    let max = events.iter().map(|(ts, _)| *ts).max()?;
+
+    let bucket_millis = nice_bucket_millis(max - min, target_buckets);
+    let start_millis = min - min.rem_euclid(bucket_millis);
+    let count = ((max - start_millis) / bucket_millis + 1) as usize;
+
+    let mut buckets = vec![[0usize; 5]; count];
+    for (ts, level) in events {
+        let idx = ((ts - start_millis) / bucket_millis) as usize;
+        let slot = STACK_ORDER.iter().position(|l| *l == level).unwrap_or(4);
+        buckets[idx][slot] += 1;
+    }
+
+    Some(Histogram {
+        start_millis,
+        bucket_millis,
+        buckets,
+    })
+}
+
+/// Short label for a bucket width, e.g. `30s`, `5m`, `1h`.
+pub fn format_bucket_width(millis: i64) -> String {
+    let secs = millis / 1_000;
+    if secs % 86_400 == 0 {
+        format!("{}d", secs / 86_400)
+    } else if secs % 3_600 == 0 {
+        format!("{}h", secs / 3_600)
+    } else if secs % 60 == 0 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+fn bar_color(level: LogLevel, theme: Theme) -> egui::Color32 {
+    match level {
+        LogLevel::Debug => egui::Color32::from_gray(110),
+        LogLevel::Unknown => egui::Color32::from_gray(150),
+        other => other.color(theme),
+    }
+}
+
+/// What the histogram of a view was built from: its entries and the local
+/// filters. The time window only narrows the list, not the histogram.
+#[derive(Clone, PartialEq)]
+struct HistogramKey {
+    revision: u64,
+    filter_text: String,
+    field_filters: Vec<FieldFilter>,
+    pattern_filter: PatternFilter,
+    query_text: String,
+}
+
+impl HistogramKey {
+    fn of(view: &LogsViewState) -> Self {
+        Self {
+            revision: view.revision,
+            filter_text: view.filter_text.clone(),
+            field_filters: view.field_filters.clone(),
+            pattern_filter: view.pattern_filter.clone(),
+            query_text: view.query_text.clone(),
+        }
+    }
+}
+
+/// The histogram of the entries passing the local filters, rebuilt only
+/// when they or the filters changed since the last frame.
+fn cached_histogram(ui: &egui::Ui, tab: &Tab) -> Option<Histogram> {
+    let view = &tab.logs_view;
+    let key = HistogramKey::of(view);
+    let id = egui::Id::new(("histogram", tab.id));
+    if let Some((cached_key, histogram)) =
+        ui.data(|d| d.get_temp::<(HistogramKey, Option<Histogram>)>(id))
+        && cached_key == key
+    {
+        return histogram;
+    }
+    let matches = view.filter_matcher();
+    let histogram = build_histogram(
+        (0..view.entries.len())
+            .filter(|&idx| matches(idx))
+            .map(|idx| (view.entries[idx].timestamp_millis, view.level_of(idx))),
+        TARGET_BUCKETS,
+    );
+    ui.data_mut(|d| d.insert_temp(id, (key, histogram.clone())));
+    histogram
+}
+
+/// Draw the histogram for the entries passing the local filters. Clicking a
+/// bar or dragging across several narrows the list to that time window.
+pub fn draw_histogram(tab: &mut Tab, theme: Theme, worker: &WorkerHandle, ui: &mut egui::Ui) {
+    let Some(histogram) = cached_histogram(ui, tab) else {
+        return;
+    };
+    let view = &tab.logs_view;
+
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), HEIGHT),
+        egui::Sense::click_and_drag(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let n = histogram.buckets.len();
+    let bar_width = rect.width() / n as f32;
+    let max_total = histogram.max_total().max(1) as f32;
+    let bucket_at = |x: f32| (((x - rect.left()) / bar_width).floor().max(0.0) as usize).min(n - 1);
+
+    // Highlight the active window.
+    if let Some((start, end)) = view.time_window {
+        let to_x = |ts: i64| {
+            rect.left()
+                + ((ts - histogram.start_millis) as f32 / histogram.bucket_millis as f32)
+                    * bar_width
+        };
+        let window = egui::Rect::from_x_y_ranges(
+            to_x(start).max(rect.left())..=to_x(end).min(rect.right()),
+            rect.y_range(),
+        );
+        painter.rect_filled(
+            window,
+            0.0,
+            ui.visuals().selection.bg_fill.gamma_multiply(0.4),
+        );
+    }
+
+    for (idx, counts) in histogram.buckets.iter().enumerate() {
+        let x0 = rect.left() + idx as f32 * bar_width;
+        let mut y = rect.bottom();
+        for (slot, count) in counts.iter().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            let h = *count as f32 / max_total * (rect.height() - 2.0);
+            let bar = egui::Rect::from_min_max(
+                egui::pos2(x0 + 0.5, y - h),
+                egui::pos2(x0 + bar_width - 0.5, y),
+            );
+            painter.rect_filled(bar, 0.0, bar_color(STACK_ORDER[slot], theme));
+            y -= h;
+        }
+    }
+
+    // Brush: remember the bucket where the drag started.
+    let brush_id = response.id.with("brush_start");
+    if response.drag_started()
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        ui.data_mut(|d| d.insert_temp(brush_id, bucket_at(pos.x)));
+    }
+    let brush_start: Option<usize> = ui.data(|d| d.get_temp(brush_id));
+
+    if response.dragged()
+        && let (Some(from), Some(pos)) = (brush_start, response.interact_pointer_pos())
+    {
+        let to = bucket_at(pos.x);
+        let (a, b) = (from.min(to), from.max(to));
+        let brush = egui::Rect::from_x_y_ranges(
+            rect.left() + a as f32 * bar_width..=rect.left() + (b + 1) as f32 * bar_width,
+            rect.y_range(),
+        );
+        painter.rect_stroke(
+            brush,
+            0.0,
+            ui.visuals().selection.stroke,
+            egui::StrokeKind::Inside,
+        );
+    }
+
+    let mut new_window = None;
+    if response.drag_stopped()
+        && let (Some(from), Some(pos)) = (brush_start, response.interact_pointer_pos())
+    {
+        let to = bucket_at(pos.x);
+        new_window = Some((
+            histogram.bucket_range(from.min(to)).0,
+            histogram.bucket_range(from.max(to)).1,
+        ));
+        ui.data_mut(|d| d.remove::<usize>(brush_id));
+    } else if response.clicked()
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        new_window = Some(histogram.bucket_range(bucket_at(pos.x)));
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        let idx = bucket_at(pos.x);
+        let (start, _) = histogram.bucket_range(idx);
+        let counts = histogram.buckets[idx];
+        let mut text = format_timestamp_millis(start, view.show_local_time);
+        for (slot, count) in counts.iter().enumerate() {
+            if *count > 0 {
+                text.push_str(&format!("\n{}: {count}", STACK_ORDER[slot].label()));
+            }
+        }
+        response.on_hover_text_at_pointer(text);
+    }
+
+    let use_local = view.show_local_time;
+    let window = view.time_window;
+    ui.horizontal(|ui| {
+        ui.small(format_timestamp_millis(histogram.start_millis, use_local));
+        ui.small(format!(
+            "· {} buckets ·",
+            format_bucket_width(histogram.bucket_millis)
+        ));
+        ui.small(format_timestamp_millis(
+            histogram.bucket_range(n - 1).1,
+            use_local,
+        ));
+
+        if let Some((start, end)) = window {
+            ui.separator();
+            ui.label(format!(
+                "Window: {} – {}",
+                format_timestamp_millis(start, use_local),
+                format_timestamp_millis(end, use_local)
+            ));
+            if ui.small_button("Clear").clicked() {
+                tab.logs_view.time_window = None;
+            }
+            if ui
+                .small_button("Re-fetch this range")
+                .on_hover_text("Fetch this absolute time range from CloudWatch")
+                .clicked()
+            {
+                tab.logs_view.fetch_range = Some((start, end));
+                tab.logs_view.tail_mode = false;
+                tab.start_fetch_logs(
+                    worker,
+                    std::time::Duration::from_millis((end - start) as u64),
+                );
+            }
+        }
+    });
+
+    if let Some(window) = new_window {
+        tab.logs_view.time_window = Some(window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nice_bucket_millis_respects_target() {
+        assert_eq!(nice_bucket_millis(0, 60), 1_000);
+        assert_eq!(nice_bucket_millis(5 * 60_000, 60), 10_000);
+        assert_eq!(nice_bucket_millis(3_600_000, 60), 2 * 60_000);
+        assert_eq!(nice_bucket_millis(30 * DAY_MILLIS, 60), DAY_MILLIS);
+        assert_eq!(nice_bucket_millis(365 * DAY_MILLIS, 60), 7 * DAY_MILLIS);
+    }
+
+    #[test]
+    fn bogus_timestamps_do_not_exceed_the_target_buckets() {
+        let h = build_histogram(
+            vec![
+                (1_700_000_000, LogLevel::Info), // seconds among millis
+                (1_700_000_000_000, LogLevel::Info),
+                (9_000_000_000_000_000_000, LogLevel::Error),
+                (i64::MAX, LogLevel::Error),
+            ],
+            60,
+        )
+        .expect("has events");
+
+        assert!(h.buckets.len() <= 60);
+        assert_eq!(h.buckets.iter().flatten().sum::<usize>(), 4);
+        assert_eq!(h.bucket_range(h.buckets.len() - 1).1, i64::MAX);
+    }
+
+    #[test]
+    fn build_histogram_stacks_levels_per_bucket() {
+        let h = build_histogram(
+            vec![
+                (10_500, LogLevel::Error),
+                (10_900, LogLevel::Info),
+                (12_100, LogLevel::Error),
+                (0, LogLevel::Error), // no timestamp: ignored
+            ],
+            60,
+        )
+        .expect("has events");
+
+        assert_eq!(h.bucket_millis, 1_000);
+        assert_eq!(h.start_millis, 10_000);
+        assert_eq!(h.buckets.len(), 3);
+        assert_eq!(h.buckets[0], [1, 0, 1, 0, 0]);
+        assert_eq!(h.buckets[1], [0; 5]);
+        assert_eq!(h.buckets[2], [1, 0, 0, 0, 0]);
+        assert_eq!(h.max_total(), 2);
+        assert_eq!(h.bucket_range(2), (12_000, 13_000));
+    }
+
+    #[test]
+    fn format_bucket_width_uses_largest_whole_unit() {
+        assert_eq!(format_bucket_width(15_000), "15s");
+        assert_eq!(format_bucket_width(5 * 60_000), "5m");
+        assert_eq!(format_bucket_width(2 * 3_600_000), "2h");
+        assert_eq!(format_bucket_width(24 * 3_600_000), "1d");
+    }
+
+    #[test]
+    fn build_histogram_is_none_without_timestamps() {
+        assert_eq!(build_histogram(vec![(0, LogLevel::Info)], 60), None);
+    }
+}
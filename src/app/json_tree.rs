@@ -0,0 +1,432 @@
+use std::borrow::Cow;
+
+use serde_json::Value as JsonValue;
+
+use crate::app::state::try_parse_json;
+
+/// Arrays longer than this start collapsed.
+#[cfg(feature = "gui")]
+const LARGE_ARRAY_LEN: usize = 20;
+
+/// Something the user asked for from inside a JSON tree.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonTreeAction {
+    /// Add a `path = value` condition to the current search.
+    FilterByValue { path: String, value: JsonValue },
+    /// Search the selected log groups for this value (request / trace ID).
+    TraceValue { value: String },
+}
+
+/// One step in a JSON path such as `$.detail.items[0].name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Render `value` as an expandable tree.
+///
+/// `id_salt` must be unique per rendered tree (e.g. per log entry) so the
+/// open/closed state of the nodes is remembered across frames.
+#[cfg(feature = "gui")]
+pub fn show_json_tree(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    value: &JsonValue,
+    actions: &mut Vec<JsonTreeAction>,
+) {
+    let id = ui.make_persistent_id(id_salt);
+    show_node(ui, id, None, "$", value, actions);
+}
+
+#[cfg(feature = "gui")]
+fn show_node(
+    ui: &mut egui::Ui,
+    id: egui::Id,
+    key: Option<&str>,
+    path: &str,
+    value: &JsonValue,
+    actions: &mut Vec<JsonTreeAction>,
+) {
+    match value {
+        JsonValue::Object(map) => {
+            let summary = format!("{{{} keys}}", map.len());
+            show_container(ui, id, key, path, value, summary, true, |ui| {
+                for (child_key, child) in map {
+                    let child_path = push_key(path, child_key);
+                    show_node(
+                        ui,
+                        id.with(child_key),
+                        Some(child_key),
+                        &child_path,
+                        child,
+                        actions,
+                    );
+                }
+            });
+        }
+        JsonValue::Array(items) => {
+            let summary = format!("[{} items]", items.len());
+            let default_open = items.len() <= LARGE_ARRAY_LEN;
+            show_container(ui, id, key, path, value, summary, default_open, |ui| {
+                for (idx, child) in items.iter().enumerate() {
+                    let child_path = format!("{path}[{idx}]");
+                    let label = idx.to_string();
+                    show_node(ui, id.with(idx), Some(&label), &child_path, child, actions);
+                }
+            });
+        }
+        JsonValue::String(s) => match embedded_json(s) {
+            Some(inner) => {
+                let summary = "(embedded JSON)".to_string();
+                show_container(ui, id, key, path, value, summary, false, |ui| {
+                    show_node(ui, id.with("embedded"), None, path, &inner, actions);
+                });
+            }
+            None => show_leaf(ui, key, path, value, actions),
+        },
+        _ => show_leaf(ui, key, path, value, actions),
+    }
+}
+
+#[cfg(feature = "gui")]
+#[allow(clippy::too_many_arguments)]
+fn show_container(
+    ui: &mut egui::Ui,
+    id: egui::Id,
+    key: Option<&str>,
+    path: &str,
+    value: &JsonValue,
+    summary: String,
+    default_open: bool,
+    add_children: impl FnOnce(&mut egui::Ui),
+) {
+    let palette = Palette::for_ui(ui);
+    egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, default_open)
+        .show_header(ui, |ui| {
+            if let Some(key) = key {
+                ui.label(egui::RichText::new(format!("{key}:")).color(palette.key));
+            }
+            let response = ui.add(
+                egui::Label::new(egui::RichText::new(summary).color(palette.null).italics())
+                    .sense(egui::Sense::click()),
+            );
+            response.context_menu(|ui| node_menu(ui, path, value, None));
+        })
+        .body(add_children);
+}
+
+#[cfg(feature = "gui")]
+fn show_leaf(
+    ui: &mut egui::Ui,
+    key: Option<&str>,
+    path: &str,
+    value: &JsonValue,
+    actions: &mut Vec<JsonTreeAction>,
+) {
+    let palette = Palette::for_ui(ui);
+    ui.horizontal_wrapped(|ui| {
+        if let Some(key) = key {
+            ui.label(egui::RichText::new(format!("{key}:")).color(palette.key));
+        }
+        let text = egui::RichText::new(value.to_string())
+            .monospace()
+            .color(palette.color_for(value));
+        let response = ui.add(egui::Label::new(text).sense(egui::Sense::click()));
+        response.context_menu(|ui| node_menu(ui, path, value, Some(actions)));
+    });
+}
+
+#[cfg(feature = "gui")]
+fn node_menu(
+    ui: &mut egui::Ui,
+    path: &str,
+    value: &JsonValue,
+    actions: Option<&mut Vec<JsonTreeAction>>,
+) {
+    if ui.button("Copy path").clicked() {
+        ui.ctx().copy_text(path.to_string());
+        ui.close();
+    }
+    if ui.button("Copy value").clicked() {
+        ui.ctx().copy_text(value_to_copy_text(value));
+        ui.close();
+    }
+    let Some(actions) = actions else {
+        return;
+    };
+    if ui.button("Filter by this value").clicked() {
+        actions.push(JsonTreeAction::FilterByValue {
+            path: path.to_string(),
+            value: value.clone(),
+        });
+        ui.close();
+    }
+    if matches!(value, JsonValue::String(_) | JsonValue::Number(_))
+        && ui.button("Trace this value").clicked()
+    {
+        actions.push(JsonTreeAction::TraceValue {
+            value: value_to_copy_text(value),
+        });
+        ui.close();
+    }
+}
+
+/// Text placed on the clipboard by "Copy value": strings without quotes,
+/// containers as pretty JSON, everything else as its JSON literal.
+pub fn value_to_copy_text(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Object(_) | JsonValue::Array(_) => {
+            serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Parse a string value that itself contains a JSON object or array,
+/// as commonly found in Lambda payloads (`"body": "{\"id\":1}"`).
+pub fn embedded_json(s: &str) -> Option<JsonValue> {
+    try_parse_json(s)
+}
+
+/// Append an object key to a path, quoting it when it is not a plain identifier.
+#[cfg(feature = "gui")]
+pub fn push_key(path: &str, key: &str) -> String {
+    let is_ident = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_ident {
+        format!("{path}.{key}")
+    } else {
+        format!("{path}[{}]", JsonValue::String(key.to_string()))
+    }
+}
+
+/// Parse a path produced by [`push_key`] (`$.a["b c"][0]`) into segments.
+pub fn parse_path(path: &str) -> Option<Vec<PathSegment>> {
+    let rest = path.trim();
+    let mut rest = rest.strip_prefix('$').unwrap_or(rest);
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            if end == 0 {
+                return None;
+            }
+            segments.push(PathSegment::Key(after_dot[..end].to_string()));
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            if after_bracket.starts_with('"') {
+                // Quoted key: reuse the JSON string parser for escapes.
+                let mut de =
+                    serde_json::Deserializer::from_str(after_bracket).into_iter::<String>();
+                let key = de.next()?.ok()?;
+                let consumed = de.byte_offset();
+                rest = after_bracket[consumed..].strip_prefix(']')?;
+                segments.push(PathSegment::Key(key));
+            } else {
+                let end = after_bracket.find(']')?;
+                let idx = after_bracket[..end].trim().parse().ok()?;
+                segments.push(PathSegment::Index(idx));
+                rest = &after_bracket[end + 1..];
+            }
+        } else if segments.is_empty() {
+            // Allow bare `a.b` as shorthand for `$.a.b`.
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            segments.push(PathSegment::Key(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else {
+            return None;
+        }
+    }
+
+    Some(segments)
+}
+
+/// Resolve `path` inside `value`, descending into embedded JSON strings.
+/// The result is borrowed from `value` unless it lies inside an embedded
+/// string, which has to be parsed.
+pub fn lookup_path<'a>(value: &'a JsonValue, path: &str) -> Option<Cow<'a, JsonValue>> {
+    let segments = parse_path(path)?;
+    let mut current = Cow::Borrowed(value);
+
+    for segment in segments {
+        if let JsonValue::String(s) = current.as_ref() {
+            current = Cow::Owned(embedded_json(s)?);
+        }
+        current = match current {
+            Cow::Borrowed(value) => Cow::Borrowed(match (segment, value) {
+                (PathSegment::Key(k), JsonValue::Object(map)) => map.get(&k)?,
+                (PathSegment::Index(i), JsonValue::Array(items)) => items.get(i)?,
+                _ => return None,
+            }),
+            Cow::Owned(value) => Cow::Owned(match (segment, value) {
+                (PathSegment::Key(k), JsonValue::Object(mut map)) => map.remove(&k)?,
+                (PathSegment::Index(i), JsonValue::Array(mut items)) if i < items.len() => {
+                    items.swap_remove(i)
+                }
+                _ => return None,
+            }),
+        };
+    }
+
+    Some(current)
+}
+
+/// Flatten `value` into `(path, leaf value)` pairs, expanding embedded JSON
+/// strings, in document order.
+#[cfg(feature = "gui")]
+pub fn flatten_json(value: &JsonValue) -> Vec<(String, JsonValue)> {
+    fn walk(path: String, value: &JsonValue, out: &mut Vec<(String, JsonValue)>) {
+        match value {
+            JsonValue::Object(map) => {
+                for (k, v) in map {
+                    walk(push_key(&path, k), v, out);
+                }
+            }
+            JsonValue::Array(items) => {
+                for (idx, v) in items.iter().enumerate() {
+                    walk(format!("{path}[{idx}]"), v, out);
+                }
+            }
+            JsonValue::String(s) => match embedded_json(s) {
+                Some(inner) => walk(path, &inner, out),
+                None => out.push((path, value.clone())),
+            },
+            _ => out.push((path, value.clone())),
+        }
+    }
+
+    let mut out = Vec::new();
+    walk("$".to_string(), value, &mut out);
+    out
+}
+
+/// Type colors for JSON values, picked to be readable on the current theme.
+#[cfg(feature = "gui")]
+struct Palette {
+    key: egui::Color32,
+    string: egui::Color32,
+    number: egui::Color32,
+    boolean: egui::Color32,
+    null: egui::Color32,
+}
+
+#[cfg(feature = "gui")]
+impl Palette {
+    fn for_ui(ui: &egui::Ui) -> Self {
+        if ui.visuals().dark_mode {
+            Self {
+                key: egui::Color32::LIGHT_BLUE,
+                string: egui::Color32::from_rgb(0xce, 0x91, 0x78),
+                number: egui::Color32::from_rgb(0xb5, 0xce, 0xa8),
+                boolean: egui::Color32::from_rgb(0x56, 0x9c, 0xd6),
+                null: egui::Color32::GRAY,
+            }
+        } else {
+            Self {
+                key: egui::Color32::from_rgb(0x00, 0x45, 0x8b),
+                string: egui::Color32::from_rgb(0xa3, 0x15, 0x15),
+                number: egui::Color32::from_rgb(0x09, 0x86, 0x58),
+                boolean: egui::Color32::from_rgb(0x00, 0x00, 0xff),
+                null: egui::Color32::DARK_GRAY,
+            }
+        }
+    }
+
+    fn color_for(&self, value: &JsonValue) -> egui::Color32 {
+        match value {
+            JsonValue::String(_) => self.string,
+            JsonValue::Number(_) => self.number,
+            JsonValue::Bool(_) => self.boolean,
+            _ => self.null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn push_key_quotes_non_identifier_keys() {
+        assert_eq!(push_key("$", "level"), "$.level");
+        assert_eq!(push_key("$.a", "x-amzn-trace-id"), "$.a.x-amzn-trace-id");
+        assert_eq!(push_key("$", "with space"), "$[\"with space\"]");
+        assert_eq!(push_key("$", "0abc"), "$[\"0abc\"]");
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn parse_path_round_trips_generated_paths() {
+        let path = format!("{}[3]", push_key(&push_key("$", "detail"), "a.b"));
+        assert_eq!(
+            parse_path(&path),
+            Some(vec![
+                PathSegment::Key("detail".to_string()),
+                PathSegment::Key("a.b".to_string()),
+                PathSegment::Index(3),
+            ])
+        );
+        assert_eq!(
+            parse_path("level"),
+            Some(vec![PathSegment::Key("level".to_string())])
+        );
+        assert_eq!(parse_path("$..a"), None);
+        assert_eq!(parse_path("$[x]"), None);
+    }
+
+    #[test]
+    fn lookup_path_descends_into_embedded_json_strings() {
+        let value = json!({
+            "requestContext": { "requestId": "abc" },
+            "body": "{\"user\":{\"id\":42},\"tags\":[\"a\",\"b\"]}"
+        });
+
+        assert!(matches!(
+            lookup_path(&value, "$.requestContext.requestId"),
+            Some(Cow::Borrowed(v)) if *v == json!("abc")
+        ));
+        assert_eq!(
+            lookup_path(&value, "$.body.user.id").as_deref(),
+            Some(&json!(42))
+        );
+        assert_eq!(
+            lookup_path(&value, "$.body.tags[1]").as_deref(),
+            Some(&json!("b"))
+        );
+        assert_eq!(lookup_path(&value, "$.body.missing"), None);
+        assert_eq!(lookup_path(&value, "$.requestContext[0]"), None);
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn flatten_json_lists_leaves_with_paths() {
+        let value = json!({"a": {"b": 1}, "list": [true], "inner": "{\"x\":null}"});
+        let flat = flatten_json(&value);
+
+        assert_eq!(
+            flat,
+            vec![
+                ("$.a.b".to_string(), json!(1)),
+                ("$.inner.x".to_string(), json!(null)),
+                ("$.list[0]".to_string(), json!(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn value_to_copy_text_unquotes_strings() {
+        assert_eq!(value_to_copy_text(&json!("hello")), "hello");
+        assert_eq!(value_to_copy_text(&json!(12.5)), "12.5");
+        assert_eq!(value_to_copy_text(&json!(null)), "null");
+        assert!(value_to_copy_text(&json!({"a": 1})).contains('\n'));
+    }
+}
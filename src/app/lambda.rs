@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::app::parsers::MessageFormat;
+use crate::app::state::LogsViewState;
+
+/// Runtime line logged when an invocation hits its timeout (older runtimes
+/// do not add `Status: timeout` to the `REPORT` line).
+static TASK_TIMED_OUT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}) Task timed out",
+    )
+    .expect("valid regex")
+});
+
+/// One invocation, from its `REPORT` line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    pub request_id: String,
+    /// Index into the entries of the `REPORT` line.
+    pub report_idx: usize,
+    pub duration_ms: f64,
+    pub billed_duration_ms: f64,
+    pub memory_size_mb: f64,
+    pub max_memory_used_mb: f64,
+    /// Present on cold starts.
+    pub init_duration_ms: Option<f64>,
+    pub timed_out: bool,
+}
+
+/// Aggregates over the `REPORT` lines of a Lambda log group.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LambdaSummary {
+    /// Slowest first.
+    pub invocations: Vec<Invocation>,
+}
+
+impl LambdaSummary {
+    /// Summarize the invocations reported among the entries at `indices`.
+    pub fn from_view(view: &LogsViewState, indices: &[usize]) -> Self {
+        let timed_out: HashSet<&str> = indices
+            .iter()
+            .filter_map(|&idx| TASK_TIMED_OUT.captures(&view.entries[idx].message))
+            .filter_map(|caps| caps.get(1))
+            .map(|m| m.as_str())
+            .collect();
+
+        let mut invocations: Vec<Invocation> = indices
+            .iter()
+            .filter_map(|&idx| {
+                let parsed = view.parsed(idx)?;
+                if parsed.format != MessageFormat::LambdaPlatform
+                    || parsed.field("type")?.as_str()? != "REPORT"
+                {
+                    return None;
+                }
+                let number = |name: &str| parsed.field(name).and_then(|v| v.as_f64());
+                let request_id = parsed.field("requestId")?.as_str()?.to_string();
+                let status = parsed.field("status").and_then(|v| v.as_str());
+                Some(Invocation {
+                    timed_out: status == Some("timeout") || timed_out.contains(request_id.as_str()),
+                    request_id,
+                    report_idx: idx,
+                    duration_ms: number("durationMs")?,
+                    billed_duration_ms: number("billedDurationMs").unwrap_or_default(),
+                    memory_size_mb: number("memorySizeMb").unwrap_or_default(),
+                    max_memory_used_mb: number("maxMemoryUsedMb").unwrap_or_default(),
+                    init_duration_ms: number("initDurationMs"),
+                })
+            })
+            .collect();
+        invocations.sort_by(|a, b| b.duration_ms.total_cmp(&a.duration_ms));
+
+        Self { invocations }
+    }
+
+    pub fn count(&self) -> usize {
+        self.invocations.len()
+    }
+
+    pub fn cold_starts(&self) -> usize {
+        self.invocations
+            .iter()
+            .filter(|i| i.init_duration_ms.is_some())
+            .count()
+    }
+
+    pub fn timeouts(&self) -> usize {
+        self.invocations.iter().filter(|i| i.timed_out).count()
+    }
+
+    /// Nearest-rank percentile of the durations, `p` in `0.0..=1.0`.
+    pub fn duration_percentile(&self, p: f64) -> Option<f64> {
+        let n = self.invocations.len();
+        if n == 0 {
+            return None;
+        }
+        let rank = ((p * n as f64).ceil() as usize).clamp(1, n);
+        // Sorted slowest first.
+        Some(self.invocations[n - rank].duration_ms)
+    }
+
+    pub fn total_billed_ms(&self) -> f64 {
+        self.invocations.iter().map(|i| i.billed_duration_ms).sum()
+    }
+
+    /// Billed compute in GB-seconds, as priced by Lambda.
+    pub fn gb_seconds(&self) -> f64 {
+        self.invocations
+            .iter()
+            .map(|i| i.billed_duration_ms / 1_000.0 * i.memory_size_mb / 1_024.0)
+            .sum()
+    }
+
+    pub fn max_memory_used_mb(&self) -> f64 {
+        self.invocations
+            .iter()
+            .map(|i| i.max_memory_used_mb)
+            .fold(0.0, f64::max)
+    }
+
+    /// Configured memory; the largest if it changed within the results.
+    pub fn memory_size_mb(&self) -> f64 {
+        self.invocations
+            .iter()
+            .map(|i| i.memory_size_mb)
+            .fold(0.0, f64::max)
+    }
+
+    pub fn slowest(&self, n: usize) -> &[Invocation] {
+        &self.invocations[..n.min(self.invocations.len())]
+    }
+}
+
+/// Indices of the log block of the invocation reported at `report_idx`:
+/// its stream's events from `START` to `REPORT`.
+pub fn invocation_block(view: &LogsViewState, report_idx: usize) -> Vec<usize> {
+    let Some(request_id) = view
+        .parsed(report_idx)
+        .and_then(|p| p.field("requestId"))
+        .and_then(|v| v.as_str())
+    else {
+        return vec![report_idx];
+    };
+    let stream = &view.entries[report_idx].log_stream_name;
+    let same_stream = |idx: &usize| view.entries[*idx].log_stream_name == *stream;
+
+    let start = (0..report_idx)
+        .rev()
+        .filter(same_stream)
+        .find(|&idx| {
+            view.parsed(idx).is_some_and(|p| {
+                p.format == MessageFormat::LambdaPlatform
+                    && p.field("type").and_then(|v| v.as_str()) == Some("START")
+                    && p.field("requestId").and_then(|v| v.as_str()) == Some(request_id)
+            })
+        })
+        // START outside the fetched range: the first line mentioning the ID.
+        .or_else(|| {
+            (0..report_idx)
+                .filter(same_stream)
+                .find(|&idx| view.entries[idx].message.contains(request_id))
+        })
+        .unwrap_or(report_idx);
+
+    (start..=report_idx).filter(same_stream).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::LogEntry;
+
+    const A: &str = "8f5f8f7a-1c2b-4d3e-9f00-0123456789aa";
+    const B: &str = "8f5f8f7a-1c2b-4d3e-9f00-0123456789bb";
+
+    fn entry(ts: i64, stream: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: ts,
+            message: message.to_string(),
+            log_stream_name: Some(stream.to_string()),
+            log_group_name: Some("/aws/lambda/api".to_string()),
+            event_id: None,
+            ingestion_time_millis: None,
+        }
+    }
+
+    fn report(id: &str, duration: &str, extra: &str) -> String {
+        format!(
+            "REPORT RequestId: {id}\tDuration: {duration} ms\tBilled Duration: 100 ms\tMemory Size: 1024 MB\tMax Memory Used: 80 MB\t{extra}"
+        )
+    }
+
+    fn view() -> LogsViewState {
+        let mut view = LogsViewState::new_default();
+        view.set_entries(vec![
+            entry(1, "s1", &format!("START RequestId: {A} Version: $LATEST")),
+            entry(2, "s2", &format!("START RequestId: {B} Version: $LATEST")),
+            entry(
+                3,
+                "s1",
+                &format!("2026-10-18T10:00:00.000Z\t{A}\tINFO\thello"),
+            ),
+            entry(
+                4,
+                "s2",
+                &format!("2026-10-18T10:00:03.000Z {B} Task timed out after 3.00 seconds"),
+            ),
+            entry(5, "s2", &format!("END RequestId: {B}")),
+            entry(6, "s2", &report(B, "3000.00", "")),
+            entry(7, "s1", &format!("END RequestId: {A}")),
+            entry(8, "s1", &report(A, "20.00", "Init Duration: 250.00 ms")),
+        ]);
+        view
+    }
+
+    #[test]
+    fn summarizes_report_lines() {
+        let view = view();
+        let all: Vec<usize> = (0..view.entries.len()).collect();
+        let summary = LambdaSummary::from_view(&view, &all);
+
+        assert_eq!(summary.count(), 2);
+        assert_eq!(summary.cold_starts(), 1);
+        assert_eq!(summary.timeouts(), 1);
+        assert_eq!(summary.slowest(1)[0].request_id, B);
+        assert_eq!(summary.duration_percentile(0.5), Some(20.0));
+        assert_eq!(summary.duration_percentile(0.99), Some(3_000.0));
+        assert_eq!(summary.total_billed_ms(), 200.0);
+        assert!((summary.gb_seconds() - 0.2).abs() < 1e-9);
+        assert_eq!(summary.memory_size_mb(), 1_024.0);
+        assert_eq!(summary.max_memory_used_mb(), 80.0);
+
+        assert_eq!(
+            LambdaSummary::from_view(&view, &[]).duration_percentile(0.5),
+            None
+        );
+    }
+
+    #[test]
+    fn invocation_block_spans_start_to_report_in_one_stream() {
+        let view = view();
+        assert_eq!(invocation_block(&view, 7), vec![0, 2, 6, 7]);
+        assert_eq!(invocation_block(&view, 5), vec![1, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "gui")]
+use crate::app::state::Theme;
+
+/// Severity detected from a log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Unknown,
+}
+
+impl LogLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Unknown => "-",
+        }
+    }
+
+    /// Level named by a structured field such as `"level": "warning"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "error" | "err" | "fatal" | "critical" | "crit" | "panic" | "alert" | "emerg" => {
+                Some(LogLevel::Error)
+            }
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" | "information" | "notice" => Some(LogLevel::Info),
+            "debug" | "trace" | "verbose" => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
+
+    /// Color used for entries of this level under the given theme.
+    #[cfg(feature = "gui")]
+    pub fn color(self, theme: Theme) -> egui::Color32 {
+        if theme == Theme::RetroGreen {
+            match self {
+                LogLevel::Error => egui::Color32::from_rgb(0xff, 0x40, 0x40),
+                LogLevel::Warn => egui::Color32::from_rgb(0xff, 0xff, 0x80),
+                _ => egui::Color32::from_rgb(0x00, 0xff, 0x66),
+            }
+        } else {
+            match self {
+                LogLevel::Error => egui::Color32::RED,
+                LogLevel::Warn => egui::Color32::YELLOW,
+                LogLevel::Info => egui::Color32::LIGHT_GREEN,
+                _ => egui::Color32::WHITE,
+            }
+        }
+    }
+}
+
+/// Detect the level of a message from the usual upper-case markers.
+pub fn detect_level(message: &str) -> LogLevel {
+    if message.contains("ERROR") {
+        LogLevel::Error
+    } else if message.contains("WARN") {
+        LogLevel::Warn
+    } else if message.contains("INFO") {
+        LogLevel::Info
+    } else if message.contains("DEBUG") {
+        LogLevel::Debug
+    } else {
+        LogLevel::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_level_prefers_most_severe_marker() {
+        assert_eq!(detect_level("ERROR boom"), LogLevel::Error);
+        assert_eq!(detect_level("WARN then ERROR"), LogLevel::Error);
+        assert_eq!(detect_level("[WARN] disk"), LogLevel::Warn);
+        assert_eq!(detect_level("INFO started"), LogLevel::Info);
+        assert_eq!(detect_level("DEBUG x=1"), LogLevel::Debug);
+        assert_eq!(detect_level("hello"), LogLevel::Unknown);
+    }
+
+    #[test]
+    fn from_name_accepts_common_spellings() {
+        assert_eq!(LogLevel::from_name("WARNING"), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::from_name("fatal"), Some(LogLevel::Error));
+        assert_eq!(LogLevel::from_name(" trace "), Some(LogLevel::Debug));
+        assert_eq!(LogLevel::from_name("loud"), None);
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn retro_theme_uses_green_for_non_problems() {
+        let green = egui::Color32::from_rgb(0x00, 0xff, 0x66);
+        assert_eq!(LogLevel::Info.color(Theme::RetroGreen), green);
+        assert_eq!(LogLevel::Unknown.color(Theme::RetroGreen), green);
+        assert_eq!(LogLevel::Error.color(Theme::Dark), egui::Color32::RED);
+    }
+}
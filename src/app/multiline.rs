@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use crate::aws::LogEntry;
+
+/// When an event continues the previous event of the same stream, e.g. the
+/// frames of a Java or Python stack trace logged line by line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinuationRules {
+    /// Lines starting with a space or tab continue the previous event.
+    pub leading_whitespace: bool,
+    /// Lines starting with any of these (after trimming leading whitespace)
+    /// continue the previous event.
+    pub prefixes: Vec<String>,
+    /// Events further apart than this are never folded together.
+    pub max_gap_millis: i64,
+}
+
+impl Default for ContinuationRules {
+    fn default() -> Self {
+        Self {
+            leading_whitespace: true,
+            prefixes: [
+                "at ",
+                "Caused by:",
+                "... ",
+                "Traceback",
+                "File \"",
+                "During handling of the above exception",
+                "The above exception was the direct cause",
+            ]
+            .map(String::from)
+            .to_vec(),
+            max_gap_millis: 1_000,
+        }
+    }
+}
+
+impl ContinuationRules {
+    pub fn is_continuation(&self, message: &str) -> bool {
+        if message.trim().is_empty() {
+            return false;
+        }
+        if self.leading_whitespace && message.starts_with([' ', '\t']) {
+            return true;
+        }
+        let trimmed = message.trim_start();
+        self.prefixes
+            .iter()
+            .any(|p| !p.is_empty() && trimmed.starts_with(p.as_str()))
+    }
+
+    /// Prefixes as edited in the UI: one per line.
+    pub fn prefixes_text(&self) -> String {
+        self.prefixes.join("\n")
+    }
+
+    pub fn set_prefixes_text(&mut self, text: &str) {
+        self.prefixes = text
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+}
+
+/// How entries fold into logical entries: each group has a head event and
+/// the continuation events that follow it in the same stream.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Folding {
+    /// For each entry index, the index of the head of its group.
+    pub head_of: Vec<usize>,
+    /// Continuation entry indices of each head that has any, in order.
+    pub members: HashMap<usize, Vec<usize>>,
+}
+
+impl Folding {
+    pub fn is_head(&self, idx: usize) -> bool {
+        self.head_of.get(idx) == Some(&idx)
+    }
+
+    /// Continuation entries folded under `head`.
+    pub fn members_of(&self, head: usize) -> &[usize] {
+        self.members.get(&head).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// Fold continuation events into the latest event of the same stream.
+/// Streams may interleave, so a group's members need not be adjacent.
+pub fn fold_entries(entries: &[LogEntry], rules: &ContinuationRules) -> Folding {
+    let mut folding = Folding {
+        head_of: Vec::with_capacity(entries.len()),
+        members: HashMap::new(),
+    };
+    // Per stream: (head index, timestamp of the group's latest event).
+    let mut open: HashMap<Option<&str>, (usize, i64)> = HashMap::new();
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let stream = entry.log_stream_name.as_deref();
+        let ts = entry.timestamp_millis;
+
+        let head = match open.get(&stream) {
+            Some(&(head, last_ts))
+                if (ts - last_ts).abs() <= rules.max_gap_millis
+                    && rules.is_continuation(&entry.message) =>
+            {
+                folding.members.entry(head).or_default().push(idx);
+                head
+            }
+            _ => idx,
+        };
+        open.insert(stream, (head, ts));
+        folding.head_of.push(head);
+    }
+
+    folding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ts: i64, stream: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: ts,
+            message: message.to_string(),
+            log_stream_name: Some(stream.to_string()),
+            log_group_name: None,
+            event_id: None,
+            ingestion_time_millis: None,
+        }
+    }
+
+    #[test]
+    fn default_rules_recognize_java_and_python_traces() {
+        let rules = ContinuationRules::default();
+        assert!(rules.is_continuation("\tat com.example.Foo.bar(Foo.java:42)"));
+        assert!(rules.is_continuation("Caused by: java.io.IOException: boom"));
+        assert!(rules.is_continuation("Traceback (most recent call last):"));
+        assert!(rules.is_continuation("  File \"app.py\", line 3, in <module>"));
+        assert!(!rules.is_continuation("ERROR request failed"));
+        assert!(!rules.is_continuation("   "));
+    }
+
+    #[test]
+    fn folds_continuations_per_stream_within_gap() {
+        let entries = vec![
+            entry(1_000, "a", "ERROR boom"),
+            entry(1_001, "b", "INFO other stream"),
+            entry(1_002, "a", "\tat Foo.bar(Foo.java:1)"),
+            entry(1_003, "b", "INFO still other"),
+            entry(1_004, "a", "Caused by: x"),
+            entry(9_000, "a", "\tat too.late()"),
+        ];
+        let folding = fold_entries(&entries, &ContinuationRules::default());
+
+        assert_eq!(folding.head_of, vec![0, 1, 0, 3, 0, 5]);
+        assert_eq!(folding.members_of(0), &[2, 4]);
+        assert!(folding.members_of(1).is_empty());
+        assert!(folding.is_head(5));
+        assert!(!folding.is_head(2));
+    }
+
+    #[test]
+    fn prefixes_text_round_trips_one_per_line() {
+        let mut rules = ContinuationRules::default();
+        rules.set_prefixes_text("at \n\n  \nCaused by:");
+        assert_eq!(rules.prefixes, vec!["at ", "Caused by:"]);
+        assert_eq!(rules.prefixes_text(), "at \nCaused by:");
+    }
+}
@@ -0,0 +1,805 @@
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::app::ansi::strip_ansi;
+use crate::app::level::{LogLevel, detect_level};
+use crate::app::state::try_parse_json;
+
+static LAMBDA_START: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^START RequestId: (\S+)(?: Version: (\S+))?").expect("valid regex")
+});
+
+static LAMBDA_END: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^END RequestId: (\S+)").expect("valid regex"));
+
+/// API Gateway's CLF access log format:
+/// `ip caller user [time] "method path protocol" status length requestId`.
+static API_GATEWAY_CLF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^(\S+) (\S+) (\S+) \[([^\]]+)\] "([A-Z]+) (\S+) ([^"]+)" (\d{3}) (\S+) (\S+)$"#)
+        .expect("valid regex")
+});
+
+/// Fields of an ALB access log entry, in order.
+const ALB_FIELDS: [&str; 29] = [
+    "type",
+    "time",
+    "elb",
+    "client",
+    "target",
+    "request_processing_time",
+    "target_processing_time",
+    "response_processing_time",
+    "elb_status_code",
+    "target_status_code",
+    "received_bytes",
+    "sent_bytes",
+    "request",
+    "user_agent",
+    "ssl_cipher",
+    "ssl_protocol",
+    "target_group_arn",
+    "trace_id",
+    "domain_name",
+    "chosen_cert_arn",
+    "matched_rule_priority",
+    "request_creation_time",
+    "actions_executed",
+    "redirect_url",
+    "error_reason",
+    "target_port_list",
+    "target_status_code_list",
+    "classification",
+    "classification_reason",
+];
+
+/// Fields of a Classic Load Balancer access log entry, in order.
+const ELB_FIELDS: [&str; 15] = [
+    "time",
+    "elb",
+    "client",
+    "backend",
+    "request_processing_time",
+    "backend_processing_time",
+    "response_processing_time",
+    "elb_status_code",
+    "backend_status_code",
+    "received_bytes",
+    "sent_bytes",
+    "request",
+    "user_agent",
+    "ssl_cipher",
+    "ssl_protocol",
+];
+
+const ALB_TYPES: [&str; 6] = ["http", "https", "h2", "grpcs", "ws", "wss"];
+
+/// Fields of a default (version 2) VPC Flow Logs record, in order.
+const VPC_FLOW_FIELDS: [&str; 14] = [
+    "version",
+    "account-id",
+    "interface-id",
+    "srcaddr",
+    "dstaddr",
+    "srcport",
+    "dstport",
+    "protocol",
+    "packets",
+    "bytes",
+    "start",
+    "end",
+    "action",
+    "log-status",
+];
+
+/// Field names holding a severity in structured messages.
+const LEVEL_KEYS: [&str; 6] = [
+    "level",
+    "severity",
+    "lvl",
+    "loglevel",
+    "levelname",
+    "log.level",
+];
+
+/// A message format recognized by the parser registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MessageFormat {
+    /// Lambda platform lines: `START`, `END`, `REPORT`, `INIT_START`.
+    LambdaPlatform,
+    ApiGatewayAccess,
+    /// ALB and Classic Load Balancer access logs.
+    LoadBalancerAccess,
+    VpcFlow,
+    CloudTrail,
+    /// ECS Container Insights performance events.
+    ContainerInsights,
+    /// Any other JSON object or array.
+    Json,
+    /// `key=value` pairs, as written by logfmt loggers.
+    Logfmt,
+}
+
+impl MessageFormat {
+    /// All formats, in the order the registry tries them. Generic JSON comes
+    /// last so JSON-based AWS formats are recognized first.
+    pub const ALL: [MessageFormat; 8] = [
+        MessageFormat::LambdaPlatform,
+        MessageFormat::ApiGatewayAccess,
+        MessageFormat::LoadBalancerAccess,
+        MessageFormat::VpcFlow,
+        MessageFormat::CloudTrail,
+        MessageFormat::ContainerInsights,
+        MessageFormat::Json,
+        MessageFormat::Logfmt,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MessageFormat::LambdaPlatform => "Lambda platform",
+            MessageFormat::ApiGatewayAccess => "API Gateway access",
+            MessageFormat::LoadBalancerAccess => "ALB/ELB access",
+            MessageFormat::VpcFlow => "VPC Flow Logs",
+            MessageFormat::CloudTrail => "CloudTrail",
+            MessageFormat::ContainerInsights => "Container Insights",
+            MessageFormat::Json => "JSON",
+            MessageFormat::Logfmt => "logfmt",
+        }
+    }
+
+    /// Fields shown as table columns for this format; empty when they
+    /// depend on the message.
+    #[cfg(feature = "gui")]
+    pub fn default_columns(self) -> &'static [&'static str] {
+        match self {
+            MessageFormat::LambdaPlatform => &[
+                "type",
+                "requestId",
+                "durationMs",
+                "billedDurationMs",
+                "maxMemoryUsedMb",
+                "initDurationMs",
+            ],
+            MessageFormat::ApiGatewayAccess => &[
+                "status",
+                "httpMethod",
+                "resourcePath",
+                "sourceIp",
+                "responseLength",
+            ],
+            MessageFormat::LoadBalancerAccess => &[
+                "elb_status_code",
+                "request_method",
+                "request_url",
+                "client",
+                "target_processing_time",
+            ],
+            MessageFormat::VpcFlow => &[
+                "action", "srcaddr", "srcport", "dstaddr", "dstport", "protocol", "bytes",
+            ],
+            MessageFormat::CloudTrail => &[
+                "eventName",
+                "eventSource",
+                "userIdentity.arn",
+                "sourceIPAddress",
+                "errorCode",
+            ],
+            MessageFormat::ContainerInsights => &[
+                "Type",
+                "ServiceName",
+                "TaskId",
+                "CpuUtilized",
+                "MemoryUtilized",
+            ],
+            MessageFormat::Json | MessageFormat::Logfmt => &[],
+        }
+    }
+
+    /// Fields of `message` if it is in this format. `json` is the message
+    /// parsed as JSON, when it is JSON.
+    fn parse(self, message: &str, json: Option<&JsonValue>) -> Option<JsonValue> {
+        let message = message.trim();
+        match self {
+            MessageFormat::LambdaPlatform => parse_lambda_platform(message),
+            MessageFormat::ApiGatewayAccess => parse_api_gateway(message),
+            MessageFormat::LoadBalancerAccess => parse_load_balancer(message),
+            MessageFormat::VpcFlow => parse_vpc_flow(message),
+            MessageFormat::CloudTrail => json
+                .filter(|j| has_keys(j, &["eventVersion", "eventSource", "eventName"]))
+                .cloned(),
+            MessageFormat::ContainerInsights => json
+                .filter(|j| has_keys(j, &["Type", "ClusterName", "Timestamp"]))
+                .cloned(),
+            MessageFormat::Json => json.cloned(),
+            MessageFormat::Logfmt => parse_logfmt(message),
+        }
+    }
+}
+
+/// A message turned into named fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedMessage {
+    pub format: MessageFormat,
+    /// A JSON object of named fields; for JSON-based formats, the message
+    /// itself.
+    pub fields: JsonValue,
+}
+
+impl ParsedMessage {
+    pub fn field(&self, name: &str) -> Option<&JsonValue> {
+        self.fields.get(name)
+    }
+
+    /// Severity implied by the fields, e.g. an HTTP 5xx status or a
+    /// rejected flow; `None` when the fields say nothing about it.
+    pub fn level(&self) -> Option<LogLevel> {
+        match self.format {
+            MessageFormat::LambdaPlatform => match self.field("status")?.as_str()? {
+                "success" => Some(LogLevel::Info),
+                _ => Some(LogLevel::Error),
+            },
+            MessageFormat::ApiGatewayAccess => status_level(self.field("status")?),
+            MessageFormat::LoadBalancerAccess => status_level(self.field("elb_status_code")?),
+            MessageFormat::VpcFlow => match self.field("action")?.as_str()? {
+                "REJECT" => Some(LogLevel::Warn),
+                _ => Some(LogLevel::Info),
+            },
+            MessageFormat::CloudTrail => Some(if self.field("errorCode").is_some() {
+                LogLevel::Error
+            } else {
+                LogLevel::Info
+            }),
+            MessageFormat::ContainerInsights => None,
+            MessageFormat::Json | MessageFormat::Logfmt => level_from_fields(&self.fields),
+        }
+    }
+}
+
+/// Which formats are recognized. All are enabled by default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParserRegistry {
+    #[serde(default)]
+    pub disabled: BTreeSet<MessageFormat>,
+}
+
+impl ParserRegistry {
+    pub fn is_enabled(&self, format: MessageFormat) -> bool {
+        !self.disabled.contains(&format)
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn set_enabled(&mut self, format: MessageFormat, enabled: bool) {
+        if enabled {
+            self.disabled.remove(&format);
+        } else {
+            self.disabled.insert(format);
+        }
+    }
+
+    /// Named fields of `message` from the first enabled format that
+    /// recognizes it.
+    pub fn parse(&self, message: &str) -> Option<ParsedMessage> {
+        let json = try_parse_json(message);
+        MessageFormat::ALL
+            .into_iter()
+            .filter(|&format| self.is_enabled(format))
+            .find_map(|format| {
+                format
+                    .parse(message, json.as_ref())
+                    .map(|fields| ParsedMessage { format, fields })
+            })
+    }
+
+    /// Parse `message` (escape sequences removed) and take its level from
+    /// the parsed fields, falling back to the usual text markers.
+    pub fn parse_with_level(&self, message: &str) -> (Option<ParsedMessage>, LogLevel) {
+        let plain = strip_ansi(message);
+        let parsed = self.parse(&plain);
+        let level = parsed
+            .as_ref()
+            .and_then(ParsedMessage::level)
+            .unwrap_or_else(|| detect_level(&plain));
+        (parsed, level)
+    }
+}
+
+/// Severity from a `level`-like field: a name such as `warn` or a numeric
+/// (pino / bunyan) level.
+pub fn level_from_fields(fields: &JsonValue) -> Option<LogLevel> {
+    let map = fields.as_object()?;
+    LEVEL_KEYS.iter().find_map(|key| {
+        let value = map
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)?;
+        match value {
+            JsonValue::String(name) => LogLevel::from_name(name),
+            JsonValue::Number(n) => n.as_u64().map(|n| match n {
+                50.. => LogLevel::Error,
+                40..50 => LogLevel::Warn,
+                30..40 => LogLevel::Info,
+                _ => LogLevel::Debug,
+            }),
+            _ => None,
+        }
+    })
+}
+
+fn status_level(status: &JsonValue) -> Option<LogLevel> {
+    Some(match status.as_u64()? {
+        500.. => LogLevel::Error,
+        400..500 => LogLevel::Warn,
+        _ => LogLevel::Info,
+    })
+}
+
+fn has_keys(json: &JsonValue, keys: &[&str]) -> bool {
+    json.as_object()
+        .is_some_and(|map| keys.iter().all(|k| map.contains_key(*k)))
+}
+
+/// A field value: a number when it parses as one, nothing for `-`.
+fn field_value(raw: &str) -> Option<JsonValue> {
+    if raw == "-" || raw.is_empty() {
+        return None;
+    }
+    if let Ok(n) = raw.parse::<i64>() {
+        return Some(JsonValue::from(n));
+    }
+    if let Ok(n) = raw.parse::<f64>()
+        && n.is_finite()
+    {
+        return Some(JsonValue::from(n));
+    }
+    Some(JsonValue::String(raw.to_string()))
+}
+
+fn insert_field(map: &mut Map<String, JsonValue>, name: &str, raw: &str) {
+    if let Some(value) = field_value(raw) {
+        map.insert(name.to_string(), value);
+    }
+}
+
+/// `START`, `END`, `REPORT` and `INIT_START` lines. `REPORT` metrics such as
+/// `Billed Duration: 13 ms` become `billedDurationMs: 13`.
+fn parse_lambda_platform(message: &str) -> Option<JsonValue> {
+    let mut map = Map::new();
+
+    if let Some(caps) = LAMBDA_START.captures(message) {
+        map.insert("type".into(), "START".into());
+        map.insert("requestId".into(), caps[1].into());
+        if let Some(version) = caps.get(2) {
+            map.insert("version".into(), version.as_str().into());
+        }
+        return Some(JsonValue::Object(map));
+    }
+    if let Some(caps) = LAMBDA_END.captures(message) {
+        map.insert("type".into(), "END".into());
+        map.insert("requestId".into(), caps[1].into());
+        return Some(JsonValue::Object(map));
+    }
+
+    let (kind, rest) = message.split_once(' ')?;
+    if !matches!(kind, "REPORT" | "INIT_START" | "INIT_REPORT") {
+        return None;
+    }
+    map.insert("type".into(), kind.into());
+    for pair in rest.split(['\t', '\n']) {
+        let Some((key, value)) = pair.trim().split_once(": ") else {
+            continue;
+        };
+        let (value, unit) = match value.trim().rsplit_once(' ') {
+            Some((number, unit @ ("ms" | "MB"))) => (number, unit),
+            _ => (value.trim(), ""),
+        };
+        insert_field(&mut map, &metric_name(key, unit), value);
+    }
+    // A REPORT line always carries its metrics; other lines need at least
+    // one field besides their type.
+    let recognized = match kind {
+        "REPORT" => map.contains_key("requestId") && map.contains_key("durationMs"),
+        _ => map.len() > 1,
+    };
+    recognized.then_some(JsonValue::Object(map))
+}
+
+/// `Max Memory Used` + `MB` -> `maxMemoryUsedMb`; `XRAY TraceId` -> `xrayTraceId`.
+fn metric_name(key: &str, unit: &str) -> String {
+    let mut name = String::new();
+    for (i, word) in key.split_whitespace().chain(Some(unit)).enumerate() {
+        if word.is_empty() {
+            continue;
+        }
+        let word = if word.chars().all(|c| c.is_ascii_uppercase()) {
+            word.to_ascii_lowercase()
+        } else {
+            word.to_string()
+        };
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if i == 0 {
+                name.extend(first.to_lowercase());
+            } else {
+                name.extend(first.to_uppercase());
+            }
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
+
+fn parse_api_gateway(message: &str) -> Option<JsonValue> {
+    let caps = API_GATEWAY_CLF.captures(message)?;
+    let mut map = Map::new();
+    let names = [
+        "sourceIp",
+        "caller",
+        "user",
+        "requestTime",
+        "httpMethod",
+        "resourcePath",
+        "protocol",
+        "status",
+        "responseLength",
+        "requestId",
+    ];
+    for (idx, name) in names.into_iter().enumerate() {
+        insert_field(&mut map, name, &caps[idx + 1]);
+    }
+    Some(JsonValue::Object(map))
+}
+
+fn parse_load_balancer(message: &str) -> Option<JsonValue> {
+    let tokens = split_quoted(message)?;
+    let first = tokens.first()?;
+    let names: &[&str] = if ALB_TYPES.contains(&first.as_str()) {
+        &ALB_FIELDS
+    } else if chrono::DateTime::parse_from_rfc3339(first).is_ok() {
+        &ELB_FIELDS
+    } else {
+        return None;
+    };
+    // Everything up to and including the request line is always present.
+    let required = names.iter().position(|&n| n == "request")? + 1;
+    if tokens.len() < required || tokens.len() > names.len() + 4 {
+        return None;
+    }
+
+    let mut map = Map::new();
+    for (name, raw) in names.iter().zip(&tokens) {
+        match *name {
+            // `ip:port`, or `-` when the request never reached a target.
+            "client" | "target" | "backend" => {
+                let (ip, port) = raw.rsplit_once(':').unwrap_or((raw.as_str(), "-"));
+                insert_field(&mut map, name, ip);
+                insert_field(&mut map, &format!("{name}_port"), port);
+            }
+            "request" => {
+                insert_field(&mut map, name, raw);
+                let mut parts = raw.splitn(3, ' ');
+                for part in ["request_method", "request_url", "request_protocol"] {
+                    insert_field(&mut map, part, parts.next().unwrap_or_default());
+                }
+            }
+            _ => insert_field(&mut map, name, raw),
+        }
+    }
+    // Status codes are numbers even when a field in between was malformed.
+    map.get("elb_status_code")?.as_u64()?;
+    Some(JsonValue::Object(map))
+}
+
+fn parse_vpc_flow(message: &str) -> Option<JsonValue> {
+    let tokens: Vec<&str> = message.split_whitespace().collect();
+    if tokens.len() != VPC_FLOW_FIELDS.len()
+        || tokens[0] != "2"
+        || !matches!(tokens[12], "ACCEPT" | "REJECT" | "-")
+    {
+        return None;
+    }
+    let mut map = Map::new();
+    for (name, raw) in VPC_FLOW_FIELDS.iter().zip(tokens) {
+        insert_field(&mut map, name, raw);
+    }
+    Some(JsonValue::Object(map))
+}
+
+/// `key=value` pairs separated by spaces; values may be `"quoted"` with
+/// backslash escapes. Numbers and booleans become JSON numbers and booleans.
+///
+/// Needs at least two pairs making up at least half of the words, so prose
+/// that mentions `id=5` in passing stays plain text. Bare words carry no
+/// value and are skipped.
+fn parse_logfmt(message: &str) -> Option<JsonValue> {
+    let mut map = Map::new();
+    let mut words = 0;
+    let mut chars = message.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+        words += 1;
+
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != '=') {
+            key.push(c);
+        }
+        if chars.next_if_eq(&'=').is_none() || key.is_empty() || key.contains('"') {
+            // A bare word (or `=value`): skip the rest of it.
+            while chars.next_if(|c| !c.is_whitespace()).is_some() {}
+            continue;
+        }
+
+        let value = if chars.next_if_eq(&'"').is_some() {
+            let mut value = String::new();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => match chars.next()? {
+                        'n' => value.push('\n'),
+                        't' => value.push('\t'),
+                        'r' => value.push('\r'),
+                        other => value.push(other),
+                    },
+                    c => value.push(c),
+                }
+            }
+            JsonValue::String(value)
+        } else {
+            let mut raw = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                raw.push(c);
+            }
+            logfmt_value(&raw)
+        };
+        map.insert(key, value);
+    }
+
+    (map.len() >= 2 && map.len() * 2 >= words).then_some(JsonValue::Object(map))
+}
+
+fn logfmt_value(raw: &str) -> JsonValue {
+    match raw {
+        "true" => JsonValue::Bool(true),
+        "false" => JsonValue::Bool(false),
+        _ => field_value(raw).unwrap_or_else(|| JsonValue::String(raw.to_string())),
+    }
+}
+
+/// Split on spaces, keeping `"quoted strings"` (without the quotes) as one
+/// token. `None` on an unterminated quote.
+fn split_quoted(line: &str) -> Option<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut rest = line.trim_start();
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"')?;
+            tokens.push(quoted[..end].to_string());
+            rest = quoted[end + 1..].trim_start();
+        } else {
+            let end = rest.find(' ').unwrap_or(rest.len());
+            tokens.push(rest[..end].to_string());
+            rest = rest[end..].trim_start();
+        }
+    }
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(message: &str) -> ParsedMessage {
+        ParserRegistry::default()
+            .parse(message)
+            .expect("recognized message")
+    }
+
+    #[test]
+    fn level_comes_from_fields_then_text() {
+        let parsers = ParserRegistry::default();
+        let (parsed, level) = parsers.parse_with_level("\u{1b}[31m{\"level\":\"warn\"}\u{1b}[0m");
+        assert!(parsed.is_some());
+        assert_eq!(level, LogLevel::Warn);
+        assert_eq!(
+            parsers.parse_with_level("ERROR plain text").1,
+            LogLevel::Error
+        );
+        // Markers split by color codes still count.
+        assert_eq!(
+            parsers
+                .parse_with_level("\u{1b}[1mERR\u{1b}[31mOR\u{1b}[0m boom")
+                .1,
+            LogLevel::Error
+        );
+    }
+
+    #[test]
+    fn parses_lambda_platform_lines() {
+        let id = "8f5f8f7a-1c2b-4d3e-9f00-0123456789ab";
+
+        let start = parse(&format!("START RequestId: {id} Version: $LATEST\n"));
+        assert_eq!(start.format, MessageFormat::LambdaPlatform);
+        assert_eq!(
+            start.fields,
+            json!({"type": "START", "requestId": id, "version": "$LATEST"})
+        );
+
+        let end = parse(&format!("END RequestId: {id}"));
+        assert_eq!(end.fields, json!({"type": "END", "requestId": id}));
+
+        let report = parse(&format!(
+            "REPORT RequestId: {id}\tDuration: 12.34 ms\tBilled Duration: 13 ms\tMemory Size: 128 MB\tMax Memory Used: 70 MB\tInit Duration: 150.5 ms\t\nXRAY TraceId: 1-5759e988-bd862e3fe1be46a994272793\tSampled: true\t"
+        ));
+        assert_eq!(
+            report.fields,
+            json!({
+                "type": "REPORT",
+                "requestId": id,
+                "durationMs": 12.34,
+                "billedDurationMs": 13,
+                "memorySizeMb": 128,
+                "maxMemoryUsedMb": 70,
+                "initDurationMs": 150.5,
+                "xrayTraceId": "1-5759e988-bd862e3fe1be46a994272793",
+                "sampled": "true",
+            })
+        );
+        assert_eq!(report.level(), None);
+
+        let timeout = parse(&format!(
+            "REPORT RequestId: {id}\tDuration: 3000.00 ms\tBilled Duration: 3000 ms\tMemory Size: 128 MB\tMax Memory Used: 80 MB\tStatus: timeout"
+        ));
+        assert_eq!(timeout.level(), Some(LogLevel::Error));
+
+        assert!(
+            ParserRegistry::default()
+                .parse("REPORT generated for Q3")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parses_api_gateway_clf_access_logs() {
+        let parsed = parse(
+            r#"203.0.113.7 - - [18/Oct/2026:10:00:00 +0000] "POST /orders HTTP/1.1" 502 36 c0ffee00-1111-2222-3333-444455556666"#,
+        );
+        assert_eq!(parsed.format, MessageFormat::ApiGatewayAccess);
+        assert_eq!(parsed.field("sourceIp"), Some(&json!("203.0.113.7")));
+        assert_eq!(parsed.field("httpMethod"), Some(&json!("POST")));
+        assert_eq!(parsed.field("resourcePath"), Some(&json!("/orders")));
+        assert_eq!(parsed.field("status"), Some(&json!(502)));
+        assert_eq!(parsed.field("caller"), None);
+        assert_eq!(parsed.level(), Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn parses_alb_and_classic_elb_access_logs() {
+        let alb = parse(
+            r#"https 2026-10-18T10:00:00.000000Z app/my-lb/50dc6c495c0c9188 192.168.1.10:2817 10.0.0.1:80 0.000 0.012 0.000 404 404 34 366 "GET https://example.com:443/missing HTTP/1.1" "curl/8.0" ECDHE-RSA-AES128-GCM-SHA256 TLSv1.2 arn:aws:elasticloadbalancing:us-east-1:123:targetgroup/tg/73e2d6bc24d8a067 "Root=1-58337262-36d228ad5d99923122bbe354" "example.com" "-" 0 2026-10-18T10:00:00.000000Z "forward" "-" "-" "10.0.0.1:80" "404" "-" "-""#,
+        );
+        assert_eq!(alb.format, MessageFormat::LoadBalancerAccess);
+        assert_eq!(alb.field("client"), Some(&json!("192.168.1.10")));
+        assert_eq!(alb.field("client_port"), Some(&json!(2817)));
+        assert_eq!(alb.field("elb_status_code"), Some(&json!(404)));
+        assert_eq!(alb.field("target_processing_time"), Some(&json!(0.012)));
+        assert_eq!(alb.field("request_method"), Some(&json!("GET")));
+        assert_eq!(alb.field("user_agent"), Some(&json!("curl/8.0")));
+        assert_eq!(alb.field("classification"), None);
+        assert_eq!(alb.level(), Some(LogLevel::Warn));
+
+        let elb = parse(
+            r#"2026-10-18T10:00:00.945958Z my-clb 192.168.131.39:2817 10.0.0.1:80 0.000073 0.001048 0.000057 200 200 0 29 "GET http://www.example.com:80/ HTTP/1.1" "curl/7.38.0" - -"#,
+        );
+        assert_eq!(elb.format, MessageFormat::LoadBalancerAccess);
+        assert_eq!(elb.field("backend"), Some(&json!("10.0.0.1")));
+        assert_eq!(elb.field("backend_status_code"), Some(&json!(200)));
+        assert_eq!(elb.field("ssl_cipher"), None);
+        assert_eq!(elb.level(), Some(LogLevel::Info));
+    }
+
+    #[test]
+    fn parses_default_vpc_flow_log_records() {
+        let parsed = parse(
+            "2 123456789010 eni-1235b8ca123456789 172.31.9.69 172.31.9.12 49761 3389 6 20 4249 1418530010 1418530070 REJECT OK",
+        );
+        assert_eq!(parsed.format, MessageFormat::VpcFlow);
+        assert_eq!(parsed.field("account-id"), Some(&json!(123456789010_i64)));
+        assert_eq!(parsed.field("dstport"), Some(&json!(3389)));
+        assert_eq!(parsed.field("action"), Some(&json!("REJECT")));
+        assert_eq!(parsed.level(), Some(LogLevel::Warn));
+
+        let nodata = parse(
+            "2 123456789010 eni-1235b8ca123456789 - - - - - - - 1431280876 1431280934 - NODATA",
+        );
+        assert_eq!(nodata.field("srcaddr"), None);
+    }
+
+    #[test]
+    fn recognizes_cloudtrail_and_container_insights_json() {
+        let trail = parse(
+            r#"{"eventVersion":"1.08","eventSource":"s3.amazonaws.com","eventName":"GetObject","errorCode":"AccessDenied","userIdentity":{"arn":"arn:aws:iam::123:user/bob"}}"#,
+        );
+        assert_eq!(trail.format, MessageFormat::CloudTrail);
+        assert_eq!(trail.level(), Some(LogLevel::Error));
+
+        let perf = parse(
+            r#"{"Version":"0","Type":"Task","TaskId":"abc","ClusterName":"default","Timestamp":1760781600000,"CpuUtilized":12.5,"MemoryUtilized":256}"#,
+        );
+        assert_eq!(perf.format, MessageFormat::ContainerInsights);
+        assert_eq!(perf.field("CpuUtilized"), Some(&json!(12.5)));
+        assert_eq!(perf.level(), None);
+
+        let plain = parse(r#"{"level":"warning","msg":"slow"}"#);
+        assert_eq!(plain.format, MessageFormat::Json);
+        assert_eq!(plain.level(), Some(LogLevel::Warn));
+        assert_eq!(parse(r#"{"level":50}"#).level(), Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn parses_logfmt_pairs() {
+        let parsed = parse(
+            r#"time=2026-10-18T10:00:00Z level=warn msg="slow query \"orders\"" dur=12ms rows=42 cached=false err="""#,
+        );
+        assert_eq!(parsed.format, MessageFormat::Logfmt);
+        assert_eq!(
+            parsed.fields,
+            json!({
+                "time": "2026-10-18T10:00:00Z",
+                "level": "warn",
+                "msg": "slow query \"orders\"",
+                "dur": "12ms",
+                "rows": 42,
+                "cached": false,
+                "err": "",
+            })
+        );
+        assert_eq!(parsed.level(), Some(LogLevel::Warn));
+
+        // Go's slog text handler.
+        let slog = parse(r#"time=2026-10-18T10:00:00.000Z level=ERROR msg="db down" attempt=3"#);
+        assert_eq!(slog.level(), Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn prose_with_a_stray_pair_is_not_logfmt() {
+        let registry = ParserRegistry::default();
+        assert!(
+            registry
+                .parse("processing order id=5 for the customer")
+                .is_none()
+        );
+        assert!(registry.parse("a=1").is_none());
+        assert!(registry.parse(r#"a=1 b="unterminated"#).is_none());
+        assert!(registry.parse("retrying a=1 b=2").is_some());
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn disabled_formats_are_skipped() {
+        let mut registry = ParserRegistry::default();
+        let trail = r#"{"eventVersion":"1.08","eventSource":"s3","eventName":"GetObject"}"#;
+
+        registry.set_enabled(MessageFormat::CloudTrail, false);
+        assert_eq!(
+            registry.parse(trail).map(|p| p.format),
+            Some(MessageFormat::Json)
+        );
+        registry.set_enabled(MessageFormat::CloudTrail, true);
+        assert!(registry.is_enabled(MessageFormat::CloudTrail));
+        assert!(registry.parse("plain text").is_none());
+    }
+
+    #[test]
+    fn metric_names_are_camel_case_with_unit() {
+        assert_eq!(metric_name("Max Memory Used", "MB"), "maxMemoryUsedMb");
+        assert_eq!(metric_name("XRAY TraceId", ""), "xrayTraceId");
+        assert_eq!(metric_name("RequestId", ""), "requestId");
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::app::ansi::strip_ansi;
+use crate::aws::LogEntry;
+
+/// Token standing for the positions where a pattern's messages differ.
+pub const WILDCARD: &str = "<*>";
+
+/// Messages at least this similar (share of equal tokens) join a pattern.
+const SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// Buckets of a pattern's sparkline.
+pub const SPARKLINE_BUCKETS: usize = 30;
+
+static UUID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}")
+        .expect("valid regex")
+});
+
+static IP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}(?::\d+)?\b").expect("valid regex")
+});
+
+static HEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:0x[0-9a-fA-F]+|[0-9a-fA-F]{8,})\b").expect("valid regex"));
+
+static NUMBER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b\d+(?:\.\d+)?").expect("valid regex"));
+
+/// The first line of `message` split into tokens, with variable parts
+/// (UUIDs, IPs, hex IDs, numbers) masked.
+pub fn mask_message(message: &str) -> Vec<String> {
+    let line = strip_ansi(message);
+    let line = line.lines().next().unwrap_or_default();
+    let line = UUID.replace_all(line, "<uuid>");
+    let line = IP.replace_all(&line, "<ip>");
+    let line = HEX.replace_all(&line, |caps: &regex::Captures| {
+        let text = &caps[0];
+        // Words made of a-f only (`deadbeef`) are not IDs; plain numbers are
+        // masked as numbers below.
+        let has_digit = text.contains(|c: char| c.is_ascii_digit());
+        let has_letter = text.contains(|c: char| c.is_ascii_alphabetic());
+        if text.starts_with("0x") || (has_digit && has_letter) {
+            "<hex>".to_string()
+        } else {
+            text.to_string()
+        }
+    });
+    let line = NUMBER.replace_all(&line, "<num>");
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+/// A message template; `<*>` matches any one token.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Template(pub Vec<String>);
+
+impl Template {
+    pub fn matches(&self, tokens: &[String]) -> bool {
+        self.0.len() == tokens.len()
+            && self
+                .0
+                .iter()
+                .zip(tokens)
+                .all(|(t, token)| t == WILDCARD || t == token)
+    }
+
+    pub fn text(&self) -> String {
+        self.0.join(" ")
+    }
+
+    /// Share of positions where `tokens` equals the template (wildcards
+    /// count as equal).
+    fn similarity(&self, tokens: &[String]) -> f64 {
+        if self.0.is_empty() {
+            return 1.0;
+        }
+        let same = self
+            .0
+            .iter()
+            .zip(tokens)
+            .filter(|(t, token)| *t == WILDCARD || t == token)
+            .count();
+        same as f64 / self.0.len() as f64
+    }
+
+    /// Generalize the positions where `tokens` differs.
+    fn merge(&mut self, tokens: &[String]) {
+        for (t, token) in self.0.iter_mut().zip(tokens) {
+            if t != token {
+                *t = WILDCARD.to_string();
+            }
+        }
+    }
+}
+
+/// A cluster of similar messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub template: Template,
+    /// Indices into the entries, in order.
+    pub members: Vec<usize>,
+    pub first_seen: i64,
+    pub last_seen: i64,
+    /// Member counts over the time span of all entries.
+    pub sparkline: Vec<u32>,
+}
+
+/// Which patterns the list shows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PatternFilter {
+    /// Show only messages of this pattern.
+    pub only: Option<Template>,
+    /// Hide messages of these patterns ("noise").
+    pub hidden: Vec<Template>,
+}
+
+impl PatternFilter {
+    pub fn is_active(&self) -> bool {
+        self.only.is_some() || !self.hidden.is_empty()
+    }
+
+    pub fn matches(&self, tokens: &[String]) -> bool {
+        self.only.as_ref().is_none_or(|t| t.matches(tokens))
+            && !self.hidden.iter().any(|t| t.matches(tokens))
+    }
+
+    pub fn is_hidden(&self, template: &Template) -> bool {
+        self.hidden.contains(template)
+    }
+
+    pub fn toggle_hidden(&mut self, template: &Template) {
+        if let Some(pos) = self.hidden.iter().position(|t| t == template) {
+            self.hidden.remove(pos);
+        } else {
+            self.hidden.push(template.clone());
+        }
+    }
+}
+
+/// Cluster messages Drain-style: messages with the same number of tokens
+/// and the same first token are compared position by position, and join the
+/// most similar pattern if similar enough, which generalizes the differing
+/// positions to `<*>`. Patterns are returned most frequent first.
+pub fn cluster(entries: &[LogEntry], masked: &[Vec<String>]) -> Vec<Pattern> {
+    let mut patterns: Vec<Pattern> = Vec::new();
+    // (token count, leading token) -> pattern indices.
+    let mut groups: HashMap<(usize, &str), Vec<usize>> = HashMap::new();
+
+    for (idx, tokens) in masked.iter().enumerate() {
+        let lead = match tokens.first() {
+            Some(first) if !first.starts_with('<') => first.as_str(),
+            _ => WILDCARD,
+        };
+        let candidates = groups.entry((tokens.len(), lead)).or_default();
+        let best = candidates
+            .iter()
+            .map(|&p| (p, patterns[p].template.similarity(tokens)))
+            .filter(|&(_, similarity)| similarity >= SIMILARITY_THRESHOLD)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(p, _)| p);
+
+        let ts = entries[idx].timestamp_millis;
+        match best {
+            Some(p) => {
+                let pattern = &mut patterns[p];
+                pattern.template.merge(tokens);
+                pattern.members.push(idx);
+                pattern.first_seen = pattern.first_seen.min(ts);
+                pattern.last_seen = pattern.last_seen.max(ts);
+            }
+            None => {
+                candidates.push(patterns.len());
+                patterns.push(Pattern {
+                    template: Template(tokens.clone()),
+                    members: vec![idx],
+                    first_seen: ts,
+                    last_seen: ts,
+                    sparkline: Vec::new(),
+                });
+            }
+        }
+    }
+
+    let start = entries
+        .iter()
+        .map(|e| e.timestamp_millis)
+        .min()
+        .unwrap_or(0);
+    let end = entries
+        .iter()
+        .map(|e| e.timestamp_millis)
+        .max()
+        .unwrap_or(0);
+    let span = (end - start).max(1) as f64;
+    for pattern in &mut patterns {
+        let mut buckets = vec![0; SPARKLINE_BUCKETS];
+        for &idx in &pattern.members {
+            let offset = (entries[idx].timestamp_millis - start) as f64 / span;
+            let bucket = ((offset * SPARKLINE_BUCKETS as f64) as usize).min(SPARKLINE_BUCKETS - 1);
+            buckets[bucket] += 1;
+        }
+        pattern.sparkline = buckets;
+    }
+
+    patterns.sort_by_key(|p| std::cmp::Reverse(p.members.len()));
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ts: i64, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: ts,
+            message: message.to_string(),
+            log_stream_name: None,
+            log_group_name: None,
+            event_id: None,
+            ingestion_time_millis: None,
+        }
+    }
+
+    fn tokens(text: &str) -> Vec<String> {
+        text.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn masks_variable_parts() {
+        assert_eq!(
+            mask_message(
+                "user 42 from 10.0.0.1:443 req 8f5f8f7a-1c2b-4d3e-9f00-0123456789ab obj 0x1f 5e8a9c3b7d took 12.5ms on ec2 deadbeef at 1418530010\nsecond line"
+            ),
+            tokens(
+                "user <num> from <ip> req <uuid> obj <hex> <hex> took <num>ms on ec2 deadbeef at <num>"
+            )
+        );
+    }
+
+    #[test]
+    fn clusters_similar_messages_into_templates() {
+        let entries = vec![
+            entry(0, "GET /users/1 took 12ms"),
+            entry(10, "connection reset by peer"),
+            entry(20, "GET /users/2 took 30ms"),
+            entry(30, "GET /orders took 5ms"),
+            entry(40, "POST /users took 7ms"),
+        ];
+        let masked: Vec<_> = entries.iter().map(|e| mask_message(&e.message)).collect();
+        let patterns = cluster(&entries, &masked);
+
+        assert_eq!(patterns.len(), 3);
+        assert_eq!(patterns[0].template.text(), "GET <*> took <num>ms");
+        assert_eq!(patterns[0].members, vec![0, 2, 3]);
+        assert_eq!((patterns[0].first_seen, patterns[0].last_seen), (0, 30));
+        assert_eq!(patterns[0].sparkline.iter().sum::<u32>(), 3);
+        assert_eq!(patterns[0].sparkline[0], 1);
+        assert_eq!(patterns[0].sparkline[SPARKLINE_BUCKETS - 1], 0);
+    }
+
+    #[test]
+    fn pattern_filter_shows_only_or_hides_templates() {
+        let noise = Template(tokens("health check <num>"));
+        let mut filter = PatternFilter::default();
+        assert!(!filter.is_active());
+
+        filter.toggle_hidden(&noise);
+        assert!(filter.is_hidden(&noise));
+        assert!(!filter.matches(&tokens("health check <num>")));
+        assert!(filter.matches(&tokens("order placed")));
+
+        filter.only = Some(Template(tokens("order <*>")));
+        assert!(filter.matches(&tokens("order placed")));
+        assert!(!filter.matches(&tokens("order placed twice")));
+
+        filter.toggle_hidden(&noise);
+        assert!(filter.hidden.is_empty());
+    }
+}
@@ -0,0 +1,620 @@
+use std::borrow::Cow;
+
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use thiserror::Error;
+
+use crate::app::ansi::strip_ansi;
+use crate::app::json_tree::{lookup_path, value_to_copy_text};
+use crate::app::level::LogLevel;
+use crate::app::parsers::ParsedMessage;
+use crate::aws::LogEntry;
+
+/// Fields that are not parsed from the message but always available.
+#[cfg(feature = "gui")]
+pub const PSEUDO_FIELDS: [&str; 5] = ["level", "@message", "@stream", "@group", "@format"];
+
+/// Comparison operators; where several start at the same position (`>=`
+/// and `>`), the longest wins.
+const OPERATORS: [&str; 8] = [">=", "<=", "!=", ">", "<", ":", "=", "~"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum QueryError {
+    #[error("unexpected end of query")]
+    UnexpectedEnd,
+    #[error("unexpected `{token}` at column {column}")]
+    Unexpected { token: String, column: usize },
+    #[error("missing `)` for `(` at column {0}")]
+    UnclosedParen(usize),
+    #[error("unterminated quote at column {0}")]
+    UnclosedQuote(usize),
+    #[error("missing value after `{field}{op}`")]
+    MissingValue { field: String, op: String },
+    #[error("`{0}` is not a number or duration")]
+    InvalidNumber(String),
+    #[error("invalid regex `{pattern}`: {message}")]
+    InvalidRegex { pattern: String, message: String },
+}
+
+/// A parsed local query, such as
+/// `level:error AND status>=500 AND NOT path:/health`.
+#[derive(Debug, Clone)]
+pub enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    /// Case-insensitive substring of the message, kept lowercased.
+    Text(String),
+    Field {
+        field: String,
+        condition: Condition,
+    },
+}
+
+/// What a field value is tested against.
+#[derive(Debug, Clone)]
+pub enum Condition {
+    /// `field:value` / `field=value`: case-insensitive equality.
+    Equals(String),
+    /// `field:val*`: case-insensitive wildcard match.
+    Glob(Regex),
+    /// `field>n` etc., on numbers and durations (in milliseconds).
+    Compare(CompareOp, f64),
+    /// `field:a..b`, inclusive.
+    Range(f64, f64),
+    /// `field~regex`.
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// What a query is evaluated against: an entry and what was derived from it.
+pub struct QueryTarget<'a> {
+    pub entry: &'a LogEntry,
+    /// The message lowercased, without ANSI escapes.
+    pub search_text: &'a str,
+    pub level: LogLevel,
+    pub parsed: Option<&'a ParsedMessage>,
+}
+
+impl Query {
+    /// Parse `text`; `Ok(None)` when it is blank.
+    pub fn parse(text: &str) -> Result<Option<Query>, QueryError> {
+        let tokens = tokenize(text)?;
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.parse_or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(Some(query)),
+            Some((token, column)) => Err(QueryError::Unexpected {
+                token: token.to_string(),
+                column: column + 1,
+            }),
+        }
+    }
+
+    pub fn matches(&self, target: &QueryTarget) -> bool {
+        match self {
+            Query::And(a, b) => a.matches(target) && b.matches(target),
+            Query::Or(a, b) => a.matches(target) || b.matches(target),
+            Query::Not(q) => !q.matches(target),
+            Query::Text(text) => target.search_text.contains(text.as_str()),
+            Query::Field { field, condition } => {
+                let is_level = field.eq_ignore_ascii_case("level");
+                match field_value(field, target) {
+                    None => false,
+                    Some(FieldValue::Text(text)) => condition.matches_text(&text, is_level),
+                    Some(FieldValue::Json(value)) => match value.as_ref() {
+                        JsonValue::Array(items) => {
+                            items.iter().any(|item| condition.matches(item, is_level))
+                        }
+                        value => condition.matches(value, is_level),
+                    },
+                }
+            }
+        }
+    }
+}
+
+impl Condition {
+    fn matches(&self, value: &JsonValue, is_level: bool) -> bool {
+        match (self, value) {
+            (_, JsonValue::String(text)) => self.matches_text(text, is_level),
+            (Condition::Compare(..) | Condition::Range(..), JsonValue::Number(n)) => {
+                n.as_f64().is_some_and(|v| self.matches_scalar(v))
+            }
+            (_, value) => self.matches_text(&value_to_copy_text(value), is_level),
+        }
+    }
+
+    fn matches_text(&self, text: &str, is_level: bool) -> bool {
+        match self {
+            Condition::Equals(expected) if is_level => {
+                normalize_level(text) == normalize_level(expected)
+            }
+            Condition::Equals(expected) => text
+                .chars()
+                .flat_map(char::to_lowercase)
+                .eq(expected.chars()),
+            Condition::Glob(re) | Condition::Regex(re) => re.is_match(text),
+            Condition::Compare(..) | Condition::Range(..) => {
+                parse_scalar(text).is_some_and(|v| self.matches_scalar(v))
+            }
+        }
+    }
+
+    fn matches_scalar(&self, value: f64) -> bool {
+        match self {
+            Condition::Compare(CompareOp::Gt, bound) => value > *bound,
+            Condition::Compare(CompareOp::Ge, bound) => value >= *bound,
+            Condition::Compare(CompareOp::Lt, bound) => value < *bound,
+            Condition::Compare(CompareOp::Le, bound) => value <= *bound,
+            Condition::Range(low, high) => (*low..=*high).contains(&value),
+            Condition::Equals(_) | Condition::Glob(_) | Condition::Regex(_) => false,
+        }
+    }
+}
+
+/// A field's value: the text of a pseudo field, or a parsed JSON value.
+enum FieldValue<'a> {
+    Text(Cow<'a, str>),
+    Json(Cow<'a, JsonValue>),
+}
+
+/// The value of `field` for `target`: a pseudo field, or a path into the
+/// parsed fields. Borrowed from the entry where possible.
+fn field_value<'a>(field: &str, target: &QueryTarget<'a>) -> Option<FieldValue<'a>> {
+    let entry = target.entry;
+    let text = |s: &'a str| Some(FieldValue::Text(Cow::Borrowed(s)));
+    match field.to_ascii_lowercase().as_str() {
+        "level" => Some(FieldValue::Text(Cow::Owned(
+            target.level.label().to_lowercase(),
+        ))),
+        "@message" => Some(FieldValue::Text(strip_ansi(&entry.message))),
+        "@stream" => text(entry.log_stream_name.as_deref()?),
+        "@group" => text(entry.log_group_name.as_deref()?),
+        "@format" => text(target.parsed?.format.label()),
+        _ => lookup_path(&target.parsed?.fields, field).map(FieldValue::Json),
+    }
+}
+
+fn normalize_level(name: &str) -> String {
+    LogLevel::from_name(name)
+        .map(|l| l.label().to_lowercase())
+        .unwrap_or_else(|| name.to_lowercase())
+}
+
+/// A number, or a duration such as `250ms`, `1.5s` or `2m` in milliseconds.
+pub fn parse_scalar(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if let Ok(n) = text.parse::<f64>() {
+        return n.is_finite().then_some(n);
+    }
+    let split = text.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))?;
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let millis_per_unit = match unit {
+        "ns" => 1e-6,
+        "us" | "µs" => 1e-3,
+        "ms" => 1.0,
+        "s" => 1_000.0,
+        "m" => 60_000.0,
+        "h" => 3_600_000.0,
+        _ => return None,
+    };
+    Some(number * millis_per_unit)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Text(String),
+    Term {
+        field: String,
+        op: &'static str,
+        value: String,
+    },
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::LParen => f.write_str("("),
+            Token::RParen => f.write_str(")"),
+            Token::And => f.write_str("AND"),
+            Token::Or => f.write_str("OR"),
+            Token::Not => f.write_str("NOT"),
+            Token::Text(text) => f.write_str(text),
+            Token::Term { field, op, value } => write!(f, "{field}{op}{value}"),
+        }
+    }
+}
+
+/// Split a query into tokens with their (0-based) character columns.
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, QueryError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        match chars[i] {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push((Token::LParen, start));
+                i += 1;
+            }
+            ')' => {
+                tokens.push((Token::RParen, start));
+                i += 1;
+            }
+            '"' => {
+                let (value, end) = read_quoted(&chars, i)?;
+                tokens.push((Token::Text(value), start));
+                i = end;
+            }
+            _ => {
+                while i < chars.len() && !chars[i].is_whitespace() && !"()\"".contains(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let token = match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => match split_operator(&word) {
+                        Some((field, op, value)) => {
+                            let value = if value.is_empty() && chars.get(i) == Some(&'"') {
+                                let (quoted, end) = read_quoted(&chars, i)?;
+                                i = end;
+                                quoted
+                            } else if value.is_empty() {
+                                return Err(QueryError::MissingValue {
+                                    field: field.to_string(),
+                                    op: op.to_string(),
+                                });
+                            } else {
+                                value.to_string()
+                            };
+                            Token::Term {
+                                field: field.to_string(),
+                                op,
+                                value,
+                            }
+                        }
+                        None => Token::Text(word),
+                    },
+                };
+                tokens.push((token, start));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// `"…"` starting at `start`, with `\"` and `\\` escapes; returns the
+/// content and the index after the closing quote.
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize), QueryError> {
+    let mut value = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '"' => return Ok((value, i + 1)),
+            '\\' if matches!(chars.get(i + 1), Some('"' | '\\')) => {
+                value.push(chars[i + 1]);
+                i += 2;
+            }
+            c => {
+                value.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err(QueryError::UnclosedQuote(start + 1))
+}
+
+/// `field`, operator and value of a word such as `status>=500`.
+fn split_operator(word: &str) -> Option<(&str, &'static str, &str)> {
+    let (pos, op) = OPERATORS
+        .iter()
+        .filter_map(|op| word.find(op).map(|pos| (pos, *op)))
+        .min_by_key(|&(pos, op)| (pos, std::cmp::Reverse(op.len())))?;
+    if pos == 0 {
+        return None;
+    }
+    Some((&word[..pos], op, &word[pos + op.len()..]))
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Query, QueryError> {
+        let mut query = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            query = Query::Or(Box::new(query), Box::new(self.parse_and()?));
+        }
+        Ok(query)
+    }
+
+    /// Adjacent terms without an operator are ANDed.
+    fn parse_and(&mut self) -> Result<Query, QueryError> {
+        let mut query = self.parse_not()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => self.pos += 1,
+                Some(Token::LParen | Token::Not | Token::Text(_) | Token::Term { .. }) => {}
+                _ => break,
+            }
+            query = Query::And(Box::new(query), Box::new(self.parse_not()?));
+        }
+        Ok(query)
+    }
+
+    fn parse_not(&mut self) -> Result<Query, QueryError> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(Query::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Query, QueryError> {
+        match self.next() {
+            None => Err(QueryError::UnexpectedEnd),
+            Some((Token::LParen, column)) => {
+                let query = self.parse_or()?;
+                match self.next() {
+                    Some((Token::RParen, _)) => Ok(query),
+                    _ => Err(QueryError::UnclosedParen(column + 1)),
+                }
+            }
+            Some((Token::Text(text), _)) => Ok(Query::Text(text.to_lowercase())),
+            Some((Token::Term { field, op, value }, _)) => term(field, op, value),
+            Some((token, column)) => Err(QueryError::Unexpected {
+                token: token.to_string(),
+                column: column + 1,
+            }),
+        }
+    }
+}
+
+fn term(field: String, op: &str, value: String) -> Result<Query, QueryError> {
+    let scalar =
+        |text: &str| parse_scalar(text).ok_or_else(|| QueryError::InvalidNumber(text.to_string()));
+    let regex = |pattern: &str| {
+        Regex::new(pattern).map_err(|e| QueryError::InvalidRegex {
+            pattern: pattern.to_string(),
+            message: e.to_string(),
+        })
+    };
+
+    let condition = match op {
+        ">" => Condition::Compare(CompareOp::Gt, scalar(&value)?),
+        ">=" => Condition::Compare(CompareOp::Ge, scalar(&value)?),
+        "<" => Condition::Compare(CompareOp::Lt, scalar(&value)?),
+        "<=" => Condition::Compare(CompareOp::Le, scalar(&value)?),
+        "~" => Condition::Regex(regex(&value)?),
+        _ => match value
+            .split_once("..")
+            .map(|(a, b)| (parse_scalar(a), parse_scalar(b)))
+        {
+            Some((Some(low), Some(high))) => Condition::Range(low, high),
+            _ if value.contains('*') => {
+                let pattern = value
+                    .split('*')
+                    .map(regex::escape)
+                    .collect::<Vec<_>>()
+                    .join(".*");
+                Condition::Glob(regex(&format!("(?i)^{pattern}$"))?)
+            }
+            _ => Condition::Equals(value.to_lowercase()),
+        },
+    };
+
+    let query = Query::Field { field, condition };
+    Ok(if op == "!=" {
+        Query::Not(Box::new(query))
+    } else {
+        query
+    })
+}
+
+/// The field name being typed at the end of `text`, if any: the last word,
+/// when it has no operator yet.
+#[cfg(feature = "gui")]
+pub fn completion_prefix(text: &str) -> Option<&str> {
+    if text.ends_with(char::is_whitespace) {
+        return None;
+    }
+    let start = text
+        .rfind(|c: char| c.is_whitespace() || c == '(')
+        .map_or(0, |i| i + 1);
+    let word = &text[start..];
+    let is_keyword = matches!(word, "AND" | "OR" | "NOT");
+    (!word.is_empty() && !is_keyword && split_operator(word).is_none() && !word.contains('"'))
+        .then_some(word)
+}
+
+/// Known field names completing the word at the end of `text`.
+#[cfg(feature = "gui")]
+pub fn complete_field<'a>(text: &str, fields: &'a [String]) -> Vec<&'a str> {
+    let Some(prefix) = completion_prefix(text) else {
+        return Vec::new();
+    };
+    let prefix = prefix.to_lowercase();
+    fields
+        .iter()
+        .filter(|f| f.to_lowercase().starts_with(&prefix) && f.len() > prefix.len())
+        .map(String::as_str)
+        .collect()
+}
+
+/// `text` with its last word replaced by `field:`.
+#[cfg(feature = "gui")]
+pub fn apply_completion(text: &str, field: &str) -> String {
+    let prefix_len = completion_prefix(text).map_or(0, str::len);
+    format!("{}{field}:", &text[..text.len() - prefix_len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::parsers::ParserRegistry;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: 1,
+            message: message.to_string(),
+            log_stream_name: Some("stream-a".to_string()),
+            log_group_name: Some("/aws/lambda/api".to_string()),
+            event_id: None,
+            ingestion_time_millis: None,
+        }
+    }
+
+    fn matches(query: &str, message: &str) -> bool {
+        let entry = entry(message);
+        let parsed = ParserRegistry::default().parse(message);
+        let level = parsed
+            .as_ref()
+            .and_then(ParsedMessage::level)
+            .unwrap_or_else(|| crate::app::level::detect_level(message));
+        let query = Query::parse(query)
+            .expect("valid query")
+            .expect("non-empty");
+        query.matches(&QueryTarget {
+            entry: &entry,
+            search_text: &strip_ansi(message).to_lowercase(),
+            level,
+            parsed: parsed.as_ref(),
+        })
+    }
+
+    #[test]
+    fn evaluates_boolean_field_queries() {
+        let q = "level:error AND status>=500 AND NOT path:/health";
+        assert!(matches(
+            q,
+            r#"{"level":"ERROR","status":503,"path":"/orders"}"#
+        ));
+        assert!(!matches(
+            q,
+            r#"{"level":"ERROR","status":503,"path":"/health"}"#
+        ));
+        assert!(!matches(
+            q,
+            r#"{"level":"ERROR","status":404,"path":"/orders"}"#
+        ));
+        assert!(!matches(
+            q,
+            r#"{"level":"info","status":503,"path":"/orders"}"#
+        ));
+
+        assert!(matches("level:warning", "level=warn msg=slow dur=1s"));
+        assert!(matches(
+            "(status:404 OR status:410) timeout",
+            r#"{"status":410,"msg":"Timeout"}"#
+        ));
+        assert!(!matches("missing:1", r#"{"status":410}"#));
+        assert!(matches("NOT missing:1", r#"{"status":410}"#));
+        assert!(matches("status!=200", r#"{"status":410}"#));
+    }
+
+    #[test]
+    fn compares_numbers_durations_ranges_and_patterns() {
+        let line = r#"level=info msg="GET /api/users" dur=120ms size=512 user.id=7"#;
+        assert!(matches("dur>100ms", line));
+        assert!(matches("dur<0.2s", line));
+        assert!(!matches("dur>=1s", line));
+        assert!(matches("size:500..600", line));
+        assert!(matches(r#"msg~"^GET /api/\w+$""#, line));
+        assert!(matches("msg:get*users", line));
+        assert!(matches(r#"msg:"GET /api/users""#, line));
+        assert!(matches("@stream:stream-a @format:logfmt", line));
+        assert!(matches(r#"ctx.ids:3"#, r#"{"ctx":{"ids":[1,2,3]}}"#));
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let err = |q: &str| Query::parse(q).expect_err("invalid query");
+        assert_eq!(err("(a OR b"), QueryError::UnclosedParen(1));
+        assert_eq!(err("a AND"), QueryError::UnexpectedEnd);
+        assert_eq!(
+            err("a ) b"),
+            QueryError::Unexpected {
+                token: ")".to_string(),
+                column: 3
+            }
+        );
+        assert_eq!(err(r#"msg:"open"#), QueryError::UnclosedQuote(5));
+        assert_eq!(
+            err("status>=lots"),
+            QueryError::InvalidNumber("lots".to_string())
+        );
+        assert!(matches!(
+            err(r#"msg~"([""#),
+            QueryError::InvalidRegex { .. }
+        ));
+        assert!(matches!(err("status: x"), QueryError::MissingValue { .. }));
+        assert!(Query::parse("   ").expect("blank is fine").is_none());
+    }
+
+    #[test]
+    fn parse_scalar_understands_duration_units() {
+        assert_eq!(parse_scalar("12"), Some(12.0));
+        assert_eq!(parse_scalar("1.5s"), Some(1_500.0));
+        assert_eq!(parse_scalar("2m"), Some(120_000.0));
+        assert_eq!(parse_scalar("250us"), Some(0.25));
+        assert_eq!(parse_scalar("12 apples"), None);
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn completes_field_names_at_the_end() {
+        let fields = vec![
+            "level".to_string(),
+            "status".to_string(),
+            "statusCode".to_string(),
+        ];
+        assert_eq!(
+            complete_field("level:error AND sta", &fields),
+            vec!["status", "statusCode"]
+        );
+        assert_eq!(
+            complete_field("(stat", &fields),
+            vec!["status", "statusCode"]
+        );
+        assert!(complete_field("status:5", &fields).is_empty());
+        assert!(complete_field("level ", &fields).is_empty());
+        assert_eq!(
+            apply_completion("level:error AND sta", "status"),
+            "level:error AND status:"
+        );
+    }
+}
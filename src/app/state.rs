@@ -1,15 +1,44 @@
+#[cfg(feature = "gui")]
+use std::collections::{BTreeSet, HashMap};
+
 use chrono::{Local, LocalResult, TimeZone, Utc};
+#[cfg(feature = "gui")]
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
+#[cfg(feature = "gui")]
+use crate::app::{
+    ansi::strip_ansi,
+    export::ExportOptions,
+    json_tree::{flatten_json, lookup_path},
+    level::LogLevel,
+    multiline::{ContinuationRules, Folding, fold_entries},
+    parsers::{MessageFormat, ParsedMessage, ParserRegistry},
+    patterns::{Pattern, PatternFilter, cluster, mask_message},
+    query::{PSEUDO_FIELDS, Query, QueryError, QueryTarget},
+};
 use crate::aws::LogEntry;
+#[cfg(feature = "gui")]
+use crate::{local_logs::LocalFileOptions, worker::DEFAULT_CONCURRENCY};
+
+/// Auto-picked table columns for formats without default columns.
+#[cfg(feature = "gui")]
+const MAX_AUTO_COLUMNS: usize = 6;
 
+/// Field names offered for query completion.
+#[cfg(feature = "gui")]
+const MAX_KNOWN_FIELDS: usize = 500;
+
+#[cfg(feature = "gui")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ActiveView {
     Logs,
-    // Settings,
+    Patterns,
+    Settings,
     // Favorites,
 }
 
+#[cfg(feature = "gui")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Theme {
     Light,
@@ -17,21 +46,182 @@ pub enum Theme {
     RetroGreen,
 }
 
+/// App-wide preferences, persisted across restarts.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    /// Closing the window hides it to the tray instead of quitting.
+    #[serde(default)]
+    pub close_to_tray: bool,
+    /// Requests the worker runs at once.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+}
+
+#[cfg(feature = "gui")]
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            close_to_tray: false,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+fn default_concurrency() -> usize {
+    DEFAULT_CONCURRENCY
+}
+
+/// A `path = value` condition on the fields of a message, added from the
+/// field tree.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldFilter {
+    pub path: String,
+    pub value: JsonValue,
+}
+
+#[cfg(feature = "gui")]
+impl FieldFilter {
+    pub fn matches(&self, fields: &JsonValue) -> bool {
+        lookup_path(fields, &self.path).is_some_and(|v| *v == self.value)
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} = {}", self.path, self.value)
+    }
+}
+
+/// Two tabs shown side by side: the active tab on the left and
+/// `other_tab` on the right.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitView {
+    pub other_tab: u64,
+    /// Selecting an entry in one pane marks the closest entry in the other.
+    pub sync_time: bool,
+    /// Whether the right pane has keyboard focus.
+    pub focus_right: bool,
+}
+
+/// Which entries an export covers.
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportScope {
+    All,
+    Filtered,
+    Selection,
+}
+
+/// State of the export window.
+#[cfg(feature = "gui")]
+pub struct ExportDialogState {
+    pub open: bool,
+    pub scope: ExportScope,
+    pub options: ExportOptions,
+    /// Comma-separated JSON paths exported as extra CSV columns.
+    pub extra_fields: String,
+    /// ID of the tab whose entries are exported.
+    pub source_tab: u64,
+}
+
+#[cfg(feature = "gui")]
+impl Default for ExportDialogState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            scope: ExportScope::Filtered,
+            options: ExportOptions::default(),
+            extra_fields: String::new(),
+            source_tab: 0,
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
 #[derive(Default)]
 pub struct LogsViewState {
     pub profile: String,
     pub region: String,
     pub log_group: String,
     pub filter_text: String,
+    pub field_filters: Vec<FieldFilter>,
     pub available_groups: Vec<String>,
     pub selected_group_index: Option<usize>,
     pub tail_mode: bool,
     pub show_local_time: bool,
     pub entries: Vec<LogEntry>,
+    /// Index into `entries` of the entry shown in the detail panel.
+    pub selected_entry: Option<usize>,
+    /// Indices into `entries` of all selected entries (multi-select).
+    pub selection: BTreeSet<usize>,
+    /// Where a shift-click range selection starts.
+    pub selection_anchor: Option<usize>,
     pub tail_interval_secs: u64,
-    pub last_tail_instant: Option<std::time::Instant>,
+    /// Local time window (start inclusive, end exclusive, epoch millis)
+    /// narrowing the list, set from the histogram.
+    pub time_window: Option<(i64, i64)>,
+    /// Absolute range fetched instead of the lookback, when set.
+    pub fetch_range: Option<(i64, i64)>,
+    pub show_histogram: bool,
+    /// How local files opened as a source are parsed.
+    pub local_file_options: LocalFileOptions,
+    /// Entry marked as the time-closest match to a selection in the other
+    /// split pane.
+    pub sync_marker: Option<usize>,
+    /// Scroll the marked entry into view on the next frame.
+    pub scroll_to_marker: bool,
+    /// Scroll this entry into view on the next frame.
+    pub scroll_to_entry: Option<usize>,
+    /// Fold multi-line events (stack traces) into one logical entry.
+    pub fold_multiline: bool,
+    pub multiline_rules: ContinuationRules,
+    /// Current grouping of `entries`; rebuilt by `refold`.
+    pub folding: Folding,
+    /// Heads of the folded groups shown expanded.
+    pub expanded_groups: BTreeSet<usize>,
+    /// Show messages without ANSI colors instead of rendering them.
+    pub strip_ansi: bool,
+    /// Formats recognized when turning messages into fields.
+    pub parsers: ParserRegistry,
+    /// Fields of each entry, parallel to `entries`; rebuilt by `reparse`.
+    pub parsed: Vec<Option<ParsedMessage>>,
+    /// Level of each entry, parallel to `entries`; rebuilt by `reparse` so
+    /// the histogram and list do not detect it every frame.
+    pub levels: Vec<LogLevel>,
+    /// Lower-cased message of each entry without ANSI escapes, for the text
+    /// filter; rebuilt by `reparse`.
+    pub search_text: Vec<String>,
+    /// Whether any entry is a Lambda platform line (START, END, REPORT…);
+    /// rebuilt by `reparse`.
+    pub has_lambda_lines: bool,
+    /// Bumped whenever the entries or what was parsed from them change, so
+    /// views built from them know to rebuild.
+    pub revision: u64,
+    /// Sorted timestamps of the error entries, so the tray can count recent
+    /// errors every frame; rebuilt by `reparse`.
+    error_times: Vec<i64>,
+    /// Show parsed fields as columns.
+    pub table_view: bool,
+    /// Field paths shown as columns; picked from the entries when empty.
+    pub table_columns: Vec<String>,
+    /// Local query as typed, e.g. `level:error AND status>=500`.
+    pub query_text: String,
+    /// `query_text` parsed; `None` when blank or invalid.
+    pub query: Option<Query>,
+    pub query_error: Option<QueryError>,
+    /// Field names seen in the parsed entries, for query completion.
+    pub known_fields: Vec<String>,
+    /// Masked tokens of each entry, parallel to `entries`, for pattern
+    /// matching.
+    pub masked: Vec<Vec<String>>,
+    pub pattern_filter: PatternFilter,
+    /// Patterns of `entries`, built on demand by `patterns`.
+    pub pattern_cache: Option<Vec<Pattern>>,
 }
 
+#[cfg(feature = "gui")]
 impl LogsViewState {
     pub fn new_default() -> Self {
         Self {
@@ -39,15 +229,516 @@ impl LogsViewState {
             region: "eu-west-1".to_string(),
             log_group: String::new(),
             filter_text: String::new(),
+            field_filters: Vec::new(),
             tail_mode: false,
             show_local_time: false,
             entries: Vec::new(),
+            selected_entry: None,
+            selection: BTreeSet::new(),
+            selection_anchor: None,
             available_groups: Vec::new(),
             selected_group_index: None,
             tail_interval_secs: 5,
-            last_tail_instant: None,
+            time_window: None,
+            fetch_range: None,
+            show_histogram: true,
+            local_file_options: LocalFileOptions::default(),
+            sync_marker: None,
+            scroll_to_marker: false,
+            scroll_to_entry: None,
+            fold_multiline: false,
+            multiline_rules: ContinuationRules::default(),
+            folding: Folding::default(),
+            expanded_groups: BTreeSet::new(),
+            strip_ansi: false,
+            parsers: ParserRegistry::default(),
+            parsed: Vec::new(),
+            levels: Vec::new(),
+            search_text: Vec::new(),
+            has_lambda_lines: false,
+            revision: 0,
+            error_times: Vec::new(),
+            table_view: false,
+            table_columns: Vec::new(),
+            query_text: String::new(),
+            query: None,
+            query_error: None,
+            known_fields: PSEUDO_FIELDS.map(String::from).to_vec(),
+            masked: Vec::new(),
+            pattern_filter: PatternFilter::default(),
+            pattern_cache: None,
+        }
+    }
+
+    /// Replace the fetched entries, keeping the selection on the same events
+    /// if they are still present (e.g. across tail refreshes).
+    pub fn set_entries(&mut self, entries: Vec<LogEntry>) {
+        let key_at = |entries: &[LogEntry], idx: usize| entries.get(idx).map(entry_key);
+        let primary = self.selected_entry.and_then(|i| key_at(&self.entries, i));
+        let anchor = self.selection_anchor.and_then(|i| key_at(&self.entries, i));
+        let marker = self.sync_marker.and_then(|i| key_at(&self.entries, i));
+        let expanded: Vec<_> = self
+            .expanded_groups
+            .iter()
+            .filter_map(|&i| key_at(&self.entries, i))
+            .collect();
+        let selection: Vec<_> = self
+            .selection
+            .iter()
+            .filter_map(|&i| key_at(&self.entries, i))
+            .collect();
+
+        self.entries = entries;
+        self.reparse();
+
+        let positions: HashMap<_, _> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, e)| (entry_key(e), idx))
+            .collect();
+        self.selected_entry = primary.and_then(|k| positions.get(&k).copied());
+        self.selection_anchor = anchor.and_then(|k| positions.get(&k).copied());
+        self.sync_marker = marker.and_then(|k| positions.get(&k).copied());
+        self.expanded_groups = expanded
+            .into_iter()
+            .filter_map(|k| positions.get(&k).copied())
+            .collect();
+        self.refold();
+        self.selection = selection
+            .into_iter()
+            .filter_map(|k| positions.get(&k).copied())
+            .collect();
+    }
+
+    /// Whether the entry at an index passes the local filters and time
+    /// window. The filter text is lowercased once, for a pass over many
+    /// entries.
+    pub fn entry_matcher(&self) -> impl Fn(usize) -> bool + '_ {
+        let matches_filters = self.filter_matcher();
+        move |idx| {
+            let in_window = match self.time_window {
+                Some((start, end)) => (start..end).contains(&self.entries[idx].timestamp_millis),
+                None => true,
+            };
+            in_window && matches_filters(idx)
+        }
+    }
+
+    /// Whether the entry at an index passes the local text and field
+    /// filters, regardless of the time window.
+    pub fn filter_matcher(&self) -> impl Fn(usize) -> bool + '_ {
+        let needle = self.filter_text.to_lowercase();
+        move |idx| self.matches_filters(idx, &needle)
+    }
+
+    /// Whether the entry at `idx` passes the local text and field filters.
+    /// ANSI escape codes are ignored when matching `needle`, the lowercased
+    /// filter text; field filters match on the parsed fields.
+    fn matches_filters(&self, idx: usize, needle: &str) -> bool {
+        if !needle.is_empty()
+            && !self
+                .search_text
+                .get(idx)
+                .is_some_and(|text| text.contains(needle))
+        {
+            return false;
+        }
+
+        if self.pattern_filter.is_active()
+            && let Some(tokens) = self.masked.get(idx)
+            && !self.pattern_filter.matches(tokens)
+        {
+            return false;
+        }
+
+        let parsed = self.parsed(idx);
+        if !self.field_filters.is_empty() {
+            let Some(parsed) = parsed else {
+                return false;
+            };
+            if !self.field_filters.iter().all(|f| f.matches(&parsed.fields)) {
+                return false;
+            }
+        }
+
+        self.query.as_ref().is_none_or(|query| {
+            query.matches(&QueryTarget {
+                entry: &self.entries[idx],
+                search_text: self.search_text.get(idx).map_or("", String::as_str),
+                level: self.level_of(idx),
+                parsed,
+            })
+        })
+    }
+
+    /// Set and parse the local query. An invalid query keeps the error for
+    /// display and filters nothing.
+    pub fn set_query(&mut self, text: &str) {
+        self.query_text = text.to_string();
+        match Query::parse(text) {
+            Ok(query) => {
+                self.query = query;
+                self.query_error = None;
+            }
+            Err(err) => {
+                self.query = None;
+                self.query_error = Some(err);
+            }
+        }
+    }
+
+    pub fn add_field_filter(&mut self, filter: FieldFilter) {
+        if !self.field_filters.contains(&filter) {
+            self.field_filters.push(filter);
         }
     }
+
+    /// Indices into `entries` of the entries currently shown in the list.
+    ///
+    /// With multi-line folding, a group is shown when any of its events
+    /// matches; collapsed groups show only their head.
+    pub fn visible_indices(&self) -> Vec<usize> {
+        let matches = self.entry_matcher();
+        if !self.is_folded() {
+            return (0..self.entries.len())
+                .filter(|&idx| matches(idx))
+                .collect();
+        }
+
+        let mut visible = Vec::new();
+        for head in (0..self.entries.len()).filter(|&idx| self.folding.is_head(idx)) {
+            let members = self.folding.members_of(head);
+            let shown = std::iter::once(head)
+                .chain(members.iter().copied())
+                .any(&matches);
+            if shown {
+                visible.push(head);
+                if self.expanded_groups.contains(&head) {
+                    visible.extend_from_slice(members);
+                }
+            }
+        }
+        visible
+    }
+
+    /// Re-run the parsers after entries or enabled formats changed.
+    pub fn reparse(&mut self) {
+        self.parsed.clear();
+        self.levels.clear();
+        self.search_text.clear();
+        self.masked.clear();
+        self.error_times.clear();
+        self.has_lambda_lines = false;
+        self.known_fields = PSEUDO_FIELDS.map(String::from).to_vec();
+        self.parse_from(0);
+    }
+
+    /// Add entries after the current ones, parsing only those (e.g. a page
+    /// of the fetch in flight). Indices of the current entries are kept.
+    pub fn append_entries(&mut self, entries: Vec<LogEntry>) {
+        let start = self.entries.len();
+        self.entries.extend(entries);
+        self.parse_from(start);
+        self.refold();
+    }
+
+    /// Parse the entries from `start` on, adding to what is known of the
+    /// ones before.
+    fn parse_from(&mut self, start: usize) {
+        self.revision += 1;
+        let new = &self.entries[start..];
+        let (parsed, levels): (Vec<_>, Vec<_>) = new
+            .iter()
+            .map(|e| self.parsers.parse_with_level(&e.message))
+            .unzip();
+        self.search_text
+            .extend(new.iter().map(|e| strip_ansi(&e.message).to_lowercase()));
+        self.masked
+            .extend(new.iter().map(|e| mask_message(&e.message)));
+        self.has_lambda_lines |= parsed
+            .iter()
+            .flatten()
+            .any(|p| p.format == MessageFormat::LambdaPlatform);
+        self.error_times.extend(
+            new.iter()
+                .zip(&levels)
+                .filter(|(_, level)| **level == LogLevel::Error)
+                .map(|(e, _)| e.timestamp_millis),
+        );
+        self.error_times.sort_unstable();
+        self.pattern_cache = None;
+
+        let mut fields: BTreeSet<String> = self.known_fields[PSEUDO_FIELDS.len()..]
+            .iter()
+            .cloned()
+            .collect();
+        for parsed in parsed.iter().flatten() {
+            if fields.len() >= MAX_KNOWN_FIELDS {
+                break;
+            }
+            for (path, _) in flatten_json(&parsed.fields) {
+                // Only plain dotted paths can be typed in a query.
+                if let Some(name) = path.strip_prefix("$.")
+                    && !name.contains('[')
+                {
+                    fields.insert(name.to_string());
+                }
+            }
+        }
+        self.known_fields = PSEUDO_FIELDS
+            .iter()
+            .map(|f| f.to_string())
+            .chain(fields)
+            .collect();
+
+        self.parsed.extend(parsed);
+        self.levels.extend(levels);
+    }
+
+    /// Message patterns of all entries, most frequent first.
+    pub fn patterns(&mut self) -> &[Pattern] {
+        self.pattern_cache
+            .get_or_insert_with(|| cluster(&self.entries, &self.masked))
+    }
+
+    /// Parsed fields of the entry at `idx`, if its format was recognized.
+    pub fn parsed(&self, idx: usize) -> Option<&ParsedMessage> {
+        self.parsed.get(idx)?.as_ref()
+    }
+
+    /// Level of the entry at `idx`: from its fields when they imply one,
+    /// otherwise from markers in the message.
+    pub fn level_of(&self, idx: usize) -> LogLevel {
+        self.levels.get(idx).copied().unwrap_or(LogLevel::Unknown)
+    }
+
+    /// Error entries logged at or after `since_millis`.
+    pub fn errors_since(&self, since_millis: i64) -> usize {
+        let older = self.error_times.partition_point(|&ts| ts < since_millis);
+        self.error_times.len() - older
+    }
+
+    /// Columns of the table view: the configured ones, or else those of the
+    /// most common format among `visible`.
+    pub fn effective_table_columns(&self, visible: &[usize]) -> Vec<String> {
+        if !self.table_columns.is_empty() {
+            return self.table_columns.clone();
+        }
+
+        let mut counts: HashMap<MessageFormat, usize> = HashMap::new();
+        for &idx in visible {
+            if let Some(parsed) = self.parsed(idx) {
+                *counts.entry(parsed.format).or_default() += 1;
+            }
+        }
+        let Some(format) = counts
+            .into_iter()
+            .max_by_key(|&(format, count)| (count, std::cmp::Reverse(format)))
+            .map(|(format, _)| format)
+        else {
+            return Vec::new();
+        };
+
+        let defaults = format.default_columns();
+        if !defaults.is_empty() {
+            return defaults.iter().map(|c| c.to_string()).collect();
+        }
+        // Otherwise the scalar top-level fields of the first such entry.
+        visible
+            .iter()
+            .filter_map(|&idx| self.parsed(idx))
+            .find(|p| p.format == format)
+            .and_then(|p| p.fields.as_object())
+            .map(|map| {
+                map.iter()
+                    .filter(|(_, v)| !v.is_object() && !v.is_array())
+                    .map(|(k, _)| k.clone())
+                    .take(MAX_AUTO_COLUMNS)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether `folding` is active and matches the current entries.
+    pub fn is_folded(&self) -> bool {
+        self.fold_multiline && self.folding.head_of.len() == self.entries.len()
+    }
+
+    /// Rebuild the multi-line grouping after entries or rules changed.
+    pub fn refold(&mut self) {
+        self.folding = if self.fold_multiline {
+            fold_entries(&self.entries, &self.multiline_rules)
+        } else {
+            Folding::default()
+        };
+    }
+
+    /// Continuation events folded under `idx` (empty unless it is a head).
+    pub fn folded_members(&self, idx: usize) -> &[usize] {
+        if self.is_folded() {
+            self.folding.members_of(idx)
+        } else {
+            &[]
+        }
+    }
+
+    pub fn toggle_expanded(&mut self, head: usize) {
+        if !self.expanded_groups.remove(&head) {
+            self.expanded_groups.insert(head);
+        }
+    }
+
+    /// The message of the logical entry at `idx`: a folded head followed by
+    /// its continuation lines.
+    pub fn logical_message(&self, idx: usize) -> String {
+        let Some(entry) = self.entries.get(idx) else {
+            return String::new();
+        };
+        let mut message = entry.message.trim_end().to_string();
+        for &member in self.folded_members(idx) {
+            message.push('\n');
+            message.push_str(self.entries[member].message.trim_end());
+        }
+        message
+    }
+
+    pub fn selected(&self) -> Option<&LogEntry> {
+        self.selected_entry.and_then(|idx| self.entries.get(idx))
+    }
+
+    /// The entry at `idx` as shown in the list: a collapsed folded group is one
+    /// entry carrying the whole multi-line message.
+    fn logical_entry(&self, idx: usize) -> Option<LogEntry> {
+        let entry = self.entries.get(idx)?;
+        if self.expanded_groups.contains(&idx) || self.folded_members(idx).is_empty() {
+            Some(entry.clone())
+        } else {
+            Some(LogEntry {
+                message: self.logical_message(idx),
+                ..entry.clone()
+            })
+        }
+    }
+
+    /// Selected entries in list order, as logical entries.
+    pub fn selected_logical_entries(&self) -> Vec<LogEntry> {
+        self.selection
+            .iter()
+            .filter_map(|&idx| self.logical_entry(idx))
+            .collect()
+    }
+
+    /// Entries passing the filters in list order, as logical entries.
+    pub fn visible_logical_entries(&self) -> Vec<LogEntry> {
+        self.visible_indices()
+            .into_iter()
+            .filter_map(|idx| self.logical_entry(idx))
+            .collect()
+    }
+
+    /// Plain click: select just this entry.
+    pub fn select_single(&mut self, idx: usize) {
+        self.selection.clear();
+        self.selection.insert(idx);
+        self.selection_anchor = Some(idx);
+        self.selected_entry = Some(idx);
+    }
+
+    /// Ctrl/Cmd-click: add or remove this entry from the selection.
+    pub fn toggle_selected(&mut self, idx: usize) {
+        if self.selection.remove(&idx) {
+            if self.selected_entry == Some(idx) {
+                self.selected_entry = self.selection.iter().next_back().copied();
+            }
+        } else {
+            self.selection.insert(idx);
+            self.selected_entry = Some(idx);
+        }
+        self.selection_anchor = Some(idx);
+    }
+
+    /// Shift-click: select the visible entries between the anchor and `idx`.
+    pub fn select_range_to(&mut self, idx: usize) {
+        let visible = self.visible_indices();
+        let anchor = self.selection_anchor.unwrap_or(idx);
+        let (Some(a), Some(b)) = (
+            visible.iter().position(|&i| i == anchor),
+            visible.iter().position(|&i| i == idx),
+        ) else {
+            self.select_single(idx);
+            return;
+        };
+
+        let (from, to) = if a <= b { (a, b) } else { (b, a) };
+        self.selection = visible[from..=to].iter().copied().collect();
+        self.selected_entry = Some(idx);
+    }
+
+    /// Select `indices` (e.g. one invocation's log block) and scroll to the
+    /// first of them.
+    pub fn select_block(&mut self, indices: &[usize]) {
+        let Some(&first) = indices.first() else {
+            return;
+        };
+        self.selection = indices.iter().copied().collect();
+        self.selection_anchor = Some(first);
+        self.selected_entry = Some(first);
+        self.scroll_to_entry = Some(first);
+    }
+
+    pub fn select_all_visible(&mut self) {
+        self.selection = self.visible_indices().into_iter().collect();
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.selection_anchor = None;
+        self.selected_entry = None;
+    }
+
+    /// Mark the visible entry closest in time to `ts_millis` and scroll to it.
+    pub fn mark_nearest(&mut self, ts_millis: i64) -> Option<usize> {
+        let nearest = self
+            .visible_indices()
+            .into_iter()
+            .min_by_key(|&idx| (self.entries[idx].timestamp_millis - ts_millis).unsigned_abs());
+        self.sync_marker = nearest;
+        self.scroll_to_marker = nearest.is_some();
+        nearest
+    }
+
+    /// Move the selection by `delta` rows within the visible entries.
+    pub fn move_selection(&mut self, delta: isize) {
+        let visible = self.visible_indices();
+        if visible.is_empty() {
+            return;
+        }
+
+        let current = self
+            .selected_entry
+            .and_then(|sel| visible.iter().position(|&idx| idx == sel));
+        let next = match current {
+            Some(pos) => pos.saturating_add_signed(delta).min(visible.len() - 1),
+            None if delta < 0 => visible.len() - 1,
+            None => 0,
+        };
+        self.select_single(visible[next]);
+    }
+}
+
+/// Identity of an entry across refetches: the CloudWatch event ID when
+/// present, otherwise timestamp + stream + message.
+pub(crate) fn entry_key(entry: &LogEntry) -> (Option<String>, i64, Option<String>, String) {
+    match &entry.event_id {
+        Some(id) => (Some(id.clone()), 0, None, String::new()),
+        None => (
+            None,
+            entry.timestamp_millis,
+            entry.log_stream_name.clone(),
+            entry.message.clone(),
+        ),
+    }
 }
 
 pub fn format_timestamp_millis(ts_millis: i64, use_local: bool) -> String {
@@ -71,7 +762,9 @@ pub fn format_timestamp_millis(ts_millis: i64, use_local: bool) -> String {
     }
 }
 
-pub fn try_pretty_json(message: &str) -> Option<String> {
+/// Parse a message that is a JSON object or array (other JSON values are
+/// treated as plain text).
+pub fn try_parse_json(message: &str) -> Option<JsonValue> {
     let trimmed = message.trim();
     if trimmed.is_empty() {
         return None;
@@ -83,13 +776,15 @@ pub fn try_pretty_json(message: &str) -> Option<String> {
         return None;
     }
 
-    match serde_json::from_str::<JsonValue>(trimmed) {
-        Ok(v) => serde_json::to_string_pretty(&v).ok(),
-        Err(_) => None,
-    }
+    serde_json::from_str::<JsonValue>(trimmed).ok()
+}
+
+#[cfg(feature = "gui")]
+pub fn try_pretty_json(message: &str) -> Option<String> {
+    try_parse_json(message).and_then(|v| serde_json::to_string_pretty(&v).ok())
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "gui"))]
 mod tests {
     use super::*;
 
@@ -101,13 +796,320 @@ mod tests {
         assert_eq!(s.region, "eu-west-1");
         assert_eq!(s.log_group, "");
         assert_eq!(s.filter_text, "");
+        assert!(s.field_filters.is_empty());
         assert!(!s.tail_mode);
         assert!(!s.show_local_time);
         assert!(s.entries.is_empty());
+        assert_eq!(s.selected_entry, None);
+        assert!(s.selection.is_empty());
         assert!(s.available_groups.is_empty());
         assert_eq!(s.selected_group_index, None);
         assert_eq!(s.tail_interval_secs, 5);
-        assert!(s.last_tail_instant.is_none());
+        assert_eq!(s.time_window, None);
+        assert_eq!(s.fetch_range, None);
+        assert!(s.show_histogram);
+    }
+
+    #[test]
+    fn time_window_narrows_visible_entries() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![
+            entry("a", 1_000, "one"),
+            entry("b", 2_000, "two"),
+            entry("c", 3_000, "three"),
+        ]);
+
+        s.time_window = Some((2_000, 3_000));
+        assert_eq!(s.visible_indices(), vec![1]);
+        assert!(s.filter_matcher()(0));
+    }
+
+    fn entry(id: &str, ts: i64, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: ts,
+            message: message.to_string(),
+            log_stream_name: None,
+            log_group_name: None,
+            event_id: Some(id.to_string()),
+            ingestion_time_millis: None,
+        }
+    }
+
+    #[test]
+    fn appended_entries_are_parsed_like_a_full_set() {
+        let first = vec![entry("a", 1, r#"{"level":"error","user":"x"}"#)];
+        let page = vec![
+            entry("b", 2, r#"{"level":"info","path":"/"}"#),
+            entry("c", 3, "plain ERROR line"),
+        ];
+        let mut appended = LogsViewState::new_default();
+        appended.set_entries(first.clone());
+        appended.select_single(0);
+        appended.append_entries(page.clone());
+
+        let mut full = LogsViewState::new_default();
+        full.set_entries(first.into_iter().chain(page).collect());
+
+        assert_eq!(appended.levels, full.levels);
+        assert_eq!(appended.search_text, full.search_text);
+        assert_eq!(appended.masked, full.masked);
+        assert_eq!(appended.known_fields, full.known_fields);
+        assert_eq!(appended.errors_since(0), full.errors_since(0));
+        assert_eq!(appended.selected_entry, Some(0));
+    }
+
+    #[test]
+    fn set_entries_keeps_selection_on_same_event() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![entry("a", 1, "one"), entry("b", 2, "two")]);
+        s.select_single(1);
+
+        s.set_entries(vec![
+            entry("z", 0, "zero"),
+            entry("a", 1, "one"),
+            entry("b", 2, "two"),
+        ]);
+        assert_eq!(s.selected_entry, Some(2));
+        assert_eq!(s.selection, BTreeSet::from([2]));
+
+        s.set_entries(vec![entry("c", 3, "three")]);
+        assert_eq!(s.selected_entry, None);
+        assert!(s.selection.is_empty());
+    }
+
+    #[test]
+    fn click_modifiers_build_multi_selection() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![
+            entry("a", 1, "keep 1"),
+            entry("b", 2, "drop"),
+            entry("c", 3, "keep 2"),
+            entry("d", 4, "keep 3"),
+        ]);
+        s.filter_text = "keep".to_string();
+
+        s.select_single(0);
+        s.select_range_to(3);
+        assert_eq!(s.selection, BTreeSet::from([0, 2, 3]));
+        assert_eq!(s.selected_entry, Some(3));
+
+        s.toggle_selected(2);
+        assert_eq!(s.selection, BTreeSet::from([0, 3]));
+        s.toggle_selected(3);
+        assert_eq!(s.selection, BTreeSet::from([0]));
+        assert_eq!(s.selected_entry, Some(0));
+
+        let selected = s.selected_logical_entries();
+        let messages: Vec<_> = selected.iter().map(|e| &e.message).collect();
+        assert_eq!(messages, vec!["keep 1"]);
+
+        s.clear_selection();
+        assert!(s.selection.is_empty());
+        assert_eq!(s.selected_entry, None);
+    }
+
+    #[test]
+    fn move_selection_walks_visible_entries_only() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![
+            entry("a", 1, "keep 1"),
+            entry("b", 2, "drop"),
+            entry("c", 3, "keep 2"),
+        ]);
+        s.filter_text = "keep".to_string();
+
+        assert_eq!(s.visible_indices(), vec![0, 2]);
+
+        s.move_selection(1);
+        assert_eq!(s.selected_entry, Some(0));
+        s.move_selection(1);
+        assert_eq!(s.selected_entry, Some(2));
+        s.move_selection(1);
+        assert_eq!(s.selected_entry, Some(2));
+        s.move_selection(-5);
+        assert_eq!(s.selected_entry, Some(0));
+    }
+
+    #[test]
+    fn mark_nearest_picks_closest_visible_entry() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![
+            entry("a", 1_000, "keep"),
+            entry("b", 1_900, "drop"),
+            entry("c", 3_000, "keep"),
+        ]);
+        s.filter_text = "keep".to_string();
+
+        assert_eq!(s.mark_nearest(1_800), Some(0));
+        assert_eq!(s.mark_nearest(2_600), Some(2));
+        assert!(s.scroll_to_marker);
+
+        // The marker follows its event across refreshes.
+        s.set_entries(vec![entry("z", 500, "keep"), entry("c", 3_000, "keep")]);
+        assert_eq!(s.sync_marker, Some(1));
+
+        s.filter_text = "nothing".to_string();
+        assert_eq!(s.mark_nearest(2_600), None);
+        assert!(!s.scroll_to_marker);
+    }
+
+    #[test]
+    fn folded_groups_match_on_any_line_and_expand() {
+        let mut s = LogsViewState::new_default();
+        s.fold_multiline = true;
+        s.set_entries(vec![
+            entry("a", 1_000, "ERROR boom"),
+            entry("b", 1_001, "\tat Foo.bar(Foo.java:1)"),
+            entry("c", 1_002, "Caused by: NullPointerException"),
+            entry("d", 5_000, "INFO done"),
+        ]);
+
+        assert_eq!(s.visible_indices(), vec![0, 3]);
+        assert_eq!(s.folded_members(0), &[1, 2]);
+        assert_eq!(
+            s.logical_message(0),
+            "ERROR boom\n\tat Foo.bar(Foo.java:1)\nCaused by: NullPointerException"
+        );
+
+        s.filter_text = "nullpointer".to_string();
+        assert_eq!(s.visible_indices(), vec![0]);
+
+        s.toggle_expanded(0);
+        assert_eq!(s.visible_indices(), vec![0, 1, 2]);
+
+        // Expanded state survives a refresh.
+        s.set_entries(s.entries.clone());
+        assert!(s.expanded_groups.contains(&0));
+
+        s.fold_multiline = false;
+        s.refold();
+        assert_eq!(s.visible_indices(), vec![2]);
+        assert_eq!(s.logical_message(0), "ERROR boom");
+    }
+
+    #[test]
+    fn visible_logical_entries_keep_folded_lines() {
+        let mut s = LogsViewState::new_default();
+        s.fold_multiline = true;
+        s.set_entries(vec![
+            entry("a", 1_000, "ERROR boom"),
+            entry("b", 1_001, "\tat Foo.bar(Foo.java:1)"),
+            entry("c", 5_000, "INFO done"),
+        ]);
+        s.filter_text = "foo.bar".to_string();
+
+        let messages: Vec<_> = s
+            .visible_logical_entries()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages, vec!["ERROR boom\n\tat Foo.bar(Foo.java:1)"]);
+
+        // An expanded group exports its lines as they are shown.
+        s.toggle_expanded(0);
+        let messages: Vec<_> = s
+            .visible_logical_entries()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages, vec!["ERROR boom", "\tat Foo.bar(Foo.java:1)"]);
+    }
+
+    #[test]
+    fn text_filter_ignores_ansi_escapes() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![entry("a", 1, "\u{1b}[31mERR\u{1b}[0mOR: boom")]);
+
+        s.filter_text = "error".to_string();
+        assert_eq!(s.visible_indices(), vec![0]);
+        s.filter_text = "[31m".to_string();
+        assert!(s.visible_indices().is_empty());
+    }
+
+    #[test]
+    fn query_filters_entries_and_reports_errors() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![
+            entry("a", 1, r#"{"level":"error","status":503,"path":"/orders"}"#),
+            entry("b", 2, r#"{"level":"error","status":503,"path":"/health"}"#),
+            entry("c", 3, "level=info status=200 path=/orders"),
+        ]);
+        assert!(s.known_fields.contains(&"status".to_string()));
+        assert!(s.known_fields.contains(&"level".to_string()));
+
+        s.set_query("level:error AND status>=500 AND NOT path:/health");
+        assert_eq!(s.visible_indices(), vec![0]);
+
+        s.set_query("status>=");
+        assert!(s.query_error.is_some());
+        assert_eq!(s.visible_indices(), vec![0, 1, 2]);
+
+        s.set_query("");
+        assert!(s.query_error.is_none());
+        assert!(s.query.is_none());
+    }
+
+    #[test]
+    fn counts_recent_errors() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![
+            entry("a", 1_000, "ERROR old failure"),
+            entry("b", 5_000, r#"{"level":"error","msg":"boom"}"#),
+            entry("c", 6_000, "INFO fine"),
+            entry("d", 7_000, "ERROR disk full"),
+        ]);
+        assert_eq!(s.errors_since(5_000), 2);
+        assert_eq!(s.errors_since(0), 3);
+    }
+
+    #[test]
+    fn notices_lambda_platform_lines() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![entry("a", 1_000, "INFO hello")]);
+        assert!(!s.has_lambda_lines);
+        s.set_entries(vec![entry(
+            "a",
+            1_000,
+            "START RequestId: 8f5f8f7a-1c2b-4d3e-9f00-0123456789ab Version: $LATEST",
+        )]);
+        assert!(s.has_lambda_lines);
+    }
+
+    #[test]
+    fn pattern_filter_hides_noise_across_refreshes() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![
+            entry("a", 1, "health check 200"),
+            entry("b", 2, "order 17 placed"),
+            entry("c", 3, "health check 503"),
+        ]);
+        let noise = s.patterns()[0].template.clone();
+        assert_eq!(noise.text(), "health check <num>");
+
+        s.pattern_filter.toggle_hidden(&noise);
+        assert_eq!(s.visible_indices(), vec![1]);
+
+        s.set_entries(vec![
+            entry("d", 4, "health check 200"),
+            entry("e", 5, "order 18 placed"),
+        ]);
+        assert_eq!(s.visible_indices(), vec![1]);
+
+        s.pattern_filter.hidden.clear();
+        s.pattern_filter.only = Some(noise);
+        assert_eq!(s.visible_indices(), vec![0]);
+    }
+
+    #[test]
+    fn export_dialog_defaults_to_filtered_json_lines() {
+        let d = ExportDialogState::default();
+        assert!(!d.open);
+        assert_eq!(d.scope, ExportScope::Filtered);
+        assert_eq!(
+            d.options.format,
+            crate::app::export::ExportFormat::JsonLines
+        );
+        assert!(d.extra_fields.is_empty());
     }
 
     #[test]
@@ -134,4 +1136,50 @@ mod tests {
         assert_eq!(try_pretty_json("not json"), None);
         assert_eq!(try_pretty_json("{not json}"), None);
     }
+
+    #[test]
+    fn field_filter_matches_on_json_path_value() {
+        let filter = FieldFilter {
+            path: "$.ctx.status".to_string(),
+            value: serde_json::json!(500),
+        };
+
+        assert!(filter.matches(&serde_json::json!({"ctx": {"status": 500}})));
+        assert!(!filter.matches(&serde_json::json!({"ctx": {"status": "500"}})));
+        assert!(!filter.matches(&serde_json::json!({"ctx": {}})));
+        assert_eq!(filter.label(), "$.ctx.status = 500");
+    }
+
+    #[test]
+    fn parsed_fields_drive_filters_levels_and_columns() {
+        let mut s = LogsViewState::new_default();
+        s.set_entries(vec![
+            entry(
+                "a",
+                1,
+                r#"203.0.113.7 - - [18/Oct/2026:10:00:00 +0000] "GET /a HTTP/1.1" 503 0 req-1"#,
+            ),
+            entry(
+                "b",
+                2,
+                r#"203.0.113.8 - - [18/Oct/2026:10:00:01 +0000] "GET /b HTTP/1.1" 200 12 req-2"#,
+            ),
+            entry("c", 3, "status 503"),
+        ]);
+
+        assert_eq!(s.level_of(0), LogLevel::Error);
+        assert_eq!(s.level_of(1), LogLevel::Info);
+        assert_eq!(s.level_of(2), LogLevel::Unknown);
+
+        s.add_field_filter(FieldFilter {
+            path: "$.status".to_string(),
+            value: serde_json::json!(503),
+        });
+        assert_eq!(s.visible_indices(), vec![0]);
+
+        let columns = s.effective_table_columns(&[0, 1, 2]);
+        assert_eq!(columns[0], "status");
+        s.table_columns = vec!["requestId".to_string()];
+        assert_eq!(s.effective_table_columns(&[0]), vec!["requestId"]);
+    }
 }
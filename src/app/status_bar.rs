@@ -9,6 +9,7 @@ pub fn draw_status_bar(app: &App, ctx: &egui::Context) {
             ui.horizontal(|ui| {
                 use egui::Align;
 
+                let tab = app.active();
                 let (status, is_error) = compute_status(app);
 
                 if is_error {
@@ -18,23 +19,22 @@ pub fn draw_status_bar(app: &App, ctx: &egui::Context) {
                 }
 
                 // Friendly hint for common AWS auth issues.
-                if let Some(err) = &app.last_error {
-                    if err.contains("ExpiredTokenException")
-                        || err.contains("The security token included in the request is expired")
-                    {
-                        ui.label(
-                            egui::RichText::new(
-                                "Hint: AWS credentials expired. Re-run your AWS login.",
-                            )
-                            .italics(),
-                        );
-                    }
+                if let Some(err) = &tab.last_error
+                    && (err.contains("ExpiredTokenException")
+                        || err.contains("The security token included in the request is expired"))
+                {
+                    ui.label(
+                        egui::RichText::new(
+                            "Hint: AWS credentials expired. Re-run your AWS login.",
+                        )
+                        .italics(),
+                    );
                 }
 
                 ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
                     ui.label(format!(
                         "Tail: {}",
-                        if app.logs_view.tail_mode { "ON" } else { "OFF" }
+                        if tab.logs_view.tail_mode { "ON" } else { "OFF" }
                     ));
                 });
             });
@@ -42,18 +42,27 @@ pub fn draw_status_bar(app: &App, ctx: &egui::Context) {
 }
 
 fn compute_status(app: &App) -> (String, bool) {
-    if app.is_fetching {
-        ("Fetching logs…".to_string(), false)
-    } else if app.is_loading_groups {
+    let tab = app.active();
+    if tab.is_fetching {
+        let status = match &tab.fetch_progress {
+            Some(progress) => progress.describe(std::time::Instant::now()),
+            None => "Fetching logs…".to_string(),
+        };
+        (status, false)
+    } else if let Some(progress) = &tab.tail_progress {
+        (progress.describe(std::time::Instant::now()), false)
+    } else if tab.is_loading_groups {
         ("Loading log groups…".to_string(), false)
-    } else if let Some(err) = &app.last_error {
+    } else if app.export_rx.is_some() {
+        ("Exporting…".to_string(), false)
+    } else if let Some(err) = &tab.last_error {
         let msg = if err.len() > 61 {
             format!("Error: {}…", &err[..58])
         } else {
             format!("Error: {err}")
         };
         (msg, true)
-    } else if let Some(info) = &app.last_info {
+    } else if let Some(info) = &tab.last_info {
         (info.clone(), false) // <--- show info when present
     } else {
         ("Ready".to_string(), false)
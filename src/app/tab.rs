@@ -0,0 +1,871 @@
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::histogram::format_bucket_width;
+use crate::app::state::LogsViewState;
+use crate::aws::{AwsLogError, LogEntry, Progress};
+use crate::local_logs::{LocalLogError, local_path_from_group, source_name};
+use crate::worker::{RequestId, WorkerHandle, WorkerRequest};
+
+/// Default lookback of a fetch (and of each tail refresh).
+pub const DEFAULT_LOOKBACK: Duration = Duration::from_secs(5 * 60);
+
+/// One query tab: its own source, filters, results, tail state and
+/// in-flight worker requests.
+pub struct Tab {
+    /// Stable identity, unaffected by reordering.
+    pub id: u64,
+    pub title: String,
+    pub logs_view: LogsViewState,
+    /// Lookback of the latest fetch, also followed by the tail; unused
+    /// while `logs_view.fetch_range` is set.
+    pub lookback: Duration,
+    pub is_fetching: bool,
+    pub is_loading_groups: bool,
+    pub last_error: Option<String>,
+    pub last_info: Option<String>,
+    pub fetch_rx: Option<Receiver<Result<Vec<LogEntry>, AwsLogError>>>,
+    pub file_rx: Option<Receiver<Result<Vec<LogEntry>, LocalLogError>>>,
+    pub groups_rx: Option<Receiver<Result<Vec<String>, AwsLogError>>>,
+    /// The fetch (or file load) in flight, to cancel it.
+    pub fetch_request: Option<RequestId>,
+    pub groups_request: Option<RequestId>,
+    /// The tail running in the worker, and its interval.
+    pub tail_request: Option<(RequestId, Duration)>,
+    pub tail_rx: Option<Receiver<Result<Vec<LogEntry>, AwsLogError>>>,
+    pub tail_file_rx: Option<Receiver<Result<Vec<LogEntry>, LocalLogError>>>,
+    /// Pages and retries of the fetch in flight.
+    pub progress_rx: Option<Receiver<Progress>>,
+    pub fetch_progress: Option<FetchProgress>,
+    /// Pages and retries of the tail, and how far its refresh in flight has
+    /// come.
+    pub tail_progress_rx: Option<Receiver<Progress>>,
+    pub tail_progress: Option<FetchProgress>,
+}
+
+/// How far the fetch in flight has come.
+#[derive(Debug, Clone)]
+pub struct FetchProgress {
+    pub started: Instant,
+    pub pages: usize,
+    pub events: usize,
+    /// Set while a throttled call waits to be retried.
+    pub retry: Option<String>,
+    /// Status of a Logs Insights query, e.g. "Running, 1200 records scanned".
+    pub insights: Option<String>,
+}
+
+impl FetchProgress {
+    pub fn new(started: Instant) -> Self {
+        Self {
+            started,
+            pages: 0,
+            events: 0,
+            retry: None,
+            insights: None,
+        }
+    }
+
+    /// Count a page, or note a retry or Insights status.
+    pub fn record(&mut self, event: &Progress) {
+        match event {
+            Progress::Page { entries } => {
+                self.pages += 1;
+                self.events += entries.len();
+                self.retry = None;
+            }
+            Progress::Retrying {
+                attempt,
+                delay,
+                reason,
+            } => {
+                self.retry = Some(format!("{reason}, retry {attempt} in {}s", delay.as_secs()));
+            }
+            Progress::Refreshing => {}
+            Progress::Insights {
+                status,
+                records_scanned,
+                ..
+            } => {
+                self.retry = None;
+                self.insights = Some(format!("{status}, {records_scanned:.0} records scanned"));
+            }
+        }
+    }
+
+    /// Status line, e.g. "Fetching logs… 2 pages, 1500 events (4s)".
+    pub fn describe(&self, now: Instant) -> String {
+        let elapsed = now.duration_since(self.started).as_secs();
+        let mut line = "Fetching logs…".to_string();
+        if let Some(retry) = &self.retry {
+            line.push_str(&format!(" {retry}"));
+        } else if let Some(insights) = &self.insights {
+            line.push_str(&format!(" {insights}"));
+        } else if self.pages > 0 {
+            let pages = if self.pages == 1 { "page" } else { "pages" };
+            line.push_str(&format!(" {} {pages}, {} events", self.pages, self.events));
+        }
+        line.push_str(&format!(" ({elapsed}s)"));
+        line
+    }
+}
+
+/// The part of a tab saved across restarts. Results and tail mode are not
+/// restored: a restored tab starts idle until fetched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TabConfig {
+    pub title: String,
+    pub profile: String,
+    pub region: String,
+    pub log_group: String,
+    pub filter_text: String,
+    pub tail_interval_secs: u64,
+    pub show_local_time: bool,
+    pub show_histogram: bool,
+    #[serde(default)]
+    pub fold_multiline: bool,
+    #[serde(default)]
+    pub query_text: String,
+}
+
+/// All tabs as persisted in the eframe storage.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedTabs {
+    pub tabs: Vec<TabConfig>,
+    pub active: usize,
+}
+
+impl Tab {
+    pub fn new(id: u64, title: impl Into<String>) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            logs_view: LogsViewState::new_default(),
+            lookback: DEFAULT_LOOKBACK,
+            is_fetching: false,
+            is_loading_groups: false,
+            last_error: None,
+            last_info: None,
+            fetch_rx: None,
+            file_rx: None,
+            groups_rx: None,
+            fetch_request: None,
+            groups_request: None,
+            tail_request: None,
+            tail_rx: None,
+            tail_file_rx: None,
+            progress_rx: None,
+            fetch_progress: None,
+            tail_progress_rx: None,
+            tail_progress: None,
+        }
+    }
+
+    pub fn from_config(id: u64, config: TabConfig) -> Self {
+        let mut tab = Self::new(id, config.title);
+        let view = &mut tab.logs_view;
+        view.profile = config.profile;
+        view.region = config.region;
+        view.log_group = config.log_group;
+        view.filter_text = config.filter_text;
+        view.tail_interval_secs = config.tail_interval_secs.max(1);
+        view.show_local_time = config.show_local_time;
+        view.show_histogram = config.show_histogram;
+        view.fold_multiline = config.fold_multiline;
+        view.set_query(&config.query_text);
+        tab
+    }
+
+    pub fn to_config(&self) -> TabConfig {
+        let view = &self.logs_view;
+        TabConfig {
+            title: self.title.clone(),
+            profile: view.profile.clone(),
+            region: view.region.clone(),
+            log_group: view.log_group.clone(),
+            filter_text: view.filter_text.clone(),
+            tail_interval_secs: view.tail_interval_secs,
+            show_local_time: view.show_local_time,
+            show_histogram: view.show_histogram,
+            fold_multiline: view.fold_multiline,
+            query_text: view.query_text.clone(),
+        }
+    }
+
+    /// A new, empty tab on the same source as this one.
+    pub fn duplicate(&self, id: u64) -> Self {
+        let mut config = self.to_config();
+        config.title = format!("{} (copy)", self.title);
+        Self::from_config(id, config)
+    }
+
+    /// A busy tab has a request in flight with the worker.
+    pub fn is_busy(&self) -> bool {
+        self.is_fetching || self.is_loading_groups
+    }
+
+    /// Start a fetch, superseding the one in flight. A tailing tab restarts
+    /// its tail over `lookback` instead, which fetches right away.
+    pub fn start_fetch_logs(&mut self, worker: &WorkerHandle, lookback: Duration) {
+        self.abort_fetch(worker);
+        self.lookback = lookback;
+        if self.logs_view.tail_mode {
+            self.start_tail(worker);
+            return;
+        }
+        self.stop_tail(worker);
+
+        let profile = self.logs_view.profile.clone();
+        let region = self.logs_view.region.clone();
+        let filter = self.logs_view.filter_text.clone();
+
+        let log_group = self.logs_view.log_group.trim().to_string();
+        if log_group.is_empty() {
+            self.last_error = Some("Please select a log group.".to_string());
+            return;
+        }
+        self.logs_view.log_group = log_group.clone();
+
+        self.is_fetching = true;
+        self.last_error = None;
+        self.fetch_progress = Some(FetchProgress::new(Instant::now()));
+
+        if let Some(path) = local_path_from_group(&log_group) {
+            let (tx, rx) = channel::<Result<Vec<LogEntry>, LocalLogError>>();
+            let id = worker.send(WorkerRequest::LoadLocalFile {
+                path,
+                options: self.logs_view.local_file_options.clone(),
+                respond_to: tx,
+            });
+            self.file_rx = Some(rx);
+            self.fetch_request = Some(id);
+            return;
+        }
+
+        let (tx, rx) = channel::<Result<Vec<LogEntry>, AwsLogError>>();
+        let (progress_tx, progress_rx) = channel();
+
+        let id = worker.send(WorkerRequest::FetchRecentLogs {
+            profile: non_empty(profile),
+            region: non_empty(region),
+            log_group,
+            filter_pattern: non_empty(filter),
+            lookback,
+            absolute_range: self.logs_view.fetch_range,
+            limit: 1_000,
+            respond_to: tx,
+            progress: Some(progress_tx),
+        });
+
+        self.fetch_rx = Some(rx);
+        self.progress_rx = Some(progress_rx);
+        self.fetch_request = Some(id);
+    }
+
+    /// Abort the fetch in flight; its response, if any, is dropped.
+    pub fn cancel_fetch(&mut self, worker: &WorkerHandle) {
+        if self.is_fetching {
+            self.abort_fetch(worker);
+            self.last_info = Some("Fetch cancelled".to_string());
+        }
+    }
+
+    fn abort_fetch(&mut self, worker: &WorkerHandle) {
+        if let Some(id) = self.fetch_request.take() {
+            worker.cancel(id);
+        }
+        self.end_fetch();
+    }
+
+    /// Forget the fetch in flight, answered or not.
+    fn end_fetch(&mut self) {
+        self.is_fetching = false;
+        self.fetch_rx = None;
+        self.file_rx = None;
+        self.fetch_request = None;
+        self.progress_rx = None;
+        self.fetch_progress = None;
+    }
+
+    pub fn cancel_load_groups(&mut self, worker: &WorkerHandle) {
+        if let Some(id) = self.groups_request.take() {
+            worker.cancel(id);
+        }
+        self.is_loading_groups = false;
+        self.groups_rx = None;
+    }
+
+    /// Browse a local file as a pseudo log group.
+    pub fn open_local_file(&mut self, worker: &WorkerHandle, path: std::path::PathBuf) {
+        self.logs_view.log_group = source_name(&path);
+        self.logs_view.selected_group_index = None;
+        self.logs_view.clear_selection();
+        self.start_fetch_logs(worker, DEFAULT_LOOKBACK);
+    }
+
+    pub fn start_load_log_groups(&mut self, worker: &WorkerHandle) {
+        self.cancel_load_groups(worker);
+        let profile = self.logs_view.profile.clone();
+        let region = self.logs_view.region.clone();
+
+        self.logs_view.available_groups.clear();
+        self.logs_view.selected_group_index = None;
+        self.last_error = None;
+
+        self.is_loading_groups = true;
+
+        let (tx, rx) = channel::<Result<Vec<String>, AwsLogError>>();
+
+        let id = worker.send(WorkerRequest::ListLogGroups {
+            profile: non_empty(profile),
+            region: non_empty(region),
+            limit: 50,
+            respond_to: tx,
+        });
+
+        self.groups_rx = Some(rx);
+        self.groups_request = Some(id);
+    }
+
+    /// Collect any worker responses addressed to this tab.
+    pub fn poll_responses(&mut self) {
+        self.poll_progress();
+        self.poll_tail();
+
+        // Poll fetch results.
+        if let Some(rx) = self.fetch_rx.as_ref() {
+            match rx.try_recv() {
+                Ok(Ok(entries)) => {
+                    self.end_fetch();
+                    self.show_fetched(entries);
+                }
+
+                Ok(Err(err)) => {
+                    self.last_error = Some(format!("{err}"));
+                    self.last_info = None;
+                    self.end_fetch();
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.end_fetch();
+                    self.last_info = Some("Fetch aborted".to_string());
+                }
+            }
+        }
+
+        // Poll local file results.
+        if let Some(rx) = self.file_rx.as_ref() {
+            match rx.try_recv() {
+                Ok(Ok(entries)) => {
+                    let count = entries.len();
+                    self.logs_view.set_entries(entries);
+                    self.end_fetch();
+                    self.last_info = Some(format!("Loaded {count} events from file"));
+                    self.last_error = None;
+                }
+                Ok(Err(err)) => {
+                    self.last_error = Some(format!("{err}"));
+                    self.last_info = None;
+                    self.end_fetch();
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.end_fetch();
+                    self.last_info = Some("Loading file aborted".to_string());
+                }
+            }
+        }
+
+        // Poll group list results.
+        if let Some(rx) = self.groups_rx.as_ref() {
+            match rx.try_recv() {
+                Ok(Ok(groups)) => {
+                    self.logs_view.available_groups = groups;
+                    if let Some(idx) = self.logs_view.selected_group_index
+                        && idx >= self.logs_view.available_groups.len()
+                    {
+                        self.logs_view.selected_group_index = None;
+                    }
+                    self.groups_rx = None;
+                    self.groups_request = None;
+                    self.is_loading_groups = false;
+                }
+                Ok(Err(err)) => {
+                    self.last_error = Some(format!("{err}"));
+                    self.groups_rx = None;
+                    self.groups_request = None;
+                    self.is_loading_groups = false;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.groups_rx = None;
+                    self.groups_request = None;
+                    self.is_loading_groups = false;
+                }
+            }
+        }
+    }
+
+    fn show_fetched(&mut self, entries: Vec<LogEntry>) {
+        let count = entries.len();
+        self.logs_view.set_entries(entries);
+
+        let range = if self.logs_view.fetch_range.is_some() {
+            "selected range".to_string()
+        } else {
+            format!(
+                "last {}",
+                format_bucket_width(self.lookback.as_millis() as i64)
+            )
+        };
+        if count == 0 {
+            self.last_info = Some(format!("No results ({range})"));
+        } else {
+            self.last_info = Some(format!("Fetched {} events ({range})", count));
+        }
+        // Clear any stale error on success
+        self.last_error = None;
+    }
+
+    /// Show the pages of the fetch in flight as they arrive: the first
+    /// replaces the entries shown, later ones are appended.
+    fn poll_progress(&mut self) {
+        let Some(rx) = self.progress_rx.as_ref() else {
+            return;
+        };
+        let events: Vec<Progress> = rx.try_iter().collect();
+        let Some(progress) = self.fetch_progress.as_mut() else {
+            return;
+        };
+
+        for event in events {
+            progress.record(&event);
+            if let Progress::Page { entries } = event {
+                if progress.pages == 1 {
+                    self.logs_view.set_entries(entries);
+                } else {
+                    self.logs_view.append_entries(entries);
+                }
+            }
+        }
+    }
+
+    /// Start or stop the worker's tail to follow tail mode, restarting it
+    /// when the interval changes.
+    pub fn sync_tail(&mut self, worker: &WorkerHandle) {
+        let interval = Duration::from_secs(self.logs_view.tail_interval_secs.max(1));
+        match self.tail_request {
+            Some((_, running)) if self.logs_view.tail_mode && running == interval => {}
+            None if !self.logs_view.tail_mode => {}
+            _ if self.logs_view.tail_mode => self.start_tail(worker),
+            _ => self.stop_tail(worker),
+        }
+    }
+
+    /// (Re)start tailing the current source over the tab's lookback; the
+    /// first refresh comes right away.
+    fn start_tail(&mut self, worker: &WorkerHandle) {
+        self.abort_fetch(worker);
+        self.stop_tail(worker);
+
+        let log_group = self.logs_view.log_group.trim().to_string();
+        if log_group.is_empty() {
+            self.last_error = Some("Please select a log group.".to_string());
+            self.logs_view.tail_mode = false;
+            return;
+        }
+        self.logs_view.log_group = log_group.clone();
+        self.logs_view.fetch_range = None;
+        self.last_error = None;
+
+        let interval = Duration::from_secs(self.logs_view.tail_interval_secs.max(1));
+        let id = if let Some(path) = local_path_from_group(&log_group) {
+            let (tx, rx) = channel::<Result<Vec<LogEntry>, LocalLogError>>();
+            self.tail_file_rx = Some(rx);
+            worker.send(WorkerRequest::TailLocalFile {
+                path,
+                options: self.logs_view.local_file_options.clone(),
+                interval,
+                respond_to: tx,
+            })
+        } else {
+            let (tx, rx) = channel::<Result<Vec<LogEntry>, AwsLogError>>();
+            let (progress_tx, progress_rx) = channel();
+            self.tail_rx = Some(rx);
+            self.tail_progress_rx = Some(progress_rx);
+            worker.send(WorkerRequest::TailLogs {
+                profile: non_empty(self.logs_view.profile.clone()),
+                region: non_empty(self.logs_view.region.clone()),
+                log_group,
+                filter_pattern: non_empty(self.logs_view.filter_text.clone()),
+                lookback: self.lookback,
+                interval,
+                limit: 1_000,
+                respond_to: tx,
+                progress: Some(progress_tx),
+            })
+        };
+        self.tail_request = Some((id, interval));
+    }
+
+    pub fn stop_tail(&mut self, worker: &WorkerHandle) {
+        if let Some((id, _)) = self.tail_request.take() {
+            worker.cancel(id);
+        }
+        self.tail_rx = None;
+        self.tail_file_rx = None;
+        self.tail_progress_rx = None;
+        self.tail_progress = None;
+    }
+
+    /// Take the latest refresh of the tail; older ones are superseded.
+    fn poll_tail(&mut self) {
+        if let Some(rx) = &self.tail_progress_rx {
+            for event in rx.try_iter() {
+                match event {
+                    Progress::Refreshing => {
+                        self.tail_progress = Some(FetchProgress::new(Instant::now()));
+                    }
+                    event => {
+                        if let Some(progress) = self.tail_progress.as_mut() {
+                            progress.record(&event);
+                        }
+                    }
+                }
+            }
+        }
+        let result = match (&self.tail_rx, &self.tail_file_rx) {
+            (Some(rx), _) => latest(rx).map(|last| last.map(|r| r.map_err(|e| e.to_string()))),
+            (None, Some(rx)) => latest(rx).map(|last| last.map(|r| r.map_err(|e| e.to_string()))),
+            (None, None) => return,
+        };
+        match result {
+            Ok(Some(Ok(entries))) => {
+                self.tail_progress = None;
+                self.show_fetched(entries);
+            }
+            Ok(Some(Err(err))) => {
+                self.tail_progress = None;
+                self.last_error = Some(err);
+                self.last_info = None;
+            }
+            Ok(None) => {}
+            // The worker gave up on the tail; restarting it would likely
+            // fail the same way, so tail mode goes off.
+            Err(_) => {
+                self.tail_request = None;
+                self.tail_rx = None;
+                self.tail_file_rx = None;
+                self.tail_progress_rx = None;
+                self.tail_progress = None;
+                self.logs_view.tail_mode = false;
+                self.last_error = Some("Tail stopped unexpectedly".to_string());
+                self.last_info = None;
+            }
+        }
+    }
+}
+
+/// The last value waiting on `rx`, if any; an error once it is drained and
+/// closed.
+fn latest<T>(rx: &Receiver<T>) -> Result<Option<T>, TryRecvError> {
+    let mut last = None;
+    loop {
+        match rx.try_recv() {
+            Ok(value) => last = Some(value),
+            Err(TryRecvError::Empty) => return Ok(last),
+            Err(TryRecvError::Disconnected) => {
+                return last.map(Some).ok_or(TryRecvError::Disconnected);
+            }
+        }
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.trim().is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Move the item at `from` so that it ends up at index `to`.
+pub fn move_item<T>(items: &mut Vec<T>, from: usize, to: usize) {
+    if from >= items.len() || from == to {
+        return;
+    }
+    let item = items.remove(from);
+    items.insert(to.min(items.len()), item);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_config_round_trips_query_settings() {
+        let mut tab = Tab::new(1, "API errors");
+        tab.logs_view.profile = "prod".to_string();
+        tab.logs_view.region = "us-east-1".to_string();
+        tab.logs_view.log_group = "/aws/lambda/api".to_string();
+        tab.logs_view.filter_text = "ERROR".to_string();
+        tab.logs_view.tail_interval_secs = 30;
+        tab.logs_view.show_local_time = true;
+        tab.logs_view.tail_mode = true;
+        tab.logs_view.set_query("level:error AND NOT path:/health");
+
+        let config = tab.to_config();
+        let json = serde_json::to_string(&config).expect("serializable");
+        let restored = Tab::from_config(7, serde_json::from_str(&json).expect("deserializable"));
+
+        assert_eq!(restored.id, 7);
+        assert_eq!(restored.to_config(), config);
+        assert!(!restored.logs_view.tail_mode);
+        assert!(restored.logs_view.query.is_some());
+        assert!(restored.logs_view.entries.is_empty());
+    }
+
+    #[test]
+    fn new_fetch_supersedes_and_cancel_stops_the_one_in_flight() {
+        let worker = crate::worker::spawn_worker();
+        let mut tab = Tab::new(1, "file");
+        tab.open_local_file(&worker, "/does/not/exist.log".into());
+        let first = tab.fetch_request.expect("fetch in flight");
+
+        tab.start_fetch_logs(&worker, DEFAULT_LOOKBACK);
+        let second = tab.fetch_request.expect("fetch in flight");
+        assert_ne!(first, second);
+        assert!(tab.is_fetching);
+
+        tab.cancel_fetch(&worker);
+        assert!(!tab.is_fetching);
+        assert!(tab.file_rx.is_none() && tab.fetch_request.is_none());
+        assert_eq!(tab.last_info.as_deref(), Some("Fetch cancelled"));
+
+        // Whatever the worker answers goes nowhere.
+        tab.poll_responses();
+        assert!(tab.last_error.is_none());
+    }
+
+    #[test]
+    fn tail_follows_tail_mode_and_interval() {
+        let worker = crate::worker::spawn_worker();
+        let mut tab = Tab::new(1, "file");
+        tab.logs_view.log_group = source_name(std::path::Path::new("/does/not/exist.log"));
+
+        tab.sync_tail(&worker);
+        assert!(tab.tail_request.is_none());
+
+        tab.logs_view.tail_mode = true;
+        tab.sync_tail(&worker);
+        let (first, interval) = tab.tail_request.expect("tailing");
+        assert_eq!(interval, Duration::from_secs(5));
+        tab.sync_tail(&worker);
+        assert_eq!(tab.tail_request.map(|(id, _)| id), Some(first));
+
+        tab.logs_view.tail_interval_secs = 30;
+        tab.sync_tail(&worker);
+        let (second, interval) = tab.tail_request.expect("restarted");
+        assert_ne!(first, second);
+        assert_eq!(interval, Duration::from_secs(30));
+        assert!(tab.tail_file_rx.is_some() && !tab.is_fetching);
+
+        tab.logs_view.tail_mode = false;
+        tab.sync_tail(&worker);
+        assert!(tab.tail_request.is_none() && tab.tail_file_rx.is_none());
+    }
+
+    #[test]
+    fn fetches_and_tails_report_their_lookback() {
+        let mut tab = Tab::new(1, "api");
+        tab.lookback = Duration::from_secs(15 * 60);
+        tab.show_fetched(vec![entry(1)]);
+        assert_eq!(
+            tab.last_info.as_deref(),
+            Some("Fetched 1 events (last 15m)")
+        );
+
+        tab.logs_view.fetch_range = Some((1_000, 2_000));
+        tab.show_fetched(Vec::new());
+        assert_eq!(
+            tab.last_info.as_deref(),
+            Some("No results (selected range)")
+        );
+
+        let worker = crate::worker::spawn_worker();
+        tab.logs_view.log_group = source_name(std::path::Path::new("/does/not/exist.log"));
+        tab.logs_view.tail_mode = true;
+        tab.start_fetch_logs(&worker, Duration::from_secs(3_600));
+        assert_eq!(tab.lookback, Duration::from_secs(3_600));
+        assert!(tab.tail_request.is_some() && tab.logs_view.fetch_range.is_none());
+        tab.stop_tail(&worker);
+    }
+
+    #[test]
+    fn tail_shows_only_the_latest_refresh() {
+        let mut tab = Tab::new(1, "api");
+        let (tx, rx) = channel();
+        tab.tail_rx = Some(rx);
+        tx.send(Ok(vec![entry(1)])).unwrap();
+        tx.send(Ok(vec![entry(1), entry(2)])).unwrap();
+        tab.poll_responses();
+        assert_eq!(tab.logs_view.entries.len(), 2);
+        assert_eq!(tab.last_info.as_deref(), Some("Fetched 2 events (last 5m)"));
+
+        tab.logs_view.tail_mode = true;
+        drop(tx);
+        tab.poll_responses();
+        assert!(tab.tail_rx.is_none() && !tab.logs_view.tail_mode);
+        assert_eq!(tab.last_error.as_deref(), Some("Tail stopped unexpectedly"));
+    }
+
+    #[test]
+    fn tail_progress_covers_the_refresh_in_flight() {
+        let mut tab = Tab::new(1, "api");
+        let (tx, rx) = channel();
+        let (progress_tx, progress_rx) = channel();
+        tab.tail_rx = Some(rx);
+        tab.tail_progress_rx = Some(progress_rx);
+
+        progress_tx.send(Progress::Refreshing).unwrap();
+        progress_tx
+            .send(Progress::Retrying {
+                attempt: 1,
+                delay: Duration::from_secs(2),
+                reason: "ThrottlingException".to_string(),
+            })
+            .unwrap();
+        tab.poll_responses();
+        let progress = tab.tail_progress.as_ref().expect("refresh in flight");
+        assert_eq!(
+            progress.retry.as_deref(),
+            Some("ThrottlingException, retry 1 in 2s")
+        );
+
+        tx.send(Ok(vec![entry(1)])).unwrap();
+        tab.poll_responses();
+        assert!(tab.tail_progress.is_none());
+        assert_eq!(tab.logs_view.entries.len(), 1);
+    }
+
+    fn entry(timestamp_millis: i64) -> LogEntry {
+        LogEntry {
+            timestamp_millis,
+            message: format!("event {timestamp_millis}"),
+            log_stream_name: None,
+            log_group_name: None,
+            event_id: None,
+            ingestion_time_millis: None,
+        }
+    }
+
+    #[test]
+    fn pages_show_as_they_arrive_until_the_result_replaces_them() {
+        let mut tab = Tab::new(1, "api");
+        tab.logs_view.set_entries(vec![entry(0)]);
+        let (tx, rx) = channel();
+        let (progress_tx, progress_rx) = channel();
+        tab.is_fetching = true;
+        tab.fetch_rx = Some(rx);
+        tab.progress_rx = Some(progress_rx);
+        tab.fetch_progress = Some(FetchProgress::new(Instant::now()));
+
+        progress_tx
+            .send(Progress::Page {
+                entries: vec![entry(1), entry(2)],
+            })
+            .unwrap();
+        progress_tx
+            .send(Progress::Retrying {
+                attempt: 1,
+                delay: Duration::from_secs(1),
+                reason: "ThrottlingException".to_string(),
+            })
+            .unwrap();
+        tab.poll_responses();
+        assert_eq!(tab.logs_view.entries.len(), 2);
+        let progress = tab.fetch_progress.as_ref().expect("fetch in flight");
+        assert_eq!((progress.pages, progress.events), (1, 2));
+        assert_eq!(
+            progress.retry.as_deref(),
+            Some("ThrottlingException, retry 1 in 1s")
+        );
+
+        progress_tx
+            .send(Progress::Page {
+                entries: vec![entry(3)],
+            })
+            .unwrap();
+        tab.poll_responses();
+        let shown: Vec<i64> = tab
+            .logs_view
+            .entries
+            .iter()
+            .map(|e| e.timestamp_millis)
+            .collect();
+        assert_eq!(shown, [1, 2, 3]);
+        assert_eq!(tab.logs_view.levels.len(), 3);
+
+        tx.send(Ok(vec![entry(1), entry(2), entry(3)])).unwrap();
+        tab.poll_responses();
+        assert_eq!(tab.logs_view.entries.len(), 3);
+        assert!(!tab.is_fetching && tab.fetch_progress.is_none());
+        assert_eq!(tab.last_info.as_deref(), Some("Fetched 3 events (last 5m)"));
+    }
+
+    #[test]
+    fn fetch_progress_describes_pages_and_elapsed_time() {
+        let started = Instant::now();
+        let later = started + Duration::from_secs(4);
+        let mut progress = FetchProgress::new(started);
+        assert_eq!(progress.describe(later), "Fetching logs… (4s)");
+
+        progress.pages = 2;
+        progress.events = 1500;
+        assert_eq!(
+            progress.describe(later),
+            "Fetching logs… 2 pages, 1500 events (4s)"
+        );
+
+        progress.insights = Some("Running, 1200 records scanned".to_string());
+        assert_eq!(
+            progress.describe(later),
+            "Fetching logs… Running, 1200 records scanned (4s)"
+        );
+    }
+
+    #[test]
+    fn duplicate_copies_source_but_not_results() {
+        let mut tab = Tab::new(1, "Tab 1");
+        tab.logs_view.log_group = "/aws/lambda/api".to_string();
+        tab.logs_view.entries.push(LogEntry {
+            timestamp_millis: 1,
+            message: "hello".to_string(),
+            log_stream_name: None,
+            log_group_name: None,
+            event_id: None,
+            ingestion_time_millis: None,
+        });
+
+        let copy = tab.duplicate(2);
+        assert_eq!(copy.id, 2);
+        assert_eq!(copy.title, "Tab 1 (copy)");
+        assert_eq!(copy.logs_view.log_group, "/aws/lambda/api");
+        assert!(copy.logs_view.entries.is_empty());
+    }
+
+    #[test]
+    fn move_item_reorders_in_both_directions() {
+        let mut items = vec!['a', 'b', 'c', 'd'];
+        move_item(&mut items, 0, 2);
+        assert_eq!(items, vec!['b', 'c', 'a', 'd']);
+        move_item(&mut items, 3, 0);
+        assert_eq!(items, vec!['d', 'b', 'c', 'a']);
+        move_item(&mut items, 1, 10);
+        assert_eq!(items, vec!['d', 'c', 'a', 'b']);
+        move_item(&mut items, 9, 0);
+        assert_eq!(items, vec!['d', 'c', 'a', 'b']);
+    }
+}
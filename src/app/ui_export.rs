@@ -0,0 +1,116 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::app::export::{CsvColumn, ExportFormat};
+use crate::app::state::ExportScope;
+
+pub fn draw_export_window(app: &mut App, ctx: &egui::Context) {
+    if !app.export_dialog.open {
+        return;
+    }
+
+    let mut open = true;
+    let mut chosen_path = None;
+
+    egui::Window::new("Export")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let dialog = &mut app.export_dialog;
+            let Some(tab) = app.tabs.iter().find(|t| t.id == dialog.source_tab) else {
+                ui.label("The tab this export was started from is closed.");
+                return;
+            };
+            ui.label(format!("From tab: {}", tab.title));
+            let total = tab.logs_view.entries.len();
+            let selected = tab.logs_view.selection.len();
+
+            ui.label("Entries:");
+            ui.radio_value(
+                &mut dialog.scope,
+                ExportScope::All,
+                format!("All fetched ({total})"),
+            );
+            ui.radio_value(
+                &mut dialog.scope,
+                ExportScope::Filtered,
+                "Only those passing the local filter",
+            );
+            ui.add_enabled_ui(selected > 0, |ui| {
+                ui.radio_value(
+                    &mut dialog.scope,
+                    ExportScope::Selection,
+                    format!("Selection ({selected})"),
+                );
+            });
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label("Format:");
+                for format in ExportFormat::ALL {
+                    ui.selectable_value(&mut dialog.options.format, format, format.label());
+                }
+            });
+
+            if dialog.options.format == ExportFormat::Csv {
+                ui.label("Columns:");
+                ui.horizontal_wrapped(|ui| {
+                    for column in CsvColumn::STANDARD {
+                        let mut enabled = dialog.options.csv_columns.contains(&column);
+                        if ui.checkbox(&mut enabled, column.header()).changed() {
+                            let keep: Vec<_> = CsvColumn::STANDARD
+                                .into_iter()
+                                .filter(|c| {
+                                    if *c == column {
+                                        enabled
+                                    } else {
+                                        dialog.options.csv_columns.contains(c)
+                                    }
+                                })
+                                .collect();
+                            dialog.options.csv_columns = keep;
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("JSON fields:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut dialog.extra_fields)
+                            .hint_text("$.level, $.ctx.requestId")
+                            .desired_width(220.0),
+                    );
+                });
+            }
+
+            if dialog.options.format != ExportFormat::JsonLines {
+                ui.checkbox(&mut dialog.options.use_local_time, "Local time");
+            }
+
+            ui.separator();
+
+            let exporting = app.export_rx.is_some();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!exporting, egui::Button::new("Save as…"))
+                    .clicked()
+                {
+                    let format = dialog.options.format;
+                    chosen_path = rfd::FileDialog::new()
+                        .set_file_name(format!("logs.{}", format.file_extension()))
+                        .add_filter(format.label(), &[format.file_extension()])
+                        .save_file();
+                }
+                if exporting {
+                    ui.spinner();
+                }
+            });
+        });
+
+    if let Some(path) = chosen_path {
+        app.start_export(path);
+        open = false;
+    }
+    app.export_dialog.open = open;
+}
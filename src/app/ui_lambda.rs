@@ -0,0 +1,139 @@
+use eframe::egui;
+
+use crate::app::lambda::{LambdaSummary, invocation_block};
+use crate::app::state::format_timestamp_millis;
+use crate::app::tab::Tab;
+
+/// Slowest invocations listed in the summary.
+const SLOWEST_SHOWN: usize = 10;
+
+/// Invocation summary of a Lambda log group, built from the `REPORT` lines
+/// passing the local filters. Clicking one of the slowest invocations
+/// selects its START..END block in the list.
+pub fn draw_lambda_summary(tab: &mut Tab, ui: &mut egui::Ui) {
+    let view = &mut tab.logs_view;
+    if !view.log_group.starts_with("/aws/lambda/") && !view.has_lambda_lines {
+        return;
+    }
+
+    let mut open_block = None;
+    egui::CollapsingHeader::new("Lambda invocations")
+        .id_salt(("lambda_summary", tab.id))
+        .show(ui, |ui| {
+            let matches = view.entry_matcher();
+            let indices: Vec<usize> = (0..view.entries.len())
+                .filter(|&idx| matches(idx))
+                .collect();
+            let summary = LambdaSummary::from_view(view, &indices);
+            if summary.count() == 0 {
+                ui.weak("No REPORT lines in the results.");
+                return;
+            }
+
+            let ms = |value: Option<f64>| value.map_or("-".to_string(), format_ms);
+            egui::Grid::new(("lambda_summary_stats", tab.id))
+                .num_columns(4)
+                .spacing([24.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Invocations");
+                    ui.strong(summary.count().to_string());
+                    ui.label("Duration p50");
+                    ui.monospace(ms(summary.duration_percentile(0.50)));
+                    ui.end_row();
+
+                    ui.label("Cold starts");
+                    ui.strong(format!(
+                        "{} ({:.0}%)",
+                        summary.cold_starts(),
+                        percent(summary.cold_starts() as f64, summary.count() as f64)
+                    ));
+                    ui.label("Duration p95");
+                    ui.monospace(ms(summary.duration_percentile(0.95)));
+                    ui.end_row();
+
+                    ui.label("Timeouts");
+                    let timeouts = summary.timeouts();
+                    if timeouts > 0 {
+                        ui.colored_label(ui.visuals().error_fg_color, timeouts.to_string());
+                    } else {
+                        ui.strong("0");
+                    }
+                    ui.label("Duration p99");
+                    ui.monospace(ms(summary.duration_percentile(0.99)));
+                    ui.end_row();
+
+                    ui.label("Billed");
+                    ui.monospace(format!(
+                        "{} ({:.2} GB-s)",
+                        format_ms(summary.total_billed_ms()),
+                        summary.gb_seconds()
+                    ));
+                    ui.label("Max memory");
+                    let used = summary.max_memory_used_mb();
+                    let size = summary.memory_size_mb();
+                    let share = percent(used, size);
+                    let text = format!("{used:.0} / {size:.0} MB ({share:.0}%)");
+                    if share >= 90.0 {
+                        ui.colored_label(ui.visuals().warn_fg_color, text);
+                    } else {
+                        ui.monospace(text);
+                    }
+                    ui.end_row();
+                });
+
+            ui.add_space(4.0);
+            ui.label("Slowest invocations:");
+            egui::Grid::new(("lambda_slowest", tab.id))
+                .num_columns(5)
+                .striped(true)
+                .show(ui, |ui| {
+                    for invocation in summary.slowest(SLOWEST_SHOWN) {
+                        let entry = &view.entries[invocation.report_idx];
+                        ui.small(format_timestamp_millis(
+                            entry.timestamp_millis,
+                            view.show_local_time,
+                        ));
+                        if ui
+                            .link(egui::RichText::new(&invocation.request_id).monospace())
+                            .on_hover_text("Select this invocation's START..END block")
+                            .clicked()
+                        {
+                            open_block = Some(invocation.report_idx);
+                        }
+                        ui.monospace(format_ms(invocation.duration_ms));
+                        ui.monospace(format!("{:.0} MB", invocation.max_memory_used_mb));
+                        let mut notes = Vec::new();
+                        if let Some(init) = invocation.init_duration_ms {
+                            notes.push(format!("cold start (init {})", format_ms(init)));
+                        }
+                        if invocation.timed_out {
+                            notes.push("timed out".to_string());
+                        }
+                        ui.small(notes.join(", "));
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(report_idx) = open_block {
+        let block = invocation_block(view, report_idx);
+        view.select_block(&block);
+        tab.last_info = Some(format!("Selected {} lines of the invocation", block.len()));
+    }
+}
+
+fn format_ms(ms: f64) -> String {
+    if ms >= 1_000.0 {
+        format!("{:.2} s", ms / 1_000.0)
+    } else {
+        format!("{ms:.1} ms")
+    }
+}
+
+fn percent(part: f64, whole: f64) -> f64 {
+    if whole > 0.0 {
+        part / whole * 100.0
+    } else {
+        0.0
+    }
+}
@@ -1,99 +1,635 @@
 use eframe::egui;
 
-use crate::app::App;
-use crate::app::state::{Theme, format_timestamp_millis, try_pretty_json};
+use crate::app::ansi::{has_ansi, parse_ansi, strip_ansi};
+use crate::app::copy::{CopyFormat, format_entries};
+use crate::app::json_tree::{lookup_path, value_to_copy_text};
+use crate::app::multiline::ContinuationRules;
+use crate::app::parsers::MessageFormat;
+use crate::app::query::{apply_completion, complete_field};
+use crate::app::state::ExportScope;
+use crate::app::state::{LogsViewState, format_timestamp_millis};
+use crate::app::tab::{DEFAULT_LOOKBACK, Tab};
+use crate::app::{App, histogram, ui_lambda};
+
+/// Table cells are padded to the column name, within these bounds (chars).
+const MIN_CELL_WIDTH: usize = 6;
+const MAX_CELL_WIDTH: usize = 24;
+
+/// Draw the filter bar, histogram and entry list of the tab at `tab_idx`.
+/// Only the pane with `has_keyboard` reacts to keyboard shortcuts.
+///
+/// Returns the timestamp of the entry the user selected in this frame, so a
+/// split view can sync the other pane to it.
+pub fn draw_logs_view(
+    app: &mut App,
+    tab_idx: usize,
+    has_keyboard: bool,
+    ui: &mut egui::Ui,
+) -> Option<i64> {
+    let App {
+        tabs,
+        theme,
+        worker,
+        export_dialog,
+        ..
+    } = app;
+    let theme = *theme;
+    let tab = &mut tabs[tab_idx];
+    let selected_before = tab.logs_view.selected_entry;
 
-pub fn draw_logs_view(app: &mut App, ui: &mut egui::Ui) {
     ui.label("Logs (CloudWatch via AWS SDK):");
     ui.separator();
 
     ui.horizontal(|ui| {
         ui.label("Filter (CloudWatch pattern):");
         let filter_response =
-            ui.add(egui::TextEdit::singleline(&mut app.logs_view.filter_text).desired_width(250.0));
+            ui.add(egui::TextEdit::singleline(&mut tab.logs_view.filter_text).desired_width(250.0));
 
         if filter_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-            app.start_fetch_logs(std::time::Duration::from_secs(5 * 60));
+            tab.start_fetch_logs(worker, DEFAULT_LOOKBACK);
         }
 
         ui.separator();
 
-        ui.checkbox(&mut app.logs_view.tail_mode, "Tail");
+        ui.checkbox(&mut tab.logs_view.tail_mode, "Tail");
+
+        ui.separator();
+
+        ui.checkbox(&mut tab.logs_view.show_local_time, "Local time");
+
+        ui.separator();
+
+        ui.checkbox(&mut tab.logs_view.show_histogram, "Histogram");
 
         ui.separator();
 
-        ui.checkbox(&mut app.logs_view.show_local_time, "Local time");
+        ui.checkbox(&mut tab.logs_view.strip_ansi, "Strip ANSI colors");
+
+        ui.separator();
+
+        let view = &mut tab.logs_view;
+        let mut refold = ui
+            .checkbox(&mut view.fold_multiline, "Fold multi-line")
+            .on_hover_text("Fold stack traces split over several events into one entry")
+            .changed();
+        ui.menu_button("Rules", |ui| {
+            let rules = &mut view.multiline_rules;
+            refold |= ui
+                .checkbox(
+                    &mut rules.leading_whitespace,
+                    "Lines starting with whitespace",
+                )
+                .changed();
+            ui.label("Lines starting with (one per line):");
+            let mut prefixes = rules.prefixes_text();
+            if ui
+                .add(
+                    egui::TextEdit::multiline(&mut prefixes)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_rows(5),
+                )
+                .changed()
+            {
+                rules.set_prefixes_text(&prefixes);
+                refold = true;
+            }
+            ui.horizontal(|ui| {
+                ui.label("Max gap (ms):");
+                refold |= ui
+                    .add(egui::DragValue::new(&mut rules.max_gap_millis).range(0..=60_000))
+                    .changed();
+            });
+            if ui.button("Reset to defaults").clicked() {
+                *rules = ContinuationRules::default();
+                refold = true;
+            }
+        });
+        if refold {
+            view.refold();
+        }
+
+        ui.separator();
+
+        ui.checkbox(&mut view.table_view, "Table")
+            .on_hover_text("Show parsed fields as columns");
+        ui.menu_button("Parsing", |ui| {
+            ui.label("Recognized formats:");
+            let mut reparse = false;
+            for format in MessageFormat::ALL {
+                let mut enabled = view.parsers.is_enabled(format);
+                if ui.checkbox(&mut enabled, format.label()).changed() {
+                    view.parsers.set_enabled(format, enabled);
+                    reparse = true;
+                }
+            }
+            if reparse {
+                view.reparse();
+            }
+
+            ui.separator();
+            ui.label("Table columns (comma-separated, empty for automatic):");
+            let mut columns = view.table_columns.join(", ");
+            if ui
+                .add(egui::TextEdit::singleline(&mut columns).font(egui::TextStyle::Monospace))
+                .changed()
+            {
+                view.table_columns = columns
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+        });
 
         ui.separator();
         ui.label("Tail every (s):");
-        let mut interval = app.logs_view.tail_interval_secs as i32;
+        let mut interval = tab.logs_view.tail_interval_secs as i32;
         if ui
             .add(egui::DragValue::new(&mut interval).range(1..=300))
             .changed()
         {
-            app.logs_view.tail_interval_secs = interval.max(1) as u64;
+            tab.logs_view.tail_interval_secs = interval.max(1) as u64;
         }
     });
 
-    ui.separator();
+    draw_query_bar(&mut tab.logs_view, tab.id, ui);
 
-    egui::ScrollArea::vertical()
-        .auto_shrink([false; 2])
-        .show(ui, |ui| {
-            for entry in app.logs_view.entries.iter() {
-                let ts_formatted =
-                    format_timestamp_millis(entry.timestamp_millis, app.logs_view.show_local_time);
-
-                if !app.logs_view.filter_text.is_empty()
-                    && !entry
-                        .message
-                        .to_lowercase()
-                        .contains(&app.logs_view.filter_text.to_lowercase())
+    if !tab.logs_view.field_filters.is_empty() {
+        let mut remove = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Field filters:");
+            for (idx, filter) in tab.logs_view.field_filters.iter().enumerate() {
+                if ui
+                    .small_button(format!("{} ✕", filter.label()))
+                    .on_hover_text("Remove this condition")
+                    .clicked()
                 {
-                    continue;
+                    remove = Some(idx);
                 }
+            }
+            if ui.small_button("Clear").clicked() {
+                remove = Some(usize::MAX);
+            }
+        });
+        match remove {
+            Some(usize::MAX) => tab.logs_view.field_filters.clear(),
+            Some(idx) => {
+                tab.logs_view.field_filters.remove(idx);
+            }
+            None => {}
+        }
+    }
 
-                let level_color = if app.theme == Theme::RetroGreen {
-                    if entry.message.contains("ERROR") {
-                        egui::Color32::from_rgb(0xff, 0x40, 0x40)
-                    } else if entry.message.contains("WARN") {
-                        egui::Color32::from_rgb(0xff, 0xff, 0x80)
-                    } else {
-                        egui::Color32::from_rgb(0x00, 0xff, 0x66)
-                    }
-                } else {
-                    if entry.message.contains("ERROR") {
-                        egui::Color32::RED
-                    } else if entry.message.contains("WARN") {
-                        egui::Color32::YELLOW
-                    } else if entry.message.contains("INFO") {
-                        egui::Color32::LIGHT_GREEN
-                    } else {
-                        egui::Color32::WHITE
+    let pattern_filter = &mut tab.logs_view.pattern_filter;
+    if pattern_filter.is_active() {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Patterns:");
+            if let Some(only) = &pattern_filter.only
+                && ui
+                    .small_button(format!("only {} ✕", only.text()))
+                    .on_hover_text("Show all patterns")
+                    .clicked()
+            {
+                pattern_filter.only = None;
+            }
+            if !pattern_filter.hidden.is_empty()
+                && ui
+                    .small_button(format!("{} hidden ✕", pattern_filter.hidden.len()))
+                    .on_hover_text("Unhide all patterns")
+                    .clicked()
+            {
+                pattern_filter.hidden.clear();
+            }
+        });
+    }
+
+    if tab.logs_view.show_histogram {
+        histogram::draw_histogram(tab, theme, worker, ui);
+    }
+
+    ui_lambda::draw_lambda_summary(tab, ui);
+
+    ui.horizontal(|ui| {
+        let selected = tab.logs_view.selection.len();
+        ui.label(format!("{selected} selected"));
+
+        ui.add_enabled_ui(selected > 0, |ui| {
+            ui.menu_button("Copy", |ui| {
+                for format in CopyFormat::ALL {
+                    if ui.button(format.label()).clicked() {
+                        copy_selection(tab, ui.ctx(), format);
+                        ui.close();
                     }
-                };
+                }
+            });
+        });
 
-                let header = match &entry.log_stream_name {
-                    Some(stream) => format!("[{}] ({})", ts_formatted, stream),
-                    None => format!("[{}]", ts_formatted),
-                };
+        if ui.button("Export…").clicked() {
+            export_dialog.open = true;
+            export_dialog.source_tab = tab.id;
+            export_dialog.scope = if selected > 0 {
+                ExportScope::Selection
+            } else {
+                ExportScope::Filtered
+            };
+        }
+    });
 
-                ui.colored_label(egui::Color32::LIGHT_BLUE, header);
+    ui.separator();
 
-                if let Some(pretty_json) = try_pretty_json(&entry.message) {
-                    let mut s = pretty_json.clone();
-                    ui.add(
-                        egui::TextEdit::multiline(&mut s)
-                            .font(egui::TextStyle::Monospace)
-                            .text_color(level_color)
-                            .desired_width(f32::INFINITY)
-                            .interactive(false),
+    // Keyboard navigation and shortcuts, unless a text field is being edited.
+    let mut scroll_to_selected = false;
+    if has_keyboard && ui.memory(|m| m.focused().is_none()) {
+        let (up, down, escape, select_all, copy) = ui.input(|i| {
+            (
+                i.key_pressed(egui::Key::ArrowUp),
+                i.key_pressed(egui::Key::ArrowDown),
+                i.key_pressed(egui::Key::Escape),
+                i.modifiers.command && i.key_pressed(egui::Key::A),
+                i.events.iter().any(|e| matches!(e, egui::Event::Copy)),
+            )
+        });
+        if up {
+            tab.logs_view.move_selection(-1);
+            scroll_to_selected = true;
+        } else if down {
+            tab.logs_view.move_selection(1);
+            scroll_to_selected = true;
+        } else if escape {
+            tab.logs_view.clear_selection();
+        } else if select_all {
+            tab.logs_view.select_all_visible();
+        } else if copy && !tab.logs_view.selection.is_empty() {
+            copy_selection(tab, ui.ctx(), CopyFormat::RawText);
+        }
+    }
+
+    // Pages of the fetch in flight are listed as they arrive.
+    if let Some(progress) = &tab.fetch_progress
+        && progress.events > 0
+    {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(format!("{} events so far, more to come…", progress.events));
+        });
+    }
+
+    let visible = tab.logs_view.visible_indices();
+    let columns = if tab.logs_view.table_view {
+        tab.logs_view.effective_table_columns(&visible)
+    } else {
+        Vec::new()
+    };
+    if !columns.is_empty() {
+        draw_table_header(&tab.logs_view, &visible, &columns, ui);
+    }
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace) + 4.0;
+    let mut clicked: Option<(usize, egui::Modifiers)> = None;
+    let mut context_copy: Option<(usize, CopyFormat)> = None;
+    let mut toggle_group: Option<usize> = None;
+
+    let mut scroll_area = egui::ScrollArea::vertical().auto_shrink([false; 2]);
+    let scroll_target = if std::mem::take(&mut tab.logs_view.scroll_to_marker) {
+        tab.logs_view.sync_marker
+    } else {
+        tab.logs_view.scroll_to_entry.take()
+    };
+    if let Some(pos) =
+        scroll_target.and_then(|target| visible.iter().position(|&idx| idx == target))
+    {
+        // Rows outside the viewport are not laid out, so scroll by offset.
+        let spacing = ui.spacing().item_spacing.y;
+        let offset = pos as f32 * (row_height + spacing) - ui.available_height() / 2.0;
+        scroll_area = scroll_area.vertical_scroll_offset(offset.max(0.0));
+    }
+
+    scroll_area.show_rows(ui, row_height, visible.len(), |ui, row_range| {
+        let folded = tab.logs_view.is_folded();
+        for &idx in &visible[row_range] {
+            let view = &tab.logs_view;
+            let entry = &view.entries[idx];
+            let ts_formatted =
+                format_timestamp_millis(entry.timestamp_millis, view.show_local_time);
+            let level_color = view.level_of(idx).color(theme);
+            let first_line = entry.message.lines().next().unwrap_or_default();
+            let folded_count = view.folded_members(idx).len();
+            let expanded = view.expanded_groups.contains(&idx);
+            let is_continuation = folded && !view.folding.is_head(idx);
+
+            let mut job = egui::text::LayoutJob::default();
+            let mono = egui::TextFormat {
+                font_id: egui::TextStyle::Monospace.resolve(ui.style()),
+                color: egui::Color32::LIGHT_BLUE,
+                ..Default::default()
+            };
+            if is_continuation {
+                job.append("    ", 0.0, mono.clone());
+            }
+            job.append(&format!("[{ts_formatted}] "), 0.0, mono.clone());
+            if !columns.is_empty() {
+                let parsed = view.parsed(idx);
+                for column in &columns {
+                    let value = parsed
+                        .and_then(|p| lookup_path(&p.fields, column))
+                        .map(|v| value_to_copy_text(&v))
+                        .unwrap_or_default();
+                    job.append(
+                        &table_cell(&value, column_width(column)),
+                        0.0,
+                        egui::TextFormat {
+                            color: ui.visuals().text_color(),
+                            ..mono.clone()
+                        },
                     );
-                } else {
-                    ui.label(egui::RichText::new(&entry.message).color(level_color));
                 }
+            }
+            if view.strip_ansi || !has_ansi(first_line) {
+                job.append(
+                    &strip_ansi(first_line),
+                    0.0,
+                    egui::TextFormat {
+                        color: level_color,
+                        ..mono.clone()
+                    },
+                );
+            } else {
+                append_ansi(&mut job, first_line, &mono, level_color, ui.visuals());
+            }
+            if folded_count > 0 && !expanded {
+                job.append(
+                    &format!("  (+{folded_count} lines)"),
+                    0.0,
+                    egui::TextFormat {
+                        color: ui.visuals().weak_text_color(),
+                        ..mono
+                    },
+                );
+            }
+            job.wrap.max_rows = 1;
+            job.wrap.break_anywhere = true;
+
+            let is_selected = view.selection.contains(&idx);
+            let mut response = ui
+                .horizontal(|ui| {
+                    if folded {
+                        let toggle_size = egui::vec2(18.0, row_height);
+                        if folded_count > 0 {
+                            let toggle =
+                                egui::Button::new(if expanded { "▼" } else { "▶" }).frame(false);
+                            if ui
+                                .add_sized(toggle_size, toggle)
+                                .on_hover_text(format!("{folded_count} folded lines"))
+                                .clicked()
+                            {
+                                toggle_group = Some(idx);
+                            }
+                        } else {
+                            ui.allocate_exact_size(toggle_size, egui::Sense::hover());
+                        }
+                    }
+                    ui.add(
+                        egui::Button::selectable(is_selected, job)
+                            .min_size(egui::vec2(ui.available_width(), row_height)),
+                    )
+                })
+                .inner;
+            if let Some(stream) = &entry.log_stream_name {
+                response = response.on_hover_text(stream);
+            }
+            if response.clicked() {
+                clicked = Some((idx, ui.input(|i| i.modifiers)));
+            }
+            response.context_menu(|ui| {
+                for format in CopyFormat::ALL {
+                    if ui.button(format!("Copy as {}", format.label())).clicked() {
+                        context_copy = Some((idx, format));
+                        ui.close();
+                    }
+                }
+            });
+            if tab.logs_view.sync_marker == Some(idx) {
+                ui.painter().rect_stroke(
+                    response.rect,
+                    2.0,
+                    egui::Stroke::new(1.5, ui.visuals().warn_fg_color),
+                    egui::StrokeKind::Inside,
+                );
+            }
+            if tab.logs_view.selected_entry == Some(idx) && scroll_to_selected {
+                response.scroll_to_me(Some(egui::Align::Center));
+            }
+        }
+    });
+
+    if let Some(head) = toggle_group {
+        tab.logs_view.toggle_expanded(head);
+    }
+
+    if let Some((idx, modifiers)) = clicked {
+        if modifiers.shift {
+            tab.logs_view.select_range_to(idx);
+        } else if modifiers.command {
+            tab.logs_view.toggle_selected(idx);
+        } else {
+            tab.logs_view.select_single(idx);
+        }
+    }
+
+    if let Some((idx, format)) = context_copy {
+        // Right-clicking outside the selection acts on that row alone.
+        if !tab.logs_view.selection.contains(&idx) {
+            tab.logs_view.select_single(idx);
+        }
+        copy_selection(tab, ui.ctx(), format);
+    }
+
+    let view = &tab.logs_view;
+    match view.selected_entry {
+        Some(idx) if view.selected_entry != selected_before => {
+            view.entries.get(idx).map(|e| e.timestamp_millis)
+        }
+        _ => None,
+    }
+}
+
+/// Completions offered below the query box.
+const MAX_COMPLETIONS: usize = 8;
 
-                ui.separator();
+/// The local query box, with its parse error inline and completion of the
+/// field names seen in the current results (Tab accepts the first one).
+fn draw_query_bar(view: &mut LogsViewState, tab_id: u64, ui: &mut egui::Ui) {
+    let edit_id = ui.make_persistent_id(("local_query", tab_id));
+    let has_focus = ui.memory(|m| m.has_focus(edit_id));
+    let completions: Vec<String> = if has_focus {
+        complete_field(&view.query_text, &view.known_fields)
+            .into_iter()
+            .take(MAX_COMPLETIONS)
+            .map(str::to_string)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut completed = None;
+    if let Some(first) = completions.first()
+        && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Tab))
+    {
+        completed = Some(first.clone());
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Query:");
+        let mut text = view.query_text.clone();
+        let response = ui
+            .add(
+                egui::TextEdit::singleline(&mut text)
+                    .id(edit_id)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text("level:error AND status>=500 AND NOT path:/health")
+                    .desired_width(420.0),
+            )
+            .on_hover_text(
+                "field:value, field>=n (numbers, durations like 250ms), field:a..b, \
+                 field~\"regex\", field:pre*, free text; AND / OR / NOT and parentheses",
+            );
+        if response.changed() {
+            view.set_query(&text);
+        }
+
+        if let Some(err) = &view.query_error {
+            ui.painter().rect_stroke(
+                response.rect,
+                2.0,
+                egui::Stroke::new(1.0, ui.visuals().error_fg_color),
+                egui::StrokeKind::Outside,
+            );
+            ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+        } else if !view.query_text.trim().is_empty() && ui.small_button("Clear").clicked() {
+            view.set_query("");
+        }
+    });
+
+    if !completions.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            ui.weak("Fields (Tab):");
+            for field in &completions {
+                if ui.small_button(field).clicked() {
+                    completed = Some(field.clone());
+                }
             }
         });
+    }
+
+    if let Some(field) = completed {
+        let text = apply_completion(&view.query_text, &field);
+        view.set_query(&text);
+        // Keep typing after the inserted `field:`.
+        let ctx = ui.ctx();
+        if let Some(mut state) = egui::TextEdit::load_state(ctx, edit_id) {
+            let end = egui::text::CCursor::new(text.chars().count());
+            state
+                .cursor
+                .set_char_range(Some(egui::text::CCursorRange::one(end)));
+            state.store(ctx, edit_id);
+        }
+        ctx.memory_mut(|m| m.request_focus(edit_id));
+    }
+}
+
+/// Column names aligned with the cells of the table view.
+fn draw_table_header(
+    view: &LogsViewState,
+    visible: &[usize],
+    columns: &[String],
+    ui: &mut egui::Ui,
+) {
+    let ts_width = visible
+        .first()
+        .map(|&idx| {
+            format_timestamp_millis(view.entries[idx].timestamp_millis, view.show_local_time)
+                .chars()
+                .count()
+        })
+        .unwrap_or_default();
+    let mut header = " ".repeat(ts_width + 3);
+    for column in columns {
+        header.push_str(&table_cell(column, column_width(column)));
+    }
+    header.push_str("message");
+
+    ui.horizontal(|ui| {
+        if view.is_folded() {
+            ui.add_space(18.0 + ui.spacing().item_spacing.x);
+        }
+        // Match the inner margin of the row buttons.
+        ui.add_space(ui.spacing().button_padding.x);
+        ui.add(
+            egui::Label::new(
+                egui::RichText::new(header)
+                    .monospace()
+                    .color(ui.visuals().weak_text_color()),
+            )
+            .truncate(),
+        );
+    });
+}
+
+fn column_width(column: &str) -> usize {
+    column.chars().count().clamp(MIN_CELL_WIDTH, MAX_CELL_WIDTH)
+}
+
+/// `text` padded or cut to `width` characters, plus a separating space.
+fn table_cell(text: &str, width: usize) -> String {
+    let text = text.lines().next().unwrap_or_default();
+    let len = text.chars().count();
+    if len > width {
+        let cut: String = text.chars().take(width - 1).collect();
+        format!("{cut}… ")
+    } else {
+        format!("{text}{} ", " ".repeat(width - len))
+    }
+}
+
+/// Append `text` with its ANSI styling; unstyled runs use `default_color`.
+/// egui has no bold monospace face, so bold is shown in the strong text color.
+fn append_ansi(
+    job: &mut egui::text::LayoutJob,
+    text: &str,
+    base: &egui::TextFormat,
+    default_color: egui::Color32,
+    visuals: &egui::Visuals,
+) {
+    for segment in parse_ansi(text) {
+        let style = segment.style;
+        let color = match style.fg {
+            Some(fg) => fg,
+            None if style.bold => visuals.strong_text_color(),
+            None => default_color,
+        };
+        job.append(
+            &segment.text,
+            0.0,
+            egui::TextFormat {
+                color,
+                background: style.bg.unwrap_or(egui::Color32::TRANSPARENT),
+                italics: style.italic,
+                underline: if style.underline {
+                    egui::Stroke::new(1.0, color)
+                } else {
+                    egui::Stroke::NONE
+                },
+                ..base.clone()
+            },
+        );
+    }
+}
+
+fn copy_selection(tab: &mut Tab, ctx: &egui::Context, format: CopyFormat) {
+    let entries = tab.logs_view.selected_logical_entries();
+    let count = entries.len();
+    let refs: Vec<_> = entries.iter().collect();
+    let text = format_entries(&refs, format, tab.logs_view.show_local_time);
+    ctx.copy_text(text);
+    tab.last_info = Some(format!("Copied {count} entries as {}", format.label()));
 }
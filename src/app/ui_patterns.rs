@@ -0,0 +1,159 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::app::patterns::Template;
+use crate::app::state::{ActiveView, format_timestamp_millis};
+
+/// Patterns listed, most frequent first.
+const MAX_PATTERNS_SHOWN: usize = 200;
+
+/// Sample messages shown per pattern.
+const SAMPLES: usize = 3;
+
+const SPARKLINE_SIZE: egui::Vec2 = egui::vec2(120.0, 16.0);
+
+enum PatternAction {
+    ShowOnly(Template),
+    ToggleHidden(Template),
+}
+
+/// The patterns view: the active tab's messages clustered into templates,
+/// each of which can be shown alone or hidden from the list.
+pub fn draw_patterns_view(app: &mut App, tab_idx: usize, ui: &mut egui::Ui) {
+    let tab = &mut app.tabs[tab_idx];
+    tab.logs_view.patterns();
+    let view = &tab.logs_view;
+    let patterns = view.pattern_cache.as_deref().unwrap_or_default();
+    let filter = &view.pattern_filter;
+    let total = view.entries.len();
+
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "{} patterns in {total} entries of \"{}\"",
+            patterns.len(),
+            tab.title
+        ));
+        if patterns.len() > MAX_PATTERNS_SHOWN {
+            ui.weak(format!("(showing the {MAX_PATTERNS_SHOWN} most frequent)"));
+        }
+        if !filter.hidden.is_empty() {
+            ui.separator();
+            ui.label(format!("{} hidden", filter.hidden.len()));
+        }
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            for (idx, pattern) in patterns.iter().take(MAX_PATTERNS_SHOWN).enumerate() {
+                let hidden = filter.is_hidden(&pattern.template);
+                let only = filter.only.as_ref() == Some(&pattern.template);
+
+                ui.horizontal(|ui| {
+                    ui.add_sized(
+                        [56.0, SPARKLINE_SIZE.y],
+                        egui::Label::new(
+                            egui::RichText::new(pattern.members.len().to_string()).strong(),
+                        ),
+                    );
+                    draw_sparkline(ui, &pattern.sparkline);
+
+                    if ui
+                        .selectable_label(only, "Show only")
+                        .on_hover_text("Filter the list to this pattern")
+                        .clicked()
+                    {
+                        action = Some(PatternAction::ShowOnly(pattern.template.clone()));
+                    }
+                    if ui
+                        .selectable_label(hidden, if hidden { "Hidden" } else { "Hide" })
+                        .on_hover_text("Hide this pattern from the list (noise)")
+                        .clicked()
+                    {
+                        action = Some(PatternAction::ToggleHidden(pattern.template.clone()));
+                    }
+
+                    let mut text = egui::RichText::new(pattern.template.text()).monospace();
+                    if hidden {
+                        text = text.weak().strikethrough();
+                    }
+                    ui.add(egui::Label::new(text).truncate());
+                });
+
+                egui::CollapsingHeader::new("Details")
+                    .id_salt(("pattern_details", idx, pattern.template.text()))
+                    .show(ui, |ui| {
+                        ui.small(format!(
+                            "First seen {} · last seen {}",
+                            format_timestamp_millis(pattern.first_seen, view.show_local_time),
+                            format_timestamp_millis(pattern.last_seen, view.show_local_time)
+                        ));
+                        let mut samples: Vec<&str> = Vec::new();
+                        for &member in &pattern.members {
+                            let first_line = view.entries[member]
+                                .message
+                                .lines()
+                                .next()
+                                .unwrap_or_default();
+                            if !samples.contains(&first_line) {
+                                samples.push(first_line);
+                            }
+                            if samples.len() == SAMPLES {
+                                break;
+                            }
+                        }
+                        for sample in samples {
+                            ui.add(
+                                egui::Label::new(egui::RichText::new(sample).monospace().small())
+                                    .truncate(),
+                            );
+                        }
+                    });
+                ui.separator();
+            }
+        });
+
+    match action {
+        Some(PatternAction::ShowOnly(template)) => {
+            let view = &mut app.tabs[tab_idx].logs_view;
+            view.pattern_filter.only = Some(template);
+            app.view = ActiveView::Logs;
+        }
+        Some(PatternAction::ToggleHidden(template)) => {
+            app.tabs[tab_idx]
+                .logs_view
+                .pattern_filter
+                .toggle_hidden(&template);
+        }
+        None => {}
+    }
+}
+
+/// Member counts over time as a small bar chart.
+fn draw_sparkline(ui: &mut egui::Ui, buckets: &[u32]) {
+    let (rect, _) = ui.allocate_exact_size(SPARKLINE_SIZE, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let max = buckets.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let width = rect.width() / buckets.len().max(1) as f32;
+    let color = ui.visuals().selection.bg_fill;
+    for (i, &count) in buckets.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let height = (count as f32 / max * rect.height()).max(1.0);
+        let left = rect.left() + i as f32 * width;
+        painter.rect_filled(
+            egui::Rect::from_min_max(
+                egui::pos2(left, rect.bottom() - height),
+                egui::pos2(left + width.max(1.0), rect.bottom()),
+            ),
+            0.0,
+            color,
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::app::alerts::AlertRule;
+use crate::app::query::Query;
+
+/// Upper bound of the parallel requests setting.
+const MAX_CONCURRENCY: usize = 16;
+
+pub fn draw_settings_view(app: &mut App, ui: &mut egui::Ui) {
+    ui.heading("Settings");
+    ui.separator();
+
+    let tray_available = app.tray.is_available();
+    ui.add_enabled_ui(tray_available, |ui| {
+        ui.checkbox(
+            &mut app.settings.close_to_tray,
+            "Closing the window hides it to the tray",
+        )
+        .on_disabled_hover_text("No tray icon on this system");
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Parallel requests:");
+        let changed = ui
+            .add(egui::DragValue::new(&mut app.settings.concurrency).range(1..=MAX_CONCURRENCY))
+            .on_hover_text("Fetches, tails and searched log groups run at the same time")
+            .changed();
+        if changed {
+            app.worker.set_concurrency(app.settings.concurrency);
+        }
+    });
+
+    ui.add_space(8.0);
+    draw_alert_rules(app, ui);
+
+    ui.add_space(8.0);
+    ui.label("Favorites (most recently used first):");
+    if app.favorites.items.is_empty() {
+        ui.weak("None yet. Star a query with ☆ next to the log group.");
+        return;
+    }
+
+    let mut remove = None;
+    egui::Grid::new("favorites_grid")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for (idx, favorite) in app.favorites.items.iter().enumerate() {
+                ui.label(&favorite.title);
+                ui.monospace(&favorite.log_group);
+                if ui.small_button("Remove").clicked() {
+                    remove = Some(idx);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(idx) = remove {
+        app.favorites.items.remove(idx);
+    }
+}
+
+enum RuleAction {
+    Edit(usize),
+    Remove(usize),
+    Open(u64),
+    Toggled,
+}
+
+/// Alert rules with their last status, and the editor of the draft rule.
+fn draw_alert_rules(app: &mut App, ui: &mut egui::Ui) {
+    ui.label("Alert rules (checked every minute, also while hidden to the tray):");
+
+    let mut action = None;
+    if !app.alert_rules.is_empty() {
+        egui::Grid::new("alert_rules_grid")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                for (idx, rule) in app.alert_rules.iter_mut().enumerate() {
+                    if ui.checkbox(&mut rule.enabled, &rule.name).changed() {
+                        action = Some(RuleAction::Toggled);
+                    }
+                    ui.monospace(&rule.log_group);
+                    ui.label(rule.describe());
+
+                    match app.alert_status.get(&rule.id) {
+                        _ if !rule.enabled => {
+                            ui.weak("disabled");
+                        }
+                        None => {
+                            ui.weak("pending");
+                        }
+                        Some(status) => {
+                            if let Some(err) = &status.error {
+                                ui.colored_label(ui.visuals().warn_fg_color, "error")
+                                    .on_hover_text(err);
+                            } else {
+                                let response = if status.firing {
+                                    ui.colored_label(
+                                        ui.visuals().error_fg_color,
+                                        format!("firing ({})", status.matches_label()),
+                                    )
+                                } else {
+                                    ui.label(format!("ok ({})", status.matches_label()))
+                                };
+                                if status.capped {
+                                    response.on_hover_text(
+                                        "The fetch limit was reached; there may be more matches",
+                                    );
+                                }
+                            }
+                        }
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button("Open")
+                            .on_hover_text("Show the matching events of the last window")
+                            .clicked()
+                        {
+                            action = Some(RuleAction::Open(rule.id));
+                        }
+                        if ui.small_button("Edit").clicked() {
+                            action = Some(RuleAction::Edit(idx));
+                        }
+                        if ui.small_button("Remove").clicked() {
+                            action = Some(RuleAction::Remove(idx));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+    }
+
+    if app.alert_draft.is_none() && ui.button("New alert from the active tab").clicked() {
+        let id = app.alert_rules.iter().map(|r| r.id + 1).max().unwrap_or(0);
+        let view = &app.active().logs_view;
+        let mut rule = AlertRule::new(id);
+        rule.profile = view.profile.clone();
+        rule.region = view.region.clone();
+        rule.log_group = view.log_group.clone();
+        rule.filter_pattern = view.filter_text.clone();
+        rule.query = view.query_text.clone();
+        rule.parsers = view.parsers.clone();
+        app.alert_draft = Some(rule);
+    }
+
+    let mut changed = false;
+    if let Some(draft) = app.alert_draft.as_mut() {
+        let mut close = false;
+        ui.group(|ui| {
+            egui::Grid::new("alert_draft_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut draft.name);
+                    ui.end_row();
+
+                    ui.label("Profile / region:");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut draft.profile).desired_width(80.0));
+                        ui.add(egui::TextEdit::singleline(&mut draft.region).desired_width(100.0));
+                    });
+                    ui.end_row();
+
+                    ui.label("Log group:");
+                    ui.text_edit_singleline(&mut draft.log_group);
+                    ui.end_row();
+
+                    ui.label("CloudWatch pattern:");
+                    ui.text_edit_singleline(&mut draft.filter_pattern);
+                    ui.end_row();
+
+                    ui.label("Local query:");
+                    ui.vertical(|ui| {
+                        ui.text_edit_singleline(&mut draft.query);
+                        if let Err(err) = Query::parse(&draft.query) {
+                            ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Fire when:");
+                    ui.horizontal(|ui| {
+                        ui.label("more than");
+                        ui.add(egui::DragValue::new(&mut draft.threshold));
+                        ui.label("matches in");
+                        ui.add(egui::DragValue::new(&mut draft.window_minutes).range(1..=1440));
+                        ui.label("minutes");
+                    });
+                    ui.end_row();
+                });
+
+            ui.horizontal(|ui| {
+                let valid =
+                    !draft.log_group.trim().is_empty() && Query::parse(&draft.query).is_ok();
+                if ui.add_enabled(valid, egui::Button::new("Save")).clicked() {
+                    match app.alert_rules.iter_mut().find(|r| r.id == draft.id) {
+                        Some(rule) => *rule = draft.clone(),
+                        None => app.alert_rules.push(draft.clone()),
+                    }
+                    app.alert_status.remove(&draft.id);
+                    changed = true;
+                    close = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    close = true;
+                }
+            });
+        });
+        if close {
+            app.alert_draft = None;
+        }
+    }
+
+    match action {
+        Some(RuleAction::Edit(idx)) => app.alert_draft = Some(app.alert_rules[idx].clone()),
+        Some(RuleAction::Remove(idx)) => {
+            let rule = app.alert_rules.remove(idx);
+            app.alert_status.remove(&rule.id);
+            changed = true;
+        }
+        Some(RuleAction::Open(rule_id)) => app.open_alert(rule_id),
+        Some(RuleAction::Toggled) => changed = true,
+        None => {}
+    }
+    if changed {
+        app.sync_alert_rules();
+    }
+}
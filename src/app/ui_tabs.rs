@@ -0,0 +1,222 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::app::state::SplitView;
+use crate::app::tab::move_item;
+
+enum TabAction {
+    Activate(usize),
+    StartRename(usize),
+    Duplicate(usize),
+    OpenBeside(usize),
+    Close(usize),
+    Move { from: usize, to: usize },
+}
+
+/// The tab strip: click to switch, double-click to rename, drag to reorder,
+/// right-click for more.
+pub fn draw_tab_strip(app: &mut App, ui: &mut egui::Ui) {
+    let mut action = None;
+    // Some(true) to apply the edited title, Some(false) to discard it.
+    let mut rename_done = None;
+    let tab_count = app.tabs.len();
+
+    ui.horizontal(|ui| {
+        egui::ScrollArea::horizontal()
+            .id_salt("tab_strip")
+            .auto_shrink([false, true])
+            .max_width(ui.available_width() - 30.0)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for (idx, tab) in app.tabs.iter().enumerate() {
+                        if let Some((id, name)) = app.renaming_tab.as_mut()
+                            && *id == tab.id
+                        {
+                            let edit = ui.add(
+                                egui::TextEdit::singleline(name)
+                                    .desired_width(120.0)
+                                    .id_salt(("tab_rename", tab.id)),
+                            );
+                            if !edit.has_focus() && !edit.lost_focus() {
+                                edit.request_focus();
+                            }
+                            if edit.lost_focus() {
+                                rename_done = Some(!ui.input(|i| i.key_pressed(egui::Key::Escape)));
+                            }
+                            continue;
+                        }
+
+                        let mut title = tab.title.clone();
+                        if tab.is_busy() {
+                            title.push_str(" ⟳");
+                        } else if tab.logs_view.tail_mode {
+                            title.push_str(" ●");
+                        }
+                        if tab.last_error.is_some() {
+                            title.push_str(" ⚠");
+                        }
+
+                        let inner = ui.dnd_drag_source(egui::Id::new(("tab", tab.id)), idx, |ui| {
+                            ui.selectable_label(idx == app.active_tab, title)
+                        });
+                        let label = inner.inner;
+                        let response = inner.response;
+
+                        if label.double_clicked() {
+                            action = Some(TabAction::StartRename(idx));
+                        } else if label.clicked() {
+                            action = Some(TabAction::Activate(idx));
+                        }
+                        if let Some(from) = response.dnd_release_payload::<usize>() {
+                            action = Some(TabAction::Move {
+                                from: *from,
+                                to: idx,
+                            });
+                        }
+
+                        label
+                            .on_hover_text(if tab.logs_view.log_group.is_empty() {
+                                "<no log group>"
+                            } else {
+                                tab.logs_view.log_group.as_str()
+                            })
+                            .context_menu(|ui| {
+                                if ui.button("Rename").clicked() {
+                                    action = Some(TabAction::StartRename(idx));
+                                    ui.close();
+                                }
+                                if ui.button("Duplicate").clicked() {
+                                    action = Some(TabAction::Duplicate(idx));
+                                    ui.close();
+                                }
+                                if ui
+                                    .add_enabled(
+                                        idx != app.active_tab,
+                                        egui::Button::new("Open beside active tab"),
+                                    )
+                                    .clicked()
+                                {
+                                    action = Some(TabAction::OpenBeside(idx));
+                                    ui.close();
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_enabled(idx > 0, egui::Button::new("Move left"))
+                                    .clicked()
+                                {
+                                    action = Some(TabAction::Move {
+                                        from: idx,
+                                        to: idx - 1,
+                                    });
+                                    ui.close();
+                                }
+                                if ui
+                                    .add_enabled(
+                                        idx + 1 < tab_count,
+                                        egui::Button::new("Move right"),
+                                    )
+                                    .clicked()
+                                {
+                                    action = Some(TabAction::Move {
+                                        from: idx,
+                                        to: idx + 1,
+                                    });
+                                    ui.close();
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_enabled(tab_count > 1, egui::Button::new("Close"))
+                                    .clicked()
+                                {
+                                    action = Some(TabAction::Close(idx));
+                                    ui.close();
+                                }
+                            });
+
+                        if tab_count > 1
+                            && ui.small_button("✕").on_hover_text("Close tab").clicked()
+                        {
+                            action = Some(TabAction::Close(idx));
+                        }
+                        ui.separator();
+                    }
+                });
+            });
+
+        if ui.button("+").on_hover_text("New tab").clicked() {
+            app.new_tab();
+        }
+
+        ui.menu_button("Split", |ui| {
+            let active_id = app.active().id;
+            let current = app.split.filter(|_| app.split_pane_index().is_some());
+            for tab in app.tabs.iter().filter(|t| t.id != active_id) {
+                let checked = current.is_some_and(|s| s.other_tab == tab.id);
+                if ui
+                    .selectable_label(checked, format!("Beside: {}", tab.title))
+                    .clicked()
+                {
+                    app.split = Some(SplitView {
+                        other_tab: tab.id,
+                        sync_time: current.is_none_or(|s| s.sync_time),
+                        focus_right: false,
+                    });
+                    ui.close();
+                }
+            }
+            if app.tabs.len() < 2 {
+                ui.label("Open another tab to compare side by side.");
+            }
+            if let Some(split) = app.split.as_mut()
+                && current.is_some()
+            {
+                ui.separator();
+                ui.checkbox(&mut split.sync_time, "Sync selection by time");
+                if ui.button("Close split").clicked() {
+                    app.split = None;
+                    ui.close();
+                }
+            }
+        });
+    });
+
+    if let Some(commit) = rename_done
+        && let Some((id, name)) = app.renaming_tab.take()
+    {
+        let name = name.trim();
+        if let Some(tab) = app.tabs.iter_mut().find(|t| t.id == id)
+            && commit
+            && !name.is_empty()
+        {
+            tab.title = name.to_string();
+        }
+    }
+
+    match action {
+        Some(TabAction::Activate(idx)) => app.active_tab = idx,
+        Some(TabAction::StartRename(idx)) => {
+            let tab = &app.tabs[idx];
+            app.renaming_tab = Some((tab.id, tab.title.clone()));
+        }
+        Some(TabAction::Duplicate(idx)) => {
+            let tab = app.tabs[idx].duplicate(app.next_tab_id);
+            app.next_tab_id += 1;
+            app.tabs.insert(idx + 1, tab);
+            app.active_tab = idx + 1;
+        }
+        Some(TabAction::OpenBeside(idx)) => {
+            app.split = Some(SplitView {
+                other_tab: app.tabs[idx].id,
+                sync_time: true,
+                focus_right: false,
+            });
+        }
+        Some(TabAction::Close(idx)) => app.close_tab(idx),
+        Some(TabAction::Move { from, to }) => {
+            let active_id = app.tabs[app.active_tab].id;
+            move_item(&mut app.tabs, from, to);
+            app.active_tab = app.tab_index(active_id).unwrap_or(0);
+        }
+        None => {}
+    }
+}
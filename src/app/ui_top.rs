@@ -1,5 +1,8 @@
-use crate::app::App;
 use crate::app::state::{ActiveView, Theme};
+use crate::app::tab::DEFAULT_LOOKBACK;
+use crate::app::{App, ui_tabs};
+use crate::launch::LaunchRequest;
+use crate::local_logs::{DEFAULT_TIMESTAMP_REGEX, LocalFormat, local_path_from_group};
 use eframe::egui;
 
 pub fn draw_top_bar(app: &mut App, ctx: &egui::Context) {
@@ -11,6 +14,8 @@ pub fn draw_top_bar(app: &mut App, ctx: &egui::Context) {
             ui.separator();
 
             ui.selectable_value(&mut app.view, ActiveView::Logs, "Logs");
+            ui.selectable_value(&mut app.view, ActiveView::Patterns, "Patterns");
+            ui.selectable_value(&mut app.view, ActiveView::Settings, "Settings");
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.button("✕").clicked() {
@@ -37,25 +42,42 @@ pub fn draw_top_bar(app: &mut App, ctx: &egui::Context) {
 
         ui.separator();
 
+        ui_tabs::draw_tab_strip(app, ui);
+
+        ui.separator();
+
+        // The remaining rows edit the active tab's query.
+        let App {
+            tabs,
+            active_tab,
+            worker,
+            favorites,
+            ..
+        } = &mut *app;
+        let tab = &mut tabs[*active_tab];
+
         // Second row: AWS settings.
         ui.horizontal(|ui| {
             ui.label("Profile:");
-            ui.add(egui::TextEdit::singleline(&mut app.logs_view.profile).desired_width(80.0));
+            ui.add(egui::TextEdit::singleline(&mut tab.logs_view.profile).desired_width(80.0));
 
             ui.separator();
 
             ui.label("Region:");
-            ui.add(egui::TextEdit::singleline(&mut app.logs_view.region).desired_width(100.0));
+            ui.add(egui::TextEdit::singleline(&mut tab.logs_view.region).desired_width(100.0));
 
             ui.separator();
 
-            let load_btn = ui.add_enabled(!app.is_loading_groups, egui::Button::new("Load groups"));
+            let load_btn = ui.add_enabled(!tab.is_loading_groups, egui::Button::new("Load groups"));
             if load_btn.clicked() {
-                app.start_load_log_groups();
+                tab.start_load_log_groups(worker);
             }
 
-            if app.is_loading_groups {
+            if tab.is_loading_groups {
                 ui.spinner();
+                if ui.small_button("Cancel").clicked() {
+                    tab.cancel_load_groups(worker);
+                }
             }
         });
 
@@ -63,12 +85,12 @@ pub fn draw_top_bar(app: &mut App, ctx: &egui::Context) {
         ui.horizontal(|ui| {
             ui.label("Group:");
 
-            let current_group_name = app
+            let current_group_name = tab
                 .logs_view
                 .selected_group_index
-                .and_then(|idx| app.logs_view.available_groups.get(idx))
+                .and_then(|idx| tab.logs_view.available_groups.get(idx))
                 .cloned()
-                .unwrap_or_else(|| app.logs_view.log_group.clone());
+                .unwrap_or_else(|| tab.logs_view.log_group.clone());
 
             egui::ComboBox::from_id_salt("log_group_combo")
                 .selected_text(if current_group_name.is_empty() {
@@ -77,24 +99,81 @@ pub fn draw_top_bar(app: &mut App, ctx: &egui::Context) {
                     current_group_name.as_str()
                 })
                 .show_ui(ui, |ui| {
-                    for (idx, name) in app.logs_view.available_groups.iter().enumerate() {
-                        let selected = Some(idx) == app.logs_view.selected_group_index;
+                    for (idx, name) in tab.logs_view.available_groups.iter().enumerate() {
+                        let selected = Some(idx) == tab.logs_view.selected_group_index;
                         if ui.selectable_label(selected, name).clicked() {
-                            app.logs_view.selected_group_index = Some(idx);
-                            app.logs_view.log_group = name.clone();
+                            tab.logs_view.selected_group_index = Some(idx);
+                            tab.logs_view.log_group = name.clone();
                         }
                     }
                 });
 
+            if ui.button("Open file…").clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Logs", &["log", "txt", "json", "jsonl", "gz"])
+                    .add_filter("All files", &["*"])
+                    .pick_file()
+            {
+                tab.open_local_file(worker, path);
+            }
+
+            let config = tab.to_config();
+            let starred = favorites.contains(&config);
+            if ui
+                .selectable_label(starred, if starred { "★" } else { "☆" })
+                .on_hover_text("Favorite this query (offered in the tray menu)")
+                .clicked()
+            {
+                favorites.toggle(config);
+            }
+            if ui
+                .button("🔗")
+                .on_hover_text("Copy an axe:// link opening this query")
+                .clicked()
+            {
+                ui.ctx().copy_text(LaunchRequest::for_tab(tab).to_url());
+            }
+
+            ui.menu_button("File options", |ui| {
+                let options = &mut tab.logs_view.local_file_options;
+                egui::ComboBox::from_label("Format")
+                    .selected_text(options.format.label())
+                    .show_ui(ui, |ui| {
+                        for format in LocalFormat::ALL {
+                            ui.selectable_value(&mut options.format, format, format.label());
+                        }
+                    });
+                ui.label("Plain text timestamp prefix (regex):");
+                ui.add(
+                    egui::TextEdit::singleline(&mut options.timestamp_regex)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(320.0),
+                );
+                if ui.button("Reset regex").clicked() {
+                    options.timestamp_regex = DEFAULT_TIMESTAMP_REGEX.to_string();
+                }
+            });
+
             ui.separator();
 
-            let fetch_btn = ui.add_enabled(!app.is_fetching, egui::Button::new("Fetch last 5m"));
-            if fetch_btn.clicked() {
-                app.start_fetch_logs(std::time::Duration::from_secs(5 * 60));
+            let is_local = local_path_from_group(&tab.logs_view.log_group).is_some();
+            let fetch_label = if is_local {
+                "Reload file"
+            } else {
+                "Fetch last 5m"
+            };
+            // A new fetch supersedes the one in flight.
+            if ui.button(fetch_label).clicked() {
+                tab.logs_view.fetch_range = None;
+                tab.logs_view.time_window = None;
+                tab.start_fetch_logs(worker, DEFAULT_LOOKBACK);
             }
 
-            if app.is_fetching {
+            if tab.is_fetching {
                 ui.spinner();
+                if ui.small_button("Cancel").clicked() {
+                    tab.cancel_fetch(worker);
+                }
             }
         });
     });
@@ -0,0 +1,211 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::app::level::detect_level;
+use crate::app::state::format_timestamp_millis;
+
+/// The trace window: one request / trace ID searched across log groups and
+/// shown as a single time-ordered list.
+pub fn draw_trace_window(app: &mut App, ctx: &egui::Context) {
+    if !app.trace.open {
+        return;
+    }
+
+    let theme = app.theme;
+    let use_local = app.active().logs_view.show_local_time;
+    let mut open = true;
+    let mut search = false;
+    let mut cancel = false;
+
+    egui::Window::new("Trace")
+        .open(&mut open)
+        .default_size([640.0, 480.0])
+        .show(ctx, |ui| {
+            let trace = &mut app.trace;
+
+            ui.horizontal(|ui| {
+                ui.label("ID:");
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut trace.term)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(320.0),
+                );
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    search = true;
+                }
+                ui.add_enabled_ui(!trace.suggestions.is_empty(), |ui| {
+                    ui.menu_button("Suggestions", |ui| {
+                        for id in &trace.suggestions {
+                            if ui.button(format!("{}: {}", id.source, id.value)).clicked() {
+                                trace.term = id.value.clone();
+                                ui.close();
+                            }
+                        }
+                    });
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Window ± minutes:");
+                ui.add(egui::DragValue::new(&mut trace.window_mins).range(1..=24 * 60));
+                let (start, end) = trace.range();
+                ui.small(format!(
+                    "{} – {}",
+                    format_timestamp_millis(start, use_local),
+                    format_timestamp_millis(end, use_local)
+                ));
+            });
+
+            egui::CollapsingHeader::new(format!(
+                "Log groups ({} selected)",
+                trace.selected_groups.len()
+            ))
+            .id_salt("trace_groups")
+            .default_open(trace.candidate_groups.len() > 1)
+            .show(ui, |ui| {
+                if trace.candidate_groups.len() < 2 {
+                    ui.weak("Use \"Load groups\" to search more than this entry's group.");
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(140.0)
+                    .show(ui, |ui| {
+                        for group in &trace.candidate_groups {
+                            let mut checked = trace.selected_groups.contains(group);
+                            if ui.checkbox(&mut checked, group).changed() {
+                                if checked {
+                                    trace.selected_groups.insert(group.clone());
+                                } else {
+                                    trace.selected_groups.remove(group);
+                                }
+                            }
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!trace.is_searching, egui::Button::new("Search"))
+                    .clicked()
+                {
+                    search = true;
+                }
+                if trace.is_searching {
+                    ui.spinner();
+                    if let Some(progress) = &trace.progress {
+                        ui.label(progress.describe(std::time::Instant::now()));
+                    }
+                    if ui.small_button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                } else if let Some(err) = &trace.last_error {
+                    ui.colored_label(egui::Color32::RED, err);
+                } else {
+                    if !trace.entries.is_empty() {
+                        let groups: std::collections::BTreeSet<_> = trace
+                            .entries
+                            .iter()
+                            .filter_map(|e| e.log_group_name.as_deref())
+                            .collect();
+                        ui.label(format!(
+                            "{} events across {} log groups",
+                            trace.entries.len(),
+                            groups.len()
+                        ));
+                    }
+                    if !trace.failures.is_empty() {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            format!("{} log groups failed", trace.failures.len()),
+                        )
+                        .on_hover_text(trace.failures.join("\n"));
+                    }
+                }
+            });
+
+            ui.separator();
+
+            let row_height = ui.text_style_height(&egui::TextStyle::Monospace) + 4.0;
+            let mut clicked = None;
+            egui::ScrollArea::vertical()
+                .id_salt("trace_results")
+                .max_height(ui.available_height() * 0.6)
+                .auto_shrink([false, true])
+                .show_rows(ui, row_height, trace.entries.len(), |ui, rows| {
+                    for idx in rows {
+                        let entry = &trace.entries[idx];
+                        let mut job = egui::text::LayoutJob::default();
+                        let mono = egui::TextFormat {
+                            font_id: egui::TextStyle::Monospace.resolve(ui.style()),
+                            color: egui::Color32::LIGHT_BLUE,
+                            ..Default::default()
+                        };
+                        job.append(
+                            &format!(
+                                "[{}] ",
+                                format_timestamp_millis(entry.timestamp_millis, use_local)
+                            ),
+                            0.0,
+                            mono.clone(),
+                        );
+                        job.append(
+                            &format!("{} ", entry.log_group_name.as_deref().unwrap_or("-")),
+                            0.0,
+                            egui::TextFormat {
+                                color: ui.visuals().weak_text_color(),
+                                ..mono.clone()
+                            },
+                        );
+                        job.append(
+                            entry.message.lines().next().unwrap_or_default(),
+                            0.0,
+                            egui::TextFormat {
+                                color: detect_level(&entry.message).color(theme),
+                                ..mono
+                            },
+                        );
+                        job.wrap.max_rows = 1;
+                        job.wrap.break_anywhere = true;
+
+                        let selected = trace.selected_entry == Some(idx);
+                        if ui
+                            .add(
+                                egui::Button::selectable(selected, job)
+                                    .min_size(egui::vec2(ui.available_width(), row_height)),
+                            )
+                            .clicked()
+                        {
+                            clicked = Some(idx);
+                        }
+                    }
+                });
+            if clicked.is_some() {
+                trace.selected_entry = clicked;
+            }
+
+            if let Some(entry) = trace.selected_entry.and_then(|idx| trace.entries.get(idx)) {
+                ui.separator();
+                ui.small(format!(
+                    "{} · {}",
+                    entry.log_group_name.as_deref().unwrap_or("-"),
+                    entry.log_stream_name.as_deref().unwrap_or("-")
+                ));
+                egui::ScrollArea::vertical()
+                    .id_salt("trace_message")
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut entry.message.as_str())
+                                .font(egui::TextStyle::Monospace)
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+            }
+        });
+
+    if search {
+        app.trace.start_search(&app.worker);
+    }
+    if cancel || !open {
+        app.trace.cancel(&app.worker);
+    }
+    app.trace.open = open;
+}
@@ -0,0 +1,659 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{Receiver, Sender};
+
+use eframe::egui;
+
+use crate::app::alerts::{AlertEvent, AlertRule, AlertStatus};
+use crate::app::correlation::TraceState;
+use crate::app::export::{CsvColumn, ExportError};
+use crate::app::favorites::{Favorites, TRAY_FAVORITES};
+use crate::app::state::{ActiveView, ExportDialogState, ExportScope, Settings, SplitView, Theme};
+use crate::app::tab::{DEFAULT_LOOKBACK, SavedTabs, Tab};
+use crate::app::{
+    detail_panel, status_bar, ui_export, ui_logs, ui_patterns, ui_settings, ui_top, ui_trace,
+};
+use crate::aws::LogEntry;
+use crate::instance::LaunchReceiver;
+use crate::launch::{LaunchRange, LaunchRequest};
+use crate::tray::{TailState, TrayEvent, TrayEventReceiver, TrayHandle, TrayIconKind, TrayState};
+use crate::worker::{WorkerHandle, WorkerRequest};
+
+/// Storage key of the persisted tabs.
+const TABS_KEY: &str = "tabs";
+const FAVORITES_KEY: &str = "favorites";
+const SETTINGS_KEY: &str = "settings";
+const ALERTS_KEY: &str = "alerts";
+
+/// Errors of the tailing tabs are counted over this window in the tray
+/// tooltip.
+const TRAY_ERROR_WINDOW: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+pub struct App {
+    pub(crate) view: ActiveView,
+    pub(crate) tabs: Vec<Tab>,
+    pub(crate) active_tab: usize,
+    pub(crate) next_tab_id: u64,
+    /// Tab being renamed in the tab strip, with the edited title.
+    pub(crate) renaming_tab: Option<(u64, String)>,
+    pub(crate) split: Option<SplitView>,
+    pub(crate) should_close: bool,
+    pub(crate) worker: WorkerHandle,
+    pub(crate) theme: Theme,
+    pub(crate) export_dialog: ExportDialogState,
+    pub(crate) export_rx: Option<std::sync::mpsc::Receiver<Result<usize, ExportError>>>,
+    pub(crate) trace: TraceState,
+    pub(crate) settings: Settings,
+    pub(crate) favorites: Favorites,
+    pub(crate) tray: TrayHandle,
+    tray_events: TrayEventReceiver,
+    window_visible: bool,
+    /// Set by Quit in the tray: the next close request is not hidden to
+    /// the tray.
+    quitting: bool,
+    /// Tabs whose tail was paused from the tray menu.
+    paused_tails: Vec<u64>,
+    pub(crate) alert_rules: Vec<AlertRule>,
+    /// Last evaluation of each rule, by rule ID.
+    pub(crate) alert_status: HashMap<u64, AlertStatus>,
+    /// Rule being added or edited in the settings.
+    pub(crate) alert_draft: Option<AlertRule>,
+    alert_tx: Sender<AlertEvent>,
+    alert_rx: Receiver<AlertEvent>,
+    /// Queries to open from the command line and later launches.
+    launches: LaunchReceiver,
+}
+
+impl App {
+    pub fn new(
+        cc: &eframe::CreationContext<'_>,
+        worker: WorkerHandle,
+        tray: TrayHandle,
+        tray_events: TrayEventReceiver,
+        launches: LaunchReceiver,
+    ) -> Self {
+        let saved: SavedTabs = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, TABS_KEY))
+            .unwrap_or_default();
+        let favorites: Favorites = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, FAVORITES_KEY))
+            .unwrap_or_default();
+        let settings: Settings = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, SETTINGS_KEY))
+            .unwrap_or_default();
+        let alert_rules: Vec<AlertRule> = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, ALERTS_KEY))
+            .unwrap_or_default();
+        tray.set_repaint_context(&cc.egui_ctx);
+        launches.set_repaint_context(&cc.egui_ctx);
+        worker.set_repaint_context(&cc.egui_ctx);
+        worker.set_concurrency(settings.concurrency.max(1));
+        let (alert_tx, alert_rx) = std::sync::mpsc::channel();
+
+        let mut tabs: Vec<Tab> = saved
+            .tabs
+            .into_iter()
+            .enumerate()
+            .map(|(idx, config)| Tab::from_config(idx as u64, config))
+            .collect();
+        if tabs.is_empty() {
+            tabs.push(Tab::new(0, "Tab 1"));
+        }
+        let active_tab = saved.active.min(tabs.len() - 1);
+        let next_tab_id = tabs.len() as u64;
+
+        let app = Self {
+            view: ActiveView::Logs,
+            tabs,
+            active_tab,
+            next_tab_id,
+            renaming_tab: None,
+            split: None,
+            should_close: false,
+            worker,
+            theme: Theme::Dark,
+            export_dialog: ExportDialogState::default(),
+            export_rx: None,
+            trace: TraceState::default(),
+            settings,
+            favorites,
+            tray,
+            tray_events,
+            window_visible: true,
+            quitting: false,
+            paused_tails: Vec::new(),
+            alert_rules,
+            alert_status: HashMap::new(),
+            alert_draft: None,
+            alert_tx,
+            alert_rx,
+            launches,
+        };
+        app.sync_alert_rules();
+        app
+    }
+
+    pub(crate) fn active(&self) -> &Tab {
+        &self.tabs[self.active_tab]
+    }
+
+    pub(crate) fn tab_index(&self, id: u64) -> Option<usize> {
+        self.tabs.iter().position(|t| t.id == id)
+    }
+
+    /// Index of the tab shown in the right split pane, if split.
+    pub(crate) fn split_pane_index(&self) -> Option<usize> {
+        let split = self.split?;
+        self.tab_index(split.other_tab)
+            .filter(|&idx| idx != self.active_tab)
+    }
+
+    /// The tab that has keyboard focus: the right split pane or the active tab.
+    pub(crate) fn focused_tab_index(&self) -> usize {
+        match (self.split, self.split_pane_index()) {
+            (Some(split), Some(idx)) if split.focus_right => idx,
+            _ => self.active_tab,
+        }
+    }
+
+    /// Open a new tab on the same profile and region as the active one.
+    pub(crate) fn new_tab(&mut self) {
+        let id = self.next_tab_id;
+        self.next_tab_id += 1;
+
+        let mut tab = Tab::new(id, format!("Tab {}", self.tabs.len() + 1));
+        tab.logs_view.profile = self.active().logs_view.profile.clone();
+        tab.logs_view.region = self.active().logs_view.region.clone();
+        self.tabs.push(tab);
+        self.active_tab = self.tabs.len() - 1;
+    }
+
+    /// Close a tab, dropping its pending requests. The last tab stays open.
+    pub(crate) fn close_tab(&mut self, idx: usize) {
+        if self.tabs.len() <= 1 || idx >= self.tabs.len() {
+            return;
+        }
+        let mut tab = self.tabs.remove(idx);
+        tab.cancel_fetch(&self.worker);
+        tab.cancel_load_groups(&self.worker);
+        tab.stop_tail(&self.worker);
+        if self.active_tab > idx || self.active_tab >= self.tabs.len() {
+            self.active_tab = self.active_tab.saturating_sub(1);
+        }
+    }
+
+    /// Open a favorite in a new tab and fetch it.
+    fn open_favorite(&mut self, idx: usize) {
+        let Some(config) = self.favorites.touch(idx) else {
+            return;
+        };
+        let id = self.next_tab_id;
+        self.next_tab_id += 1;
+
+        let mut tab = Tab::from_config(id, config);
+        if !tab.logs_view.log_group.is_empty() {
+            tab.start_fetch_logs(&self.worker, DEFAULT_LOOKBACK);
+        }
+        self.tabs.push(tab);
+        self.active_tab = self.tabs.len() - 1;
+        self.view = ActiveView::Logs;
+    }
+
+    /// Hand the alert rules to the worker, which evaluates them in the
+    /// background.
+    pub(crate) fn sync_alert_rules(&self) {
+        self.worker.send(WorkerRequest::SetAlertRules {
+            rules: self.alert_rules.clone(),
+            events: self.alert_tx.clone(),
+        });
+    }
+
+    /// Open the events that made a rule fire in a new tab.
+    pub(crate) fn open_alert(&mut self, rule_id: u64) {
+        let Some(rule) = self.alert_rules.iter().find(|r| r.id == rule_id) else {
+            return;
+        };
+        let id = self.next_tab_id;
+        self.next_tab_id += 1;
+
+        let mut tab = Tab::new(id, rule.name.clone());
+        let view = &mut tab.logs_view;
+        view.profile = rule.profile.clone();
+        view.region = rule.region.clone();
+        view.log_group = rule.log_group.clone();
+        view.filter_text = rule.filter_pattern.clone();
+        view.set_query(&rule.query);
+        view.parsers = rule.parsers.clone();
+        view.fetch_range = self.alert_status.get(&rule_id).map(|s| s.range);
+        tab.start_fetch_logs(&self.worker, rule.window());
+
+        self.tabs.push(tab);
+        self.active_tab = self.tabs.len() - 1;
+        self.view = ActiveView::Logs;
+    }
+
+    /// Open each launch request in a new tab and bring the window up.
+    fn handle_launches(&mut self, ctx: &egui::Context) {
+        while let Some(request) = self.launches.try_recv() {
+            self.open_launch(request);
+            self.set_window_visible(ctx, true);
+        }
+    }
+
+    fn open_launch(&mut self, request: LaunchRequest) {
+        let id = self.next_tab_id;
+        self.next_tab_id += 1;
+
+        let title = [&request.title, &request.group]
+            .into_iter()
+            .find(|s| !s.is_empty())
+            .cloned()
+            .unwrap_or_else(|| format!("Tab {}", self.tabs.len() + 1));
+        let range = request.range(chrono::Utc::now().timestamp_millis());
+        let mut tab = Tab::new(id, title);
+        let active = &self.active().logs_view;
+        let view = &mut tab.logs_view;
+        view.profile = Some(request.profile)
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| active.profile.clone());
+        view.region = Some(request.region)
+            .filter(|r| !r.is_empty())
+            .unwrap_or_else(|| active.region.clone());
+        view.log_group = request.group;
+        view.filter_text = request.filter;
+        view.set_query(&request.query);
+        view.tail_mode = request.tail;
+
+        let lookback = match range {
+            Ok(Some(LaunchRange::Lookback(lookback))) => Some(lookback),
+            Ok(Some(LaunchRange::Absolute(start, end))) => {
+                view.fetch_range = Some((start, end));
+                Some(DEFAULT_LOOKBACK)
+            }
+            Ok(None) => Some(DEFAULT_LOOKBACK),
+            Err(e) => {
+                tab.logs_view.tail_mode = false;
+                tab.last_error = Some(e.to_string());
+                None
+            }
+        };
+        if let Some(lookback) = lookback
+            && !tab.logs_view.log_group.is_empty()
+        {
+            tab.start_fetch_logs(&self.worker, lookback);
+        }
+
+        self.tabs.push(tab);
+        self.active_tab = self.tabs.len() - 1;
+        self.view = ActiveView::Logs;
+    }
+
+    fn poll_alerts(&mut self, ctx: &egui::Context) {
+        while let Ok(event) = self.alert_rx.try_recv() {
+            match event {
+                AlertEvent::Status(status) => {
+                    self.alert_status.insert(status.rule_id, status);
+                }
+                AlertEvent::Open(rule_id) => {
+                    self.open_alert(rule_id);
+                    self.set_window_visible(ctx, true);
+                }
+            }
+        }
+    }
+
+    /// Tray icon and status line: firing alerts first, then failed
+    /// requests, then tailing.
+    fn tray_status(&self) -> (TrayIconKind, Option<String>) {
+        let firing: Vec<&str> = self
+            .alert_rules
+            .iter()
+            .filter(|r| r.enabled)
+            .filter(|r| self.alert_status.get(&r.id).is_some_and(|s| s.firing))
+            .map(|r| r.name.as_str())
+            .collect();
+        if !firing.is_empty() {
+            let status = format!(
+                "{} firing: {}",
+                plural(firing.len(), "alert"),
+                firing.join(", ")
+            );
+            return (TrayIconKind::Alert(firing.len()), Some(status));
+        }
+
+        if let Some(tab) = self.tabs.iter().find(|t| t.last_error.is_some()) {
+            return (
+                TrayIconKind::Error,
+                Some(format!("Request failed in {}", tab.title)),
+            );
+        }
+
+        let tailing: Vec<&Tab> = self.tabs.iter().filter(|t| t.logs_view.tail_mode).collect();
+        let source = match tailing.as_slice() {
+            [] => return (TrayIconKind::Idle, None),
+            [tab] => tab.logs_view.log_group.clone(),
+            tabs => format!("{} groups", tabs.len()),
+        };
+        let since = chrono::Utc::now().timestamp_millis() - TRAY_ERROR_WINDOW.as_millis() as i64;
+        let errors = tailing
+            .iter()
+            .map(|t| t.logs_view.errors_since(since))
+            .sum();
+        let status = format!(
+            "Tailing {source} \u{2014} {} in last {}m",
+            plural(errors, "error"),
+            TRAY_ERROR_WINDOW.as_secs() / 60
+        );
+        (TrayIconKind::Tailing, Some(status))
+    }
+
+    fn tail_state(&self) -> TailState {
+        if !self.paused_tails.is_empty() {
+            TailState::Paused
+        } else if self.tabs.iter().any(|t| t.logs_view.tail_mode) {
+            TailState::Tailing
+        } else {
+            TailState::Idle
+        }
+    }
+
+    /// Pause every tailing tab, or resume the tabs paused that way.
+    fn toggle_tail(&mut self) {
+        if self.paused_tails.is_empty() {
+            for tab in self.tabs.iter_mut().filter(|t| t.logs_view.tail_mode) {
+                tab.logs_view.tail_mode = false;
+                self.paused_tails.push(tab.id);
+            }
+        } else {
+            for id in std::mem::take(&mut self.paused_tails) {
+                if let Some(idx) = self.tab_index(id) {
+                    self.tabs[idx].logs_view.tail_mode = true;
+                }
+            }
+        }
+    }
+
+    /// Hide the window to the tray, or bring it back.
+    ///
+    /// Tray events are handled in `update()`, and on Windows a hidden window
+    /// gets no frames even when a repaint is requested, so it could never be
+    /// shown again; there the window is minimized instead.
+    fn set_window_visible(&mut self, ctx: &egui::Context, visible: bool) {
+        self.window_visible = visible;
+        if cfg!(windows) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(!visible));
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(visible));
+        }
+        if visible {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
+    }
+
+    fn handle_tray_events(&mut self, ctx: &egui::Context) {
+        while let Some(event) = self.tray_events.try_recv() {
+            match event {
+                TrayEvent::ToggleWindow => self.set_window_visible(ctx, !self.window_visible),
+                TrayEvent::ToggleTail => self.toggle_tail(),
+                TrayEvent::OpenFavorite(idx) => {
+                    self.open_favorite(idx);
+                    self.set_window_visible(ctx, true);
+                }
+                TrayEvent::QuitRequested => {
+                    self.quitting = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+    }
+
+    /// Hide to the tray instead of closing, when configured and possible.
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        if self.should_close {
+            self.should_close = false;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+        if ctx.input(|i| i.viewport().close_requested())
+            && !self.quitting
+            && self.settings.close_to_tray
+            && self.tray.is_available()
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.set_window_visible(ctx, false);
+        }
+    }
+
+    /// Browse a local file as a pseudo log group in the active tab.
+    fn open_local_file(&mut self, path: std::path::PathBuf) {
+        let worker = &self.worker;
+        self.tabs[self.active_tab].open_local_file(worker, path);
+    }
+
+    /// Two tabs side by side. Selecting an entry in one pane gives it keyboard
+    /// focus and, with time sync on, marks the closest entry in the other.
+    fn draw_split(&mut self, ui: &mut egui::Ui, left: usize, right: usize) {
+        let Some(split) = self.split else {
+            return;
+        };
+
+        let mut picked = [None, None];
+        ui.columns(2, |columns| {
+            for (pane, (tab_idx, column)) in [left, right].into_iter().zip(columns).enumerate() {
+                let has_keyboard = split.focus_right == (pane == 1);
+                column.push_id(("split_pane", pane), |ui| {
+                    ui.horizontal(|ui| {
+                        let title = egui::RichText::new(&self.tabs[tab_idx].title).strong();
+                        if has_keyboard {
+                            ui.label(title.underline());
+                        } else {
+                            ui.label(title);
+                        }
+                    });
+                    picked[pane] = ui_logs::draw_logs_view(self, tab_idx, has_keyboard, ui);
+                });
+            }
+        });
+
+        for (pane, ts) in picked.into_iter().enumerate() {
+            let Some(ts) = ts else {
+                continue;
+            };
+            if let Some(split) = self.split.as_mut() {
+                split.focus_right = pane == 1;
+            }
+            if split.sync_time {
+                let other = if pane == 0 { right } else { left };
+                self.tabs[other].logs_view.mark_nearest(ts);
+            }
+        }
+    }
+
+    /// Open the trace window for `term` around the selected entry of a tab.
+    pub(crate) fn open_trace(&mut self, tab_idx: usize, term: String) {
+        let tab = &self.tabs[tab_idx];
+        let Some(entry) = tab.logs_view.selected() else {
+            return;
+        };
+        self.trace.open_for(
+            term,
+            entry,
+            &tab.logs_view.profile,
+            &tab.logs_view.region,
+            &tab.logs_view.available_groups,
+        );
+        self.trace.start_search(&self.worker);
+    }
+
+    /// The tab an export was started from, if it is still open.
+    fn export_tab_mut(&mut self) -> Option<&mut Tab> {
+        let idx = self.tab_index(self.export_dialog.source_tab)?;
+        Some(&mut self.tabs[idx])
+    }
+
+    pub(crate) fn start_export(&mut self, path: std::path::PathBuf) {
+        if self.export_rx.is_some() {
+            return;
+        }
+        let Some(idx) = self.tab_index(self.export_dialog.source_tab) else {
+            return;
+        };
+        let view = &self.tabs[idx].logs_view;
+
+        let entries: Vec<LogEntry> = match self.export_dialog.scope {
+            ExportScope::All => view.entries.clone(),
+            ExportScope::Filtered => view.visible_logical_entries(),
+            ExportScope::Selection => view.selected_logical_entries(),
+        };
+
+        let mut options = self.export_dialog.options.clone();
+        options.csv_columns.extend(
+            self.export_dialog
+                .extra_fields
+                .split(',')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(|f| CsvColumn::Field(f.to_string())),
+        );
+
+        let (tx, rx) = std::sync::mpsc::channel::<Result<usize, ExportError>>();
+
+        self.worker.send(WorkerRequest::ExportEntries {
+            path,
+            entries,
+            options,
+            respond_to: tx,
+        });
+
+        self.export_rx = Some(rx);
+        self.tabs[idx].last_error = None;
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.handle_tray_events(ctx);
+        self.handle_launches(ctx);
+        self.poll_alerts(ctx);
+        self.handle_close_request(ctx);
+
+        // Apply theme visuals.
+        match self.theme {
+            Theme::Light => ctx.set_visuals(egui::Visuals::light()),
+            Theme::Dark => ctx.set_visuals(egui::Visuals::dark()),
+            Theme::RetroGreen => {
+                let mut visuals = egui::Visuals::dark();
+                visuals.override_text_color = Some(egui::Color32::from_rgb(0x00, 0xff, 0x66));
+                visuals.panel_fill = egui::Color32::BLACK;
+                visuals.extreme_bg_color = egui::Color32::BLACK;
+                visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(0x00, 0x20, 0x00);
+                visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(0x00, 0x40, 0x00);
+                visuals.widgets.active.bg_fill = egui::Color32::from_rgb(0x00, 0x60, 0x00);
+                ctx.set_visuals(visuals);
+            }
+        }
+
+        // Every tab polls its own responses, including background ones.
+        for tab in &mut self.tabs {
+            tab.poll_responses();
+        }
+
+        // Files dropped onto the window are opened as a local source.
+        let dropped = ctx.input(|i| i.raw.dropped_files.iter().find_map(|f| f.path.clone()));
+        if let Some(path) = dropped {
+            self.open_local_file(path);
+        }
+
+        // Poll export results.
+        if let Some(rx) = self.export_rx.as_ref() {
+            let outcome = match rx.try_recv() {
+                Ok(Ok(count)) => Some(Ok(format!("Exported {count} entries"))),
+                Ok(Err(err)) => Some(Err(format!("{err}"))),
+                Err(std::sync::mpsc::TryRecvError::Empty) => None,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    Some(Ok("Export aborted".to_string()))
+                }
+            };
+            if let Some(outcome) = outcome {
+                self.export_rx = None;
+                if let Some(tab) = self.export_tab_mut() {
+                    match outcome {
+                        Ok(info) => tab.last_info = Some(info),
+                        Err(err) => tab.last_error = Some(err),
+                    }
+                }
+            }
+        }
+
+        self.trace.poll();
+
+        // Keep the elapsed time in the status line ticking between events.
+        let refreshing = |tab: &Tab| tab.is_fetching || tab.tail_progress.is_some();
+        if self.trace.is_searching || self.tabs.iter().any(refreshing) {
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+
+        // The worker times the tails, background tabs' too.
+        for tab in &mut self.tabs {
+            tab.sync_tail(&self.worker);
+        }
+
+        // Top bar.
+        ui_top::draw_top_bar(self, ctx);
+
+        // Detail panel for the selected entry (must precede the central panel).
+        if self.view == ActiveView::Logs {
+            detail_panel::draw_detail_panel(self, ctx);
+        }
+
+        // Main content.
+        let active_tab = self.active_tab;
+        egui::CentralPanel::default().show(ctx, |ui| match self.view {
+            ActiveView::Logs => match self.split_pane_index() {
+                Some(other_tab) => self.draw_split(ui, active_tab, other_tab),
+                None => {
+                    ui_logs::draw_logs_view(self, active_tab, true, ui);
+                }
+            },
+            ActiveView::Patterns => ui_patterns::draw_patterns_view(self, active_tab, ui),
+            ActiveView::Settings => ui_settings::draw_settings_view(self, ui),
+        });
+
+        let (icon, status) = self.tray_status();
+        let tray_state = TrayState {
+            window_visible: self.window_visible,
+            tail: self.tail_state(),
+            favorites: self.favorites.recent_labels(TRAY_FAVORITES),
+            icon,
+            status,
+        };
+        self.tray.update(&tray_state);
+
+        // Status bar.
+        status_bar::draw_status_bar(self, ctx);
+
+        ui_export::draw_export_window(self, ctx);
+        ui_trace::draw_trace_window(self, ctx);
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let saved = SavedTabs {
+            tabs: self.tabs.iter().map(Tab::to_config).collect(),
+            active: self.active_tab,
+        };
+        eframe::set_value(storage, TABS_KEY, &saved);
+        eframe::set_value(storage, FAVORITES_KEY, &self.favorites);
+        eframe::set_value(storage, SETTINGS_KEY, &self.settings);
+        eframe::set_value(storage, ALERTS_KEY, &self.alert_rules);
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
@@ -1,17 +1,30 @@
+use std::future::Future;
 use std::time::{Duration, SystemTime};
 
 use aws_config::BehaviorVersion;
-use aws_sdk_cloudwatchlogs::types::FilteredLogEvent;
+use aws_sdk_cloudwatchlogs::error::ProvideErrorMetadata;
+use aws_sdk_cloudwatchlogs::types::{
+    FilteredLogEvent, LogStream, OrderBy, QueryStatistics, QueryStatus, ResultField,
+};
 use aws_sdk_cloudwatchlogs::{Client as CloudWatchLogsClient, Error as CloudWatchLogsError};
 
+#[cfg(feature = "gui")]
+use futures_util::future::join_all;
 use thiserror::Error;
 
+#[cfg(feature = "gui")]
+use crate::worker::Limiter;
+use crate::worker::ProgressSender;
+
 /// A single log entry returned from CloudWatch Logs.
 #[derive(Debug, Clone)]
 pub struct LogEntry {
     pub timestamp_millis: i64,
     pub message: String,
     pub log_stream_name: Option<String>,
+    pub log_group_name: Option<String>,
+    pub event_id: Option<String>,
+    pub ingestion_time_millis: Option<i64>,
 }
 
 #[derive(Debug, Error)]
@@ -25,6 +38,72 @@ pub enum AwsLogError {
 
     #[error("failed to list CloudWatch log groups in region {region}: {message}")]
     ListLogGroups { region: String, message: String },
+
+    #[error("Logs Insights query {query_id} ended as {status}")]
+    InsightsQuery { query_id: String, status: String },
+
+    #[error("Logs Insights query {query_id} did not complete within {}s", timeout.as_secs())]
+    InsightsTimeout { query_id: String, timeout: Duration },
+}
+
+/// A log stream as listed by DescribeLogStreams.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogStreamInfo {
+    pub name: String,
+    pub first_event_millis: Option<i64>,
+    pub last_event_millis: Option<i64>,
+}
+
+/// One row of a Logs Insights result: `(field, value)` pairs in query order.
+pub type InsightsRow = Vec<(String, String)>;
+
+/// Poll interval while a Logs Insights query runs.
+const INSIGHTS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A Logs Insights query still running after this long is stopped.
+const INSIGHTS_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// DescribeLogStreams returns at most this many streams per page.
+const MAX_STREAMS_PAGE_SIZE: i32 = 50;
+
+/// FilterLogEvents returns at most this many events per page.
+const MAX_PAGE_SIZE: i32 = 10_000;
+
+/// Throttled calls are retried this many times, doubling the delay.
+const MAX_RETRIES: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Progress of a long request, sent while it runs; the result still
+/// arrives on its own channel.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    not(feature = "gui"),
+    allow(dead_code, reason = "the CLI neither tails nor shows pages")
+)]
+pub enum Progress {
+    /// A page of events arrived.
+    Page { entries: Vec<LogEntry> },
+    /// A Logs Insights query changed status or scanned more.
+    Insights {
+        status: String,
+        records_matched: f64,
+        records_scanned: f64,
+        bytes_scanned: f64,
+    },
+    /// A throttled call is retried after `delay`.
+    Retrying {
+        attempt: u32,
+        delay: Duration,
+        reason: String,
+    },
+    /// A tail refresh started; its pages and retries follow.
+    Refreshing,
+}
+
+fn report(progress: Option<&ProgressSender<'_>>, event: Progress) {
+    if let Some(progress) = progress {
+        progress.send(event);
+    }
 }
 
 /// High-level parameters for fetching recent logs.
@@ -34,7 +113,11 @@ pub struct FetchLogsParams<'a> {
     pub log_group: &'a str,
     pub filter_pattern: Option<&'a str>,
     pub lookback: Duration,
+    /// Absolute `(start, end)` range in epoch millis; overrides `lookback`.
+    pub absolute_range: Option<(i64, i64)>,
     pub limit: i32,
+    /// Receives each page and retry as the fetch goes.
+    pub progress: Option<&'a ProgressSender<'a>>,
 }
 
 impl<'a> Default for FetchLogsParams<'a> {
@@ -45,7 +128,9 @@ impl<'a> Default for FetchLogsParams<'a> {
             log_group: "",
             filter_pattern: None,
             lookback: Duration::from_secs(5 * 60),
+            absolute_range: None,
             limit: 1_000,
+            progress: None,
         }
     }
 }
@@ -67,43 +152,203 @@ async fn mk_client(profile: Option<&str>, region: Option<&str>) -> CloudWatchLog
     CloudWatchLogsClient::new(&config)
 }
 
-/// Fetch recent log events from CloudWatch Logs using FilterLogEvents.
+/// Fetch recent log events from CloudWatch Logs using FilterLogEvents,
+/// following pages until `limit` events are in.
 pub async fn fetch_recent_logs(params: FetchLogsParams<'_>) -> Result<Vec<LogEntry>, AwsLogError> {
     let client: CloudWatchLogsClient = mk_client(params.profile, params.region).await;
+    fetch_with_client(&client, &params).await
+}
 
-    let now = SystemTime::now();
-    let since = now
-        .checked_sub(params.lookback)
-        .unwrap_or(SystemTime::UNIX_EPOCH);
-    let start_time_millis = to_millis(since);
-
-    // Build the request directly from the client.
-    let mut req = client
-        .filter_log_events()
-        .log_group_name(params.log_group)
-        .start_time(start_time_millis)
-        .limit(params.limit);
-
-    if let Some(pattern) = params.filter_pattern {
-        let pattern = pattern.trim();
-        if !pattern.is_empty() {
-            req = req.filter_pattern(pattern);
+/// `fetch_recent_logs` with a client built by the caller, ignoring the
+/// params' profile and region.
+async fn fetch_with_client(
+    client: &CloudWatchLogsClient,
+    params: &FetchLogsParams<'_>,
+) -> Result<Vec<LogEntry>, AwsLogError> {
+    let (start_time_millis, end_time_millis) = match params.absolute_range {
+        Some((start, end)) => (start, Some(end)),
+        None => {
+            let now = SystemTime::now();
+            let since = now
+                .checked_sub(params.lookback)
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (to_millis(since), None)
+        }
+    };
+    let pattern = params
+        .filter_pattern
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+
+    let mut events: Vec<LogEntry> = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let page_size = (params.limit - events.len() as i32).min(MAX_PAGE_SIZE);
+        let resp = with_retries(params.progress, || {
+            client
+                .filter_log_events()
+                .log_group_name(params.log_group)
+                .start_time(start_time_millis)
+                .set_end_time(end_time_millis)
+                .set_filter_pattern(pattern.map(str::to_string))
+                .set_next_token(next_token.clone())
+                .limit(page_size)
+                .send()
+        })
+        .await
+        .map_err(|e| AwsLogError::CloudWatch {
+            log_group: params.log_group.to_string(),
+            source: e.into(),
+        })?;
+
+        let page: Vec<LogEntry> = resp
+            .events
+            .unwrap_or_default()
+            .into_iter()
+            .map(|event| LogEntry {
+                log_group_name: Some(params.log_group.to_string()),
+                ..filtered_to_entry(event)
+            })
+            .collect();
+        if let Some(progress) = params.progress
+            && !page.is_empty()
+        {
+            progress.send(Progress::Page {
+                entries: page.clone(),
+            });
+        }
+        events.extend(page);
+
+        next_token = resp.next_token;
+        if next_token.is_none() || events.len() as i32 >= params.limit {
+            return Ok(events);
         }
     }
+}
 
-    let resp = req.send().await.map_err(|e| AwsLogError::CloudWatch {
-        log_group: params.log_group.to_string(),
-        source: e.into(),
-    })?;
+/// Run `call`, retrying it while CloudWatch throttles.
+async fn with_retries<T, E, F, Fut>(
+    progress: Option<&ProgressSender<'_>>,
+    mut call: F,
+) -> Result<T, E>
+where
+    E: ProvideErrorMetadata,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 0;
+    loop {
+        match call().await {
+            Err(e) if attempt < MAX_RETRIES && is_throttling(e.code()) => {
+                attempt += 1;
+                let delay = retry_delay(attempt);
+                report(
+                    progress,
+                    Progress::Retrying {
+                        attempt,
+                        delay,
+                        reason: e.code().unwrap_or_default().to_string(),
+                    },
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
 
-    let events: Vec<LogEntry> = resp
-        .events
-        .unwrap_or_default()
-        .into_iter()
-        .map(filtered_to_entry)
-        .collect();
+fn is_throttling(code: Option<&str>) -> bool {
+    matches!(
+        code,
+        Some("ThrottlingException" | "LimitExceededException" | "TooManyRequestsException")
+    )
+}
+
+/// Delay before retry `attempt` (from 1): 1s, 2s, 4s…
+fn retry_delay(attempt: u32) -> Duration {
+    RETRY_BASE_DELAY * 2u32.pow(attempt.saturating_sub(1))
+}
+
+/// Parameters for searching several log groups for one term.
+#[cfg(feature = "gui")]
+pub struct SearchLogGroupsParams<'a> {
+    pub profile: Option<&'a str>,
+    pub region: Option<&'a str>,
+    pub log_groups: &'a [String],
+    pub term: &'a str,
+    /// Absolute `(start, end)` range in epoch millis.
+    pub range: (i64, i64),
+    /// Per log group.
+    pub limit: i32,
+    /// Receives the pages of every group as they arrive.
+    pub progress: Option<&'a ProgressSender<'a>>,
+    /// Limits the groups searched at once; each takes a permit.
+    pub permits: &'a Limiter,
+}
+
+/// Matches of a search across several log groups, and the groups that
+/// could not be searched.
+#[cfg(feature = "gui")]
+#[derive(Debug, Default)]
+pub struct SearchResults {
+    /// Matches of the groups searched, merged by time.
+    pub entries: Vec<LogEntry>,
+    /// One error per failed group (e.g. missing or forbidden).
+    pub failures: Vec<AwsLogError>,
+}
+
+/// Search the log groups concurrently for an exact term (e.g. a request or
+/// trace ID) and merge the matches into one time-ordered list. A group that
+/// fails does not stop the others.
+#[cfg(feature = "gui")]
+pub async fn search_log_groups(params: SearchLogGroupsParams<'_>) -> SearchResults {
+    let client: CloudWatchLogsClient = mk_client(params.profile, params.region).await;
+    let pattern = term_filter_pattern(params.term);
+
+    let searches = params.log_groups.iter().map(|log_group| {
+        let group_params = FetchLogsParams {
+            log_group,
+            filter_pattern: Some(&pattern),
+            absolute_range: Some(params.range),
+            limit: params.limit,
+            progress: params.progress,
+            ..FetchLogsParams::default()
+        };
+        let client = &client;
+        async move {
+            let _permit = params.permits.acquire().await;
+            fetch_with_client(client, &group_params).await
+        }
+    });
+
+    let mut per_group = Vec::with_capacity(params.log_groups.len());
+    let mut failures = Vec::new();
+    for result in join_all(searches).await {
+        match result {
+            Ok(entries) => per_group.push(entries),
+            Err(e) => failures.push(e),
+        }
+    }
+
+    SearchResults {
+        entries: merge_by_time(per_group),
+        failures,
+    }
+}
 
-    Ok(events)
+/// Filter pattern matching `term` literally, quoted so that IDs containing
+/// `-`, `=` or `;` are not parsed as pattern syntax.
+#[cfg(feature = "gui")]
+pub fn term_filter_pattern(term: &str) -> String {
+    format!("\"{}\"", term.trim().replace('"', ""))
+}
+
+/// Concatenate result lists and order them by timestamp (stable, so equal
+/// timestamps keep their per-group order).
+pub fn merge_by_time(lists: Vec<Vec<LogEntry>>) -> Vec<LogEntry> {
+    let mut merged: Vec<LogEntry> = lists.into_iter().flatten().collect();
+    merged.sort_by_key(|e| e.timestamp_millis);
+    merged
 }
 
 fn filtered_to_entry(event: FilteredLogEvent) -> LogEntry {
@@ -111,6 +356,9 @@ fn filtered_to_entry(event: FilteredLogEvent) -> LogEntry {
         timestamp_millis: event.timestamp.unwrap_or_default(),
         message: event.message.unwrap_or_default(),
         log_stream_name: event.log_stream_name,
+        log_group_name: None,
+        event_id: event.event_id,
+        ingestion_time_millis: event.ingestion_time,
     }
 }
 
@@ -121,6 +369,199 @@ fn to_millis(t: SystemTime) -> i64 {
     }
 }
 
+/// List the streams of a log group, most recently written first.
+pub async fn list_log_streams(
+    profile: Option<&str>,
+    region: Option<&str>,
+    log_group: &str,
+    limit: i32,
+) -> Result<Vec<LogStreamInfo>, AwsLogError> {
+    let client: CloudWatchLogsClient = mk_client(profile, region).await;
+    let limit = limit.max(1) as usize;
+
+    let mut streams = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let page_size = (limit - streams.len()).min(MAX_STREAMS_PAGE_SIZE as usize);
+        let resp = client
+            .describe_log_streams()
+            .log_group_name(log_group)
+            .order_by(OrderBy::LastEventTime)
+            .descending(true)
+            .limit(page_size as i32)
+            .set_next_token(next_token.take())
+            .send()
+            .await
+            .map_err(|e| AwsLogError::CloudWatch {
+                log_group: log_group.to_string(),
+                source: e.into(),
+            })?;
+
+        streams.extend(
+            resp.log_streams
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(stream_to_info),
+        );
+        next_token = resp.next_token;
+        if next_token.is_none() || streams.len() >= limit {
+            break;
+        }
+    }
+    streams.truncate(limit);
+    Ok(streams)
+}
+
+/// Parameters for a Logs Insights query.
+pub struct InsightsQueryParams<'a> {
+    pub profile: Option<&'a str>,
+    pub region: Option<&'a str>,
+    pub log_groups: &'a [String],
+    pub query: &'a str,
+    /// Absolute `(start, end)` range in epoch millis.
+    pub range: (i64, i64),
+    pub limit: i32,
+    /// Receives status and statistics changes while the query runs.
+    pub progress: Option<&'a ProgressSender<'a>>,
+}
+
+/// Run a Logs Insights query and wait for its results.
+pub async fn run_insights_query(
+    params: InsightsQueryParams<'_>,
+) -> Result<Vec<InsightsRow>, AwsLogError> {
+    let client: CloudWatchLogsClient = mk_client(params.profile, params.region).await;
+    let to_err = |source: CloudWatchLogsError| AwsLogError::CloudWatch {
+        log_group: params.log_groups.join(", "),
+        source,
+    };
+
+    let started = with_retries(params.progress, || {
+        client
+            .start_query()
+            .set_log_group_names(Some(params.log_groups.to_vec()))
+            // Insights takes seconds.
+            .start_time(params.range.0 / 1_000)
+            .end_time((params.range.1 + 999) / 1_000)
+            .query_string(params.query)
+            .limit(params.limit)
+            .send()
+    })
+    .await
+    .map_err(|e| to_err(e.into()))?;
+    let query_id = started.query_id.unwrap_or_default();
+    let mut running = RunningQuery {
+        client: client.clone(),
+        query_id: Some(query_id.clone()),
+    };
+    let deadline = tokio::time::Instant::now() + INSIGHTS_TIMEOUT;
+
+    let mut last_reported = None;
+    loop {
+        let resp = with_retries(params.progress, || {
+            client.get_query_results().query_id(&query_id).send()
+        })
+        .await
+        .map_err(|e| to_err(e.into()))?;
+
+        let state = (resp.status.clone(), resp.statistics.clone());
+        if last_reported.as_ref() != Some(&state) {
+            report(
+                params.progress,
+                insights_progress(state.0.as_ref(), state.1.as_ref()),
+            );
+            last_reported = Some(state);
+        }
+
+        if !matches!(
+            resp.status,
+            Some(QueryStatus::Running | QueryStatus::Scheduled) | None
+        ) {
+            running.query_id = None;
+        }
+        match resp.status {
+            Some(QueryStatus::Complete) => {
+                return Ok(resp
+                    .results
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(result_to_row)
+                    .collect());
+            }
+            Some(QueryStatus::Running | QueryStatus::Scheduled) | None => {
+                if tokio::time::Instant::now() >= deadline {
+                    running.stop().await;
+                    return Err(AwsLogError::InsightsTimeout {
+                        query_id,
+                        timeout: INSIGHTS_TIMEOUT,
+                    });
+                }
+                tokio::time::sleep(INSIGHTS_POLL_INTERVAL).await;
+            }
+            Some(status) => {
+                return Err(AwsLogError::InsightsQuery {
+                    query_id,
+                    status: status.as_str().to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// A started Insights query, stopped when dropped before it ended, e.g. when
+/// its request is cancelled, so it does not go on scanning.
+struct RunningQuery {
+    client: CloudWatchLogsClient,
+    /// Cleared once the query ended on its own.
+    query_id: Option<String>,
+}
+
+impl RunningQuery {
+    async fn stop(&mut self) {
+        if let Some(query_id) = self.query_id.take() {
+            let _ = self.client.stop_query().query_id(query_id).send().await;
+        }
+    }
+}
+
+impl Drop for RunningQuery {
+    fn drop(&mut self) {
+        if let Some(query_id) = self.query_id.take()
+            && let Ok(runtime) = tokio::runtime::Handle::try_current()
+        {
+            let client = self.client.clone();
+            runtime.spawn(async move {
+                let _ = client.stop_query().query_id(query_id).send().await;
+            });
+        }
+    }
+}
+
+fn insights_progress(status: Option<&QueryStatus>, stats: Option<&QueryStatistics>) -> Progress {
+    Progress::Insights {
+        status: status.map_or("Unknown", QueryStatus::as_str).to_string(),
+        records_matched: stats.map_or(0.0, |s| s.records_matched),
+        records_scanned: stats.map_or(0.0, |s| s.records_scanned),
+        bytes_scanned: stats.map_or(0.0, |s| s.bytes_scanned),
+    }
+}
+
+fn stream_to_info(stream: LogStream) -> Option<LogStreamInfo> {
+    Some(LogStreamInfo {
+        name: stream.log_stream_name?,
+        first_event_millis: stream.first_event_timestamp,
+        last_event_millis: stream.last_event_timestamp,
+    })
+}
+
+/// `(field, value)` pairs of a result row, without the `@ptr` record link.
+fn result_to_row(fields: Vec<ResultField>) -> InsightsRow {
+    fields
+        .into_iter()
+        .filter_map(|f| Some((f.field?, f.value.unwrap_or_default())))
+        .filter(|(field, _)| field != "@ptr")
+        .collect()
+}
+
 pub async fn list_log_groups(
     profile: Option<&str>,
     region: Option<&str>,
@@ -163,34 +604,33 @@ pub async fn list_log_groups(
 }
 
 fn extract_nice_aws_message_from_debug(debug_str: &str) -> Option<String> {
-    if let Some(start_idx) = debug_str.find("b\"{") {
-        if let Some(rest) = debug_str.get(start_idx + 2..) {
-            if let Some(end_rel) = rest.find("\"}") {
-                let json_slice = &rest[..end_rel + 2]; // include the closing "}
-                let unescaped = json_slice.replace("\\\"", "\"");
-
-                if let Ok(v) = serde_json::from_str::<serde_json::Value>(&unescaped) {
-                    let code = v
-                        .get("__type")
-                        .and_then(|c| c.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    let msg = v
-                        .get("message")
-                        .and_then(|m| m.as_str())
-                        .unwrap_or("")
-                        .to_string();
-
-                    if !code.is_empty() || !msg.is_empty() {
-                        return Some(if !code.is_empty() && !msg.is_empty() {
-                            format!("{code}: {msg}")
-                        } else if !code.is_empty() {
-                            code
-                        } else {
-                            msg
-                        });
-                    }
-                }
+    if let Some(start_idx) = debug_str.find("b\"{")
+        && let Some(rest) = debug_str.get(start_idx + 2..)
+        && let Some(end_rel) = rest.find("\"}")
+    {
+        let json_slice = &rest[..end_rel + 2]; // include the closing "}
+        let unescaped = json_slice.replace("\\\"", "\"");
+
+        if let Ok(v) = serde_json::from_str::<serde_json::Value>(&unescaped) {
+            let code = v
+                .get("__type")
+                .and_then(|c| c.as_str())
+                .unwrap_or("")
+                .to_string();
+            let msg = v
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("")
+                .to_string();
+
+            if !code.is_empty() || !msg.is_empty() {
+                return Some(if !code.is_empty() && !msg.is_empty() {
+                    format!("{code}: {msg}")
+                } else if !code.is_empty() {
+                    code
+                } else {
+                    msg
+                });
             }
         }
     }
@@ -244,6 +684,8 @@ mod tests {
             .timestamp(1_700_000_000_123_i64)
             .message("hello world".to_string())
             .log_stream_name("my-stream".to_string())
+            .event_id("3761234".to_string())
+            .ingestion_time(1_700_000_000_456_i64)
             .build();
 
         let entry = filtered_to_entry(event);
@@ -251,6 +693,47 @@ mod tests {
         assert_eq!(entry.timestamp_millis, 1_700_000_000_123_i64);
         assert_eq!(entry.message, "hello world");
         assert_eq!(entry.log_stream_name.as_deref(), Some("my-stream"));
+        assert_eq!(entry.event_id.as_deref(), Some("3761234"));
+        assert_eq!(entry.ingestion_time_millis, Some(1_700_000_000_456_i64));
+        assert_eq!(entry.log_group_name, None);
+    }
+
+    #[test]
+    fn insights_rows_drop_record_pointers() {
+        let row = result_to_row(vec![
+            ResultField::builder()
+                .field("@timestamp")
+                .value("2026-10-18 10:00:00.000")
+                .build(),
+            ResultField::builder()
+                .field("@ptr")
+                .value("CmAKJ...")
+                .build(),
+            ResultField::builder().field("count()").build(),
+        ]);
+        assert_eq!(
+            row,
+            vec![
+                (
+                    "@timestamp".to_string(),
+                    "2026-10-18 10:00:00.000".to_string()
+                ),
+                ("count()".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn streams_without_names_are_skipped() {
+        let stream = LogStream::builder()
+            .log_stream_name("2026/10/18/[$LATEST]abc")
+            .last_event_timestamp(42)
+            .build();
+        assert_eq!(
+            stream_to_info(stream).map(|s| (s.name, s.last_event_millis)),
+            Some(("2026/10/18/[$LATEST]abc".to_string(), Some(42)))
+        );
+        assert_eq!(stream_to_info(LogStream::builder().build()), None);
     }
 
     #[test]
@@ -263,6 +746,40 @@ mod tests {
         assert_eq!(entry.timestamp_millis, 0);
         assert_eq!(entry.message, "");
         assert_eq!(entry.log_stream_name, None);
+        assert_eq!(entry.event_id, None);
+        assert_eq!(entry.ingestion_time_millis, None);
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn term_filter_pattern_quotes_the_term() {
+        assert_eq!(
+            term_filter_pattern(" Root=1-5759e988-bd862e3fe1be46a994272793 "),
+            "\"Root=1-5759e988-bd862e3fe1be46a994272793\""
+        );
+        assert_eq!(term_filter_pattern("a\"b"), "\"ab\"");
+    }
+
+    #[test]
+    fn merge_by_time_interleaves_groups() {
+        let entry = |ts: i64, group: &str| LogEntry {
+            timestamp_millis: ts,
+            message: String::new(),
+            log_stream_name: None,
+            log_group_name: Some(group.to_string()),
+            event_id: None,
+            ingestion_time_millis: None,
+        };
+
+        let merged = merge_by_time(vec![
+            vec![entry(1, "a"), entry(5, "a")],
+            vec![entry(2, "b"), entry(5, "b")],
+        ]);
+        let order: Vec<_> = merged
+            .iter()
+            .map(|e| (e.timestamp_millis, e.log_group_name.as_deref().unwrap()))
+            .collect();
+        assert_eq!(order, vec![(1, "a"), (2, "b"), (5, "a"), (5, "b")]);
     }
 
     #[test]
@@ -272,6 +789,66 @@ mod tests {
         assert_eq!(params.log_group, "");
         assert_eq!(params.filter_pattern, None);
         assert_eq!(params.lookback, Duration::from_secs(5 * 60));
+        assert_eq!(params.absolute_range, None);
         assert_eq!(params.limit, 1_000);
     }
+
+    #[test]
+    fn throttled_calls_are_retried_with_growing_delays() {
+        use aws_sdk_cloudwatchlogs::error::ErrorMetadata;
+
+        assert!(is_throttling(Some("ThrottlingException")));
+        assert!(!is_throttling(Some("ResourceNotFoundException")));
+        assert!(!is_throttling(None));
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(3), Duration::from_secs(4));
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let repaint = crate::worker::Repaint::default();
+        let progress = ProgressSender::new(&tx, &repaint);
+        let mut calls = 0;
+        let result = rt.block_on(with_retries(Some(&progress), || {
+            calls += 1;
+            let code = if calls == 1 {
+                "ThrottlingException"
+            } else {
+                "ResourceNotFoundException"
+            };
+            std::future::ready(Err::<(), _>(ErrorMetadata::builder().code(code).build()))
+        }));
+
+        assert_eq!(
+            result.unwrap_err().code(),
+            Some("ResourceNotFoundException")
+        );
+        assert_eq!(calls, 2);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(Progress::Retrying { attempt: 1, ref reason, .. }) if reason == "ThrottlingException"
+        ));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn insights_progress_defaults_missing_statistics() {
+        let stats = QueryStatistics::builder()
+            .records_matched(3.0)
+            .records_scanned(120.0)
+            .bytes_scanned(4096.0)
+            .build();
+        assert!(matches!(
+            insights_progress(Some(&QueryStatus::Running), Some(&stats)),
+            Progress::Insights { ref status, records_scanned, bytes_scanned, .. }
+                if status == "Running" && records_scanned == 120.0 && bytes_scanned == 4096.0
+        ));
+        assert!(matches!(
+            insights_progress(None, None),
+            Progress::Insights { ref status, records_matched, .. }
+                if status == "Unknown" && records_matched == 0.0
+        ));
+    }
 }
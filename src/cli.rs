@@ -0,0 +1,980 @@
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+use std::sync::mpsc::{Sender, channel};
+use std::thread;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::{Map, Value as JsonValue, json};
+use thiserror::Error;
+
+use crate::app::ansi::strip_ansi;
+use crate::app::export::{
+    CsvColumn, ExportFormat, ExportOptions, entry_to_json, write_csv_row, write_entry, write_header,
+};
+use crate::app::level::LogLevel;
+use crate::app::parsers::ParserRegistry;
+use crate::app::query::{Query, QueryError, QueryTarget};
+use crate::app::state::{entry_key, format_timestamp_millis};
+use crate::aws::{AwsLogError, InsightsRow, LogEntry, LogStreamInfo, Progress, merge_by_time};
+use crate::worker::{
+    DEFAULT_CONCURRENCY, WorkerHandle, WorkerRequest, spawn_worker_with_concurrency,
+};
+
+/// Each tail refresh re-reads this much before the newest event seen, so
+/// events ingested late are not missed; duplicates are dropped.
+const TAIL_OVERLAP_MILLIS: i64 = 30_000;
+
+/// Lumberjack Axe: a CloudWatch Logs viewer. Without a subcommand the GUI
+/// starts; with one, it runs headless and writes to stdout.
+#[derive(Debug, Parser)]
+#[command(name = "axe", version)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+
+    #[command(flatten)]
+    pub open: OpenArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Args)]
+pub struct GlobalArgs {
+    /// AWS profile (default: the usual AWS environment and config).
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    /// AWS region (default: from the profile or environment).
+    #[arg(long, global = true)]
+    pub region: Option<String>,
+
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    #[arg(long, global = true, value_enum, default_value_t = ColorMode::Auto)]
+    pub color: ColorMode,
+
+    /// Only print events of this level or more severe (error, warn, info,
+    /// debug), as detected from the parsed fields or the text.
+    #[arg(long, global = true, value_parser = parse_level)]
+    pub level: Option<LogLevel>,
+
+    /// Print and read times in the local time zone instead of UTC.
+    #[arg(long, global = true)]
+    pub local_time: bool,
+
+    /// Requests to CloudWatch run at the same time, e.g. when reading
+    /// several log groups.
+    #[arg(long, global = true, default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
+}
+
+/// What the GUI opens in a new tab; passed to the running instance if there
+/// is one.
+#[derive(Debug, Args)]
+pub struct OpenArgs {
+    /// An `axe://open?group=…&since=15m` link, or a saved-query file (JSON
+    /// with the same keys, or a link).
+    pub target: Option<String>,
+
+    /// Log group to open.
+    #[arg(long)]
+    pub group: Option<String>,
+
+    /// CloudWatch filter pattern.
+    #[arg(long)]
+    pub filter: Option<String>,
+
+    /// Local query, as in the query bar.
+    #[arg(long)]
+    pub query: Option<String>,
+
+    /// Lookback such as `15m`, or a start time.
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// End time, e.g. `2026-10-18T10:00:00Z` or epoch millis.
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Start tailing.
+    #[arg(long)]
+    pub tail: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    /// JSON Lines: one object per line.
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorMode {
+    /// Color when stdout is a terminal and `NO_COLOR` is not set.
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    fn enabled(self) -> bool {
+        match self {
+            ColorMode::Auto => io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List log groups.
+    Groups {
+        #[arg(long, default_value_t = 50, value_parser = clap::value_parser!(i32).range(1..))]
+        limit: i32,
+    },
+    /// List the streams of a log group, most recently written first.
+    Streams {
+        #[arg(long = "group", short = 'g')]
+        group: String,
+        #[arg(long, default_value_t = 50, value_parser = clap::value_parser!(i32).range(1..))]
+        limit: i32,
+    },
+    /// Fetch the events of a time range.
+    Fetch {
+        #[command(flatten)]
+        source: SourceArgs,
+        #[command(flatten)]
+        range: RangeArgs,
+        /// Events per log group.
+        #[arg(long, default_value_t = 1_000, value_parser = clap::value_parser!(i32).range(1..))]
+        limit: i32,
+    },
+    /// Print events as they arrive until interrupted.
+    Tail {
+        #[command(flatten)]
+        source: SourceArgs,
+        /// How far back to start, e.g. `1m` or `2h`.
+        #[arg(long, default_value = "1m", value_parser = parse_duration)]
+        since: Duration,
+        /// Time between refreshes.
+        #[arg(long, default_value = "2s", value_parser = parse_duration)]
+        interval: Duration,
+    },
+    /// Run a Logs Insights query.
+    Insights {
+        /// The query, e.g. `fields @timestamp, @message | limit 20`.
+        query: String,
+        #[arg(long = "group", short = 'g', required = true)]
+        groups: Vec<String>,
+        #[command(flatten)]
+        range: RangeArgs,
+        #[arg(long, default_value_t = 1_000, value_parser = clap::value_parser!(i32).range(1..))]
+        limit: i32,
+    },
+}
+
+/// Which events to read.
+#[derive(Debug, Args)]
+pub struct SourceArgs {
+    /// Log group; repeat to merge several.
+    #[arg(long = "group", short = 'g', required = true)]
+    pub groups: Vec<String>,
+
+    /// CloudWatch filter pattern, applied by CloudWatch.
+    #[arg(long, short)]
+    pub pattern: Option<String>,
+
+    /// Local query applied to the fetched events, as in the query bar,
+    /// e.g. `level:error AND status>=500`.
+    #[arg(long, short)]
+    pub query: Option<String>,
+}
+
+/// A time range: `--since`, or `--start` with an optional `--end`.
+#[derive(Debug, Args)]
+pub struct RangeArgs {
+    /// Lookback from now, e.g. `15m`, `2h` or `1d` (default `5m`).
+    #[arg(long, value_parser = parse_duration, conflicts_with = "start")]
+    pub since: Option<Duration>,
+
+    /// Start time, e.g. `2026-10-18T10:00:00Z` or `2026-10-18 10:00:00`.
+    #[arg(long)]
+    pub start: Option<String>,
+
+    /// End time (default: now).
+    #[arg(long, requires = "start")]
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("invalid time {0:?}: expected RFC 3339 or `YYYY-MM-DD HH:MM:SS`")]
+    InvalidTime(String),
+
+    #[error("the range ends before it starts")]
+    EmptyRange,
+
+    #[error("invalid query: {0}")]
+    Query(#[from] QueryError),
+
+    /// Boxed: the SDK error is large.
+    #[error(transparent)]
+    Aws(Box<AwsLogError>),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error("the background worker stopped")]
+    WorkerGone,
+}
+
+impl From<AwsLogError> for CliError {
+    fn from(err: AwsLogError) -> Self {
+        CliError::Aws(Box::new(err))
+    }
+}
+
+/// Run the subcommand; returns the process exit code.
+pub fn run(global: GlobalArgs, command: Command) -> i32 {
+    let worker = spawn_worker_with_concurrency(global.concurrency.max(1));
+    match execute(&worker, &global, command) {
+        Ok(()) => 0,
+        // Closed pipe, e.g. `axe tail ... | head`.
+        Err(CliError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => 0,
+        Err(e) => {
+            eprintln!("axe: {e}");
+            1
+        }
+    }
+}
+
+fn execute(worker: &WorkerHandle, global: &GlobalArgs, command: Command) -> Result<(), CliError> {
+    let profile = global.profile.clone();
+    let region = global.region.clone();
+    let mut out = io::stdout().lock();
+
+    match command {
+        Command::Groups { limit } => {
+            let groups = ask(worker, |respond_to| WorkerRequest::ListLogGroups {
+                profile,
+                region,
+                limit,
+                respond_to,
+            })??;
+            write_groups(&mut out, global.output, &groups)?;
+        }
+        Command::Streams { group, limit } => {
+            let streams = ask(worker, |respond_to| WorkerRequest::ListLogStreams {
+                profile,
+                region,
+                log_group: group,
+                limit,
+                respond_to,
+            })??;
+            write_streams(&mut out, global, &streams)?;
+        }
+        Command::Fetch {
+            source,
+            range,
+            limit,
+        } => {
+            let range = range.resolve(now_millis(), global.local_time)?;
+            let mut printer = Printer::new(&mut out, global, &source)?;
+            let entries = fetch(worker, global, &source, range, limit)?;
+            printer.header()?;
+            for entry in &entries {
+                printer.entry(entry)?;
+            }
+            printer.out.flush()?;
+        }
+        Command::Tail {
+            source,
+            since,
+            interval,
+        } => {
+            let mut printer = Printer::new(&mut out, global, &source)?;
+            printer.header()?;
+            tail(worker, global, &source, since, interval, &mut printer)?;
+        }
+        Command::Insights {
+            query,
+            groups,
+            range,
+            limit,
+        } => {
+            let range = range.resolve(now_millis(), global.local_time)?;
+            let rows = ask(worker, |respond_to| WorkerRequest::RunInsightsQuery {
+                profile,
+                region,
+                log_groups: groups,
+                query,
+                range,
+                limit,
+                respond_to,
+                progress: progress_reporter(),
+            })??;
+            write_insights(&mut out, global.output, &rows)?;
+        }
+    }
+    Ok(())
+}
+
+/// Send a request to the worker and wait for its response.
+fn ask<T>(
+    worker: &WorkerHandle,
+    request: impl FnOnce(Sender<T>) -> WorkerRequest,
+) -> Result<T, CliError> {
+    let (tx, rx) = channel();
+    worker.send(request(tx));
+    rx.recv().map_err(|_| CliError::WorkerGone)
+}
+
+/// Fetch `range` from every source group at once, merged by time.
+fn fetch(
+    worker: &WorkerHandle,
+    global: &GlobalArgs,
+    source: &SourceArgs,
+    range: (i64, i64),
+    limit: i32,
+) -> Result<Vec<LogEntry>, CliError> {
+    let pending: Vec<_> = source
+        .groups
+        .iter()
+        .map(|group| {
+            let (tx, rx) = channel();
+            worker.send(WorkerRequest::FetchRecentLogs {
+                profile: global.profile.clone(),
+                region: global.region.clone(),
+                log_group: group.clone(),
+                filter_pattern: source.pattern.clone(),
+                lookback: Duration::ZERO,
+                absolute_range: Some(range),
+                limit,
+                respond_to: tx,
+                progress: progress_reporter(),
+            });
+            rx
+        })
+        .collect();
+
+    let mut per_group = Vec::with_capacity(pending.len());
+    for rx in pending {
+        per_group.push(rx.recv().map_err(|_| CliError::WorkerGone)??);
+    }
+    Ok(merge_by_time(per_group))
+}
+
+fn tail<W: Write>(
+    worker: &WorkerHandle,
+    global: &GlobalArgs,
+    source: &SourceArgs,
+    since: Duration,
+    interval: Duration,
+    printer: &mut Printer<W>,
+) -> Result<(), CliError> {
+    let mut cursor = now_millis() - since.as_millis() as i64;
+    let mut seen = HashMap::new();
+
+    loop {
+        let start = cursor - TAIL_OVERLAP_MILLIS;
+        let entries = fetch(worker, global, source, (start, now_millis()), 10_000)?;
+        seen.retain(|_, ts| *ts >= start);
+        for entry in &entries {
+            if seen
+                .insert(entry_key(entry), entry.timestamp_millis)
+                .is_none()
+            {
+                printer.entry(entry)?;
+            }
+            cursor = cursor.max(entry.timestamp_millis);
+        }
+        printer.out.flush()?;
+        thread::sleep(interval);
+    }
+}
+
+impl RangeArgs {
+    /// The absolute `(start, end)` range in epoch millis.
+    pub fn resolve(&self, now: i64, local: bool) -> Result<(i64, i64), CliError> {
+        let Some(start) = &self.start else {
+            let since = self.since.unwrap_or(Duration::from_secs(5 * 60));
+            return Ok((now - since.as_millis() as i64, now));
+        };
+        let start = parse_time(start, local)?;
+        let end = match &self.end {
+            Some(end) => parse_time(end, local)?,
+            None => now,
+        };
+        if end < start {
+            return Err(CliError::EmptyRange);
+        }
+        Ok((start, end))
+    }
+}
+
+/// Epoch millis of an RFC 3339 time, or of a naive one in UTC (or the local
+/// zone with `local`).
+fn parse_time(text: &str, local: bool) -> Result<i64, CliError> {
+    use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+
+    let invalid = || CliError::InvalidTime(text.to_string());
+    if let Ok(dt) = DateTime::parse_from_rfc3339(text.trim()) {
+        return Ok(dt.timestamp_millis());
+    }
+    let naive = [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|fmt| NaiveDateTime::parse_from_str(text.trim(), fmt).ok())
+    .ok_or_else(invalid)?;
+    if !local {
+        return Ok(naive.and_utc().timestamp_millis());
+    }
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.timestamp_millis())
+        .ok_or_else(invalid)
+}
+
+/// Longest duration accepted, so its millis fit the `i64` times they are
+/// subtracted from.
+const MAX_DURATION_SECS: u64 = i64::MAX as u64 / 1_000;
+
+/// A duration such as `90s`, `15m`, `2h`, `1d` or `1h30m`.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration {text:?}: expected e.g. 30s, 15m, 2h or 1d");
+    let mut total = 0u64;
+    let mut rest = text.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let (number, tail) = rest.split_at(split);
+        let number: u64 = number.parse().map_err(|_| invalid())?;
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let secs = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3_600,
+            "d" => 86_400,
+            _ => return Err(invalid()),
+        };
+        total = number
+            .checked_mul(secs)
+            .and_then(|secs| total.checked_add(secs))
+            .filter(|&total| total <= MAX_DURATION_SECS)
+            .ok_or_else(invalid)?;
+        rest = tail;
+    }
+    Ok(Duration::from_secs(total))
+}
+
+fn parse_level(text: &str) -> Result<LogLevel, String> {
+    LogLevel::from_name(text)
+        .ok_or_else(|| format!("unknown level {text:?}: expected error, warn, info or debug"))
+}
+
+/// Report Insights status and retries on stderr while a request runs, when
+/// stderr is a terminal.
+fn progress_reporter() -> Option<Sender<Progress>> {
+    if !io::stderr().is_terminal() {
+        return None;
+    }
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        for line in rx.iter().filter_map(|event| describe_progress(&event)) {
+            eprintln!("{line}");
+        }
+    });
+    Some(tx)
+}
+
+fn describe_progress(event: &Progress) -> Option<String> {
+    match event {
+        Progress::Page { .. } | Progress::Refreshing => None,
+        Progress::Insights {
+            status,
+            records_matched,
+            records_scanned,
+            bytes_scanned,
+        } => Some(format!(
+            "{status}: {records_matched} of {records_scanned} records matched, {:.1} MB scanned",
+            bytes_scanned / 1_000_000.0
+        )),
+        Progress::Retrying {
+            attempt,
+            delay,
+            reason,
+        } => Some(format!("{reason}, retry {attempt} in {}s", delay.as_secs())),
+    }
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Writes the events that pass the level and query filters.
+struct Printer<W: Write> {
+    out: W,
+    format: OutputFormat,
+    color: bool,
+    min_level: Option<LogLevel>,
+    use_local_time: bool,
+    /// Prefix text lines with the log group when reading several.
+    show_group: bool,
+    query: Option<Query>,
+    parsers: ParserRegistry,
+    csv: ExportOptions,
+}
+
+impl<W: Write> Printer<W> {
+    fn new(out: W, global: &GlobalArgs, source: &SourceArgs) -> Result<Self, CliError> {
+        let query = match &source.query {
+            Some(text) => Query::parse(text)?,
+            None => None,
+        };
+        Ok(Self {
+            out,
+            format: global.output,
+            color: global.color.enabled(),
+            min_level: global.level,
+            use_local_time: global.local_time,
+            show_group: source.groups.len() > 1,
+            query,
+            parsers: ParserRegistry::default(),
+            csv: ExportOptions {
+                format: ExportFormat::Csv,
+                csv_columns: vec![
+                    CsvColumn::Timestamp,
+                    CsvColumn::LogGroup,
+                    CsvColumn::LogStream,
+                    CsvColumn::Message,
+                ],
+                use_local_time: global.local_time,
+            },
+        })
+    }
+
+    fn header(&mut self) -> io::Result<()> {
+        if self.format == OutputFormat::Csv {
+            write_header(&mut self.out, &self.csv)?;
+        }
+        Ok(())
+    }
+
+    /// Write `entry` if it passes the filters; returns whether it did.
+    fn entry(&mut self, entry: &LogEntry) -> io::Result<bool> {
+        let (parsed, level) = self.parsers.parse_with_level(&entry.message);
+        if let Some(min) = self.min_level
+            && (level == LogLevel::Unknown || level > min)
+        {
+            return Ok(false);
+        }
+        if let Some(query) = &self.query
+            && !query.matches(&QueryTarget {
+                entry,
+                search_text: &strip_ansi(&entry.message).to_lowercase(),
+                level,
+                parsed: parsed.as_ref(),
+            })
+        {
+            return Ok(false);
+        }
+
+        match self.format {
+            OutputFormat::Json => {
+                let mut object = entry_to_json(entry);
+                object["level"] = json!(level.label());
+                serde_json::to_writer(&mut self.out, &object)?;
+                self.out.write_all(b"\n")?;
+            }
+            OutputFormat::Csv => write_entry(&mut self.out, entry, &self.csv)?,
+            OutputFormat::Text => self.text_line(entry, level)?,
+        }
+        Ok(true)
+    }
+
+    /// `<time> <LEVEL> [<group> <stream>] <message>`.
+    fn text_line(&mut self, entry: &LogEntry, level: LogLevel) -> io::Result<()> {
+        let ts = format_timestamp_millis(entry.timestamp_millis, self.use_local_time);
+        let label = format!("{:<5}", level.label());
+        let label = match (self.color, level_color(level)) {
+            (true, Some(code)) => format!("\x1b[{code}m{label}\x1b[0m"),
+            _ => label,
+        };
+
+        let mut source = Vec::new();
+        if self.show_group {
+            source.extend(entry.log_group_name.as_deref());
+        }
+        source.extend(entry.log_stream_name.as_deref());
+        let source = if source.is_empty() {
+            String::new()
+        } else {
+            format!("[{}] ", source.join(" "))
+        };
+
+        let message = entry.message.trim_end();
+        let message = if self.color {
+            message.into()
+        } else {
+            strip_ansi(message)
+        };
+        writeln!(self.out, "{ts} {label} {source}{message}")
+    }
+}
+
+/// SGR color code of a level label.
+fn level_color(level: LogLevel) -> Option<&'static str> {
+    match level {
+        LogLevel::Error => Some("31"),
+        LogLevel::Warn => Some("33"),
+        LogLevel::Info => Some("32"),
+        LogLevel::Debug => Some("2"),
+        LogLevel::Unknown => None,
+    }
+}
+
+fn write_groups<W: Write>(out: &mut W, format: OutputFormat, groups: &[String]) -> io::Result<()> {
+    if format == OutputFormat::Csv {
+        writeln!(out, "logGroupName")?;
+    }
+    for group in groups {
+        match format {
+            OutputFormat::Json => writeln!(out, "{}", json!({ "logGroupName": group }))?,
+            OutputFormat::Csv => write_csv_row(out, std::slice::from_ref(group))?,
+            OutputFormat::Text => writeln!(out, "{group}")?,
+        }
+    }
+    out.flush()
+}
+
+fn write_streams<W: Write>(
+    out: &mut W,
+    global: &GlobalArgs,
+    streams: &[LogStreamInfo],
+) -> io::Result<()> {
+    let time = |ts: Option<i64>| {
+        ts.map(|ts| format_timestamp_millis(ts, global.local_time))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    if global.output == OutputFormat::Csv {
+        writeln!(out, "logStreamName,firstEventTime,lastEventTime")?;
+    }
+    for stream in streams {
+        match global.output {
+            OutputFormat::Json => writeln!(
+                out,
+                "{}",
+                json!({
+                    "logStreamName": stream.name,
+                    "firstEventTimestamp": stream.first_event_millis,
+                    "lastEventTimestamp": stream.last_event_millis,
+                })
+            )?,
+            OutputFormat::Csv => write_csv_row(
+                out,
+                &[
+                    stream.name.clone(),
+                    time(stream.first_event_millis),
+                    time(stream.last_event_millis),
+                ],
+            )?,
+            OutputFormat::Text => {
+                writeln!(out, "{}  {}", time(stream.last_event_millis), stream.name)?
+            }
+        }
+    }
+    out.flush()
+}
+
+/// Insights rows; CSV columns are the fields of the first row.
+fn write_insights<W: Write>(
+    out: &mut W,
+    format: OutputFormat,
+    rows: &[InsightsRow],
+) -> io::Result<()> {
+    let columns: Vec<String> = rows
+        .first()
+        .map(|row| row.iter().map(|(field, _)| field.clone()).collect())
+        .unwrap_or_default();
+    if format == OutputFormat::Csv {
+        write_csv_row(out, &columns)?;
+    }
+    for row in rows {
+        match format {
+            OutputFormat::Json => {
+                let object: Map<String, JsonValue> = row
+                    .iter()
+                    .map(|(field, value)| (field.clone(), json!(value)))
+                    .collect();
+                writeln!(out, "{}", JsonValue::Object(object))?;
+            }
+            OutputFormat::Csv => {
+                let values: Vec<String> = columns
+                    .iter()
+                    .map(|column| {
+                        row.iter()
+                            .find(|(field, _)| field == column)
+                            .map(|(_, value)| value.clone())
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                write_csv_row(out, &values)?;
+            }
+            OutputFormat::Text => {
+                let pairs: Vec<String> = row
+                    .iter()
+                    .map(|(field, value)| format!("{field}={value}"))
+                    .collect();
+                writeln!(out, "{}", pairs.join("  "))?;
+            }
+        }
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ts: i64, stream: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_millis: ts,
+            message: message.to_string(),
+            log_stream_name: Some(stream.to_string()),
+            log_group_name: Some("/aws/lambda/api".to_string()),
+            event_id: None,
+            ingestion_time_millis: None,
+        }
+    }
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("axe").chain(args.iter().copied())).expect("valid args")
+    }
+
+    fn print(args: &[&str], entries: &[LogEntry]) -> String {
+        let cli = parse(args);
+        let Some(Command::Fetch { source, .. }) = &cli.command else {
+            panic!("expected fetch");
+        };
+        let mut out = Vec::new();
+        let mut printer = Printer::new(&mut out, &cli.global, source).unwrap();
+        printer.header().unwrap();
+        for entry in entries {
+            printer.entry(entry).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("15m"), Ok(Duration::from_secs(900)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5_400)));
+        assert_eq!(parse_duration("1d"), Ok(Duration::from_secs(86_400)));
+        assert!(parse_duration("15").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("2w").is_err());
+        assert!(parse_duration("99999999999999999d").is_err());
+        assert!(parse_duration("9223372036854775s").is_ok());
+        assert!(parse_duration("9223372036854776s").is_err());
+    }
+
+    #[test]
+    fn resolves_ranges() {
+        let now = 1_000_000_000;
+        let range = |since: Option<u64>, start: Option<&str>, end: Option<&str>| {
+            RangeArgs {
+                since: since.map(Duration::from_secs),
+                start: start.map(str::to_string),
+                end: end.map(str::to_string),
+            }
+            .resolve(now, false)
+        };
+
+        assert_eq!(range(None, None, None).unwrap(), (now - 300_000, now));
+        assert_eq!(range(Some(60), None, None).unwrap(), (now - 60_000, now));
+        assert_eq!(
+            range(
+                None,
+                Some("2026-10-18T10:00:00Z"),
+                Some("2026-10-18 10:05:00")
+            )
+            .unwrap(),
+            (1_792_317_600_000, 1_792_317_900_000)
+        );
+        // An offset wins over `local`, also when it is negative.
+        assert_eq!(
+            parse_time("2026-10-18T05:00:00-05:00", true).unwrap(),
+            1_792_317_600_000
+        );
+        assert_eq!(
+            parse_time("2026-10-18 10:05", false).unwrap(),
+            1_792_317_900_000
+        );
+        assert!(matches!(
+            range(None, Some("yesterday"), None),
+            Err(CliError::InvalidTime(_))
+        ));
+        assert!(matches!(
+            range(
+                None,
+                Some("2026-10-18 10:05:00"),
+                Some("2026-10-18 10:00:00")
+            ),
+            Err(CliError::EmptyRange)
+        ));
+    }
+
+    #[test]
+    fn parses_subcommands_and_global_flags() {
+        let cli = parse(&[
+            "fetch", "-g", "/a", "-g", "/b", "--since", "2h", "-o", "json",
+        ]);
+        assert_eq!(cli.global.output, OutputFormat::Json);
+        match cli.command {
+            Some(Command::Fetch { source, range, .. }) => {
+                assert_eq!(source.groups, vec!["/a", "/b"]);
+                assert_eq!(range.since, Some(Duration::from_secs(7_200)));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let cli = parse(&["--level", "warning", "tail", "-g", "/a", "--interval", "5s"]);
+        assert_eq!(cli.global.level, Some(LogLevel::Warn));
+        assert!(
+            matches!(cli.command, Some(Command::Tail { interval, .. }) if interval == Duration::from_secs(5))
+        );
+
+        assert!(parse(&[]).command.is_none());
+        assert!(Cli::try_parse_from(["axe", "fetch"]).is_err());
+        assert!(
+            Cli::try_parse_from(["axe", "fetch", "-g", "/a", "--end", "2026-10-18 10:00:00"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["axe", "--level", "loud", "groups"]).is_err());
+        assert!(Cli::try_parse_from(["axe", "groups", "--limit", "0"]).is_err());
+        assert!(Cli::try_parse_from(["axe", "fetch", "-g", "/a", "--limit", "-5"]).is_err());
+    }
+
+    #[test]
+    fn prints_text_filtered_by_level_and_query() {
+        let entries = [
+            entry(1_000, "s1", r#"{"level":"info","status":200}"#),
+            entry(2_000, "s1", r#"{"level":"error","status":500}"#),
+            entry(3_000, "s2", "\u{1b}[33mWARN\u{1b}[0m disk almost full"),
+            entry(4_000, "s2", "no marker"),
+        ];
+
+        let text = print(
+            &["fetch", "-g", "/a", "--color", "never", "--level", "warn"],
+            &entries,
+        );
+        assert_eq!(
+            text,
+            "1970-01-01 00:00:02.000Z ERROR [s1] {\"level\":\"error\",\"status\":500}\n\
+             1970-01-01 00:00:03.000Z WARN  [s2] WARN disk almost full\n"
+        );
+
+        let text = print(
+            &[
+                "fetch",
+                "-g",
+                "/a",
+                "--color",
+                "always",
+                "-q",
+                "status>=500",
+            ],
+            &entries,
+        );
+        assert_eq!(text.lines().count(), 1);
+        assert!(text.contains("\u{1b}[31mERROR\u{1b}[0m"));
+    }
+
+    #[test]
+    fn prints_json_and_csv() {
+        let entries = [entry(1_000, "s1", "ERROR boom, again")];
+
+        let json = print(&["fetch", "-g", "/a", "-o", "json"], &entries);
+        let value: JsonValue = serde_json::from_str(json.trim()).unwrap();
+        assert_eq!(value["level"], "ERROR");
+        assert_eq!(value["logStreamName"], "s1");
+
+        let csv = print(&["fetch", "-g", "/a", "-o", "csv"], &entries);
+        assert_eq!(
+            csv,
+            "timestamp,logGroupName,logStreamName,message\n\
+             1970-01-01 00:00:01.000Z,/aws/lambda/api,s1,\"ERROR boom, again\"\n"
+        );
+    }
+
+    #[test]
+    fn writes_insights_rows() {
+        let rows = vec![
+            vec![
+                ("bin".to_string(), "10:00".to_string()),
+                ("count()".to_string(), "3".to_string()),
+            ],
+            vec![("bin".to_string(), "10:05".to_string())],
+        ];
+        let mut out = Vec::new();
+        write_insights(&mut out, OutputFormat::Csv, &rows).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "bin,count()\n10:00,3\n10:05,\n"
+        );
+
+        let mut out = Vec::new();
+        write_insights(&mut out, OutputFormat::Text, &rows).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "bin=10:00  count()=3\nbin=10:05\n"
+        );
+    }
+
+    #[test]
+    fn describes_insights_status_and_retries_but_not_pages() {
+        let insights = Progress::Insights {
+            status: "Running".to_string(),
+            records_matched: 3.0,
+            records_scanned: 120.0,
+            bytes_scanned: 2_500_000.0,
+        };
+        assert_eq!(
+            describe_progress(&insights).as_deref(),
+            Some("Running: 3 of 120 records matched, 2.5 MB scanned")
+        );
+
+        let retry = Progress::Retrying {
+            attempt: 2,
+            delay: Duration::from_secs(2),
+            reason: "ThrottlingException".to_string(),
+        };
+        assert_eq!(
+            describe_progress(&retry).as_deref(),
+            Some("ThrottlingException, retry 2 in 2s")
+        );
+        assert_eq!(
+            describe_progress(&Progress::Page {
+                entries: vec![entry(1, "s", "m")]
+            }),
+            None
+        );
+    }
+}
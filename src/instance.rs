@@ -0,0 +1,329 @@
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::launch::LaunchRequest;
+
+/// Application name, also naming the eframe storage directory.
+pub const APP_NAME: &str = "Lumberjack Axe";
+
+/// The running instance's address file, in the storage directory.
+const ADDRESS_FILE: &str = "instance";
+
+/// How long a launch waits on the running instance.
+const HANDOFF_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Longest request line read; links are a few hundred bytes.
+const MAX_LINE: u64 = 64 * 1024;
+
+/// The first instance listens on a loopback port for launch requests of later
+/// ones. The port and a random token are written to the address file, which
+/// only the user can read (on Windows it lies in the user's profile), so
+/// only the same user can pass requests. The file is removed on exit.
+pub enum Instance {
+    /// Start the app; launch requests arrive on the receiver.
+    Start(LaunchReceiver),
+    /// The request was passed to the running instance.
+    Forwarded,
+}
+
+/// Hand `request` to the running instance, or start and listen for the
+/// requests of later launches. Without a request a second window starts,
+/// leaving the running instance to listen.
+pub fn claim(request: Option<&LaunchRequest>) -> Instance {
+    let path = address_path();
+    let running = path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| parse_address(&contents));
+
+    if let Some((port, token)) = &running {
+        match request {
+            Some(request) if forward(*port, token, request).is_ok() => {
+                return Instance::Forwarded;
+            }
+            None if TcpStream::connect((Ipv4Addr::LOCALHOST, *port)).is_ok() => {
+                return Instance::Start(LaunchReceiver::new(None));
+            }
+            _ => {}
+        }
+    }
+
+    let mut receiver = LaunchReceiver::new(request);
+    if let Some(path) = path {
+        match listen(
+            &path,
+            receiver.sender.clone(),
+            Arc::clone(&receiver.repaint),
+        ) {
+            Ok(contents) => receiver.address = Some((path, contents)),
+            Err(e) => eprintln!("[axe] Failed to listen for launch requests: {e}"),
+        }
+    }
+    Instance::Start(receiver)
+}
+
+fn address_path() -> Option<PathBuf> {
+    eframe::storage_dir(APP_NAME).map(|dir| dir.join(ADDRESS_FILE))
+}
+
+/// `"<port> <token>"`.
+fn parse_address(contents: &str) -> Option<(u16, String)> {
+    let (port, token) = contents.trim().split_once(' ')?;
+    Some((port.parse().ok()?, token.to_string()))
+}
+
+fn forward(port: u16, token: &str, request: &LaunchRequest) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))?;
+    stream.set_read_timeout(Some(HANDOFF_TIMEOUT))?;
+    writeln!(stream, "{token} {}", request.to_url())?;
+
+    // Wait for the acknowledgement, so a stale port is not mistaken for
+    // the running instance.
+    let mut ack = String::new();
+    BufReader::new(stream).read_line(&mut ack)?;
+    if ack.trim() == "ok" {
+        Ok(())
+    } else {
+        Err(std::io::Error::other("launch request refused"))
+    }
+}
+
+/// Listen for launch requests, returning the contents written to the
+/// address file at `path`.
+fn listen(
+    path: &Path,
+    sender: Sender<LaunchRequest>,
+    repaint: Arc<OnceLock<egui::Context>>,
+) -> std::io::Result<String> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let port = listener.local_addr()?.port();
+    let token = new_token()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let contents = format!("{port} {token}");
+    write_private(path, &contents)?;
+
+    let token = Arc::new(token);
+    thread::spawn(move || {
+        // One thread per connection, so a slow client holds up no other.
+        for stream in listener.incoming().flatten() {
+            let (token, sender, repaint) =
+                (Arc::clone(&token), sender.clone(), Arc::clone(&repaint));
+            thread::spawn(move || {
+                if let Some(request) = accept(stream, &token)
+                    && sender.send(request).is_ok()
+                    && let Some(ctx) = repaint.get()
+                {
+                    ctx.request_repaint();
+                }
+            });
+        }
+    });
+    Ok(contents)
+}
+
+/// 128 bits from the OS random generator, in hex.
+fn new_token() -> std::io::Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| std::io::Error::other(e.to_string()))?;
+    Ok(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Write `contents` to a new file at `path` that only the user can read.
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    // Replace rather than truncate, so the permissions of an old file go too.
+    let _ = std::fs::remove_file(path);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+/// Read one `"<token> <link>"` line and acknowledge a valid request.
+fn accept(mut stream: TcpStream, token: &str) -> Option<LaunchRequest> {
+    stream.set_read_timeout(Some(HANDOFF_TIMEOUT)).ok()?;
+    let mut line = String::new();
+    BufReader::new((&stream).take(MAX_LINE))
+        .read_line(&mut line)
+        .ok()?;
+
+    let (given, link) = line.trim().split_once(' ')?;
+    if !same_token(given, token) {
+        return None;
+    }
+    match LaunchRequest::from_url(link) {
+        Ok(request) => {
+            let _ = writeln!(stream, "ok");
+            Some(request)
+        }
+        Err(e) => {
+            let _ = writeln!(stream, "error: {e}");
+            None
+        }
+    }
+}
+
+/// Compare tokens without stopping at the first difference, so the time
+/// taken does not tell how much of a guess was right.
+fn same_token(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Launch requests for the app: the one of the command line, then those
+/// passed by later instances.
+pub struct LaunchReceiver {
+    sender: Sender<LaunchRequest>,
+    receiver: Receiver<LaunchRequest>,
+    repaint: Arc<OnceLock<egui::Context>>,
+    /// The address file this instance wrote and its contents, removed on
+    /// exit.
+    address: Option<(PathBuf, String)>,
+}
+
+impl LaunchReceiver {
+    pub fn new(initial: Option<&LaunchRequest>) -> Self {
+        let (sender, receiver) = channel();
+        if let Some(request) = initial {
+            let _ = sender.send(request.clone());
+        }
+        Self {
+            sender,
+            receiver,
+            repaint: Arc::new(OnceLock::new()),
+            address: None,
+        }
+    }
+
+    /// Wake the UI when a request arrives, even while hidden to the tray.
+    pub fn set_repaint_context(&self, ctx: &egui::Context) {
+        let _ = self.repaint.set(ctx.clone());
+    }
+
+    pub fn try_recv(&self) -> Option<LaunchRequest> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl Drop for LaunchReceiver {
+    fn drop(&mut self) {
+        // Leave the file alone if another instance has taken over since.
+        if let Some((path, contents)) = &self.address
+            && std::fs::read_to_string(path).is_ok_and(|current| current == *contents)
+        {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_address_file() {
+        assert_eq!(
+            parse_address("40123 00ff00ff00ff00ff\n"),
+            Some((40123, "00ff00ff00ff00ff".to_string()))
+        );
+        assert_eq!(parse_address("garbage"), None);
+        assert_eq!(parse_address("99999 token"), None);
+    }
+
+    #[test]
+    fn forwards_requests_with_the_token() {
+        let path = std::env::temp_dir().join(format!("axe-instance-{}", std::process::id()));
+        let receiver = LaunchReceiver::new(None);
+        listen(
+            &path,
+            receiver.sender.clone(),
+            Arc::clone(&receiver.repaint),
+        )
+        .unwrap();
+        let (port, token) = parse_address(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let request = LaunchRequest {
+            group: "/aws/lambda/api".to_string(),
+            tail: true,
+            ..LaunchRequest::default()
+        };
+        assert!(forward(port, "wrong", &request).is_err());
+        forward(port, &token, &request).unwrap();
+
+        assert_eq!(receiver.receiver.recv_timeout(HANDOFF_TIMEOUT), Ok(request));
+        assert_eq!(receiver.try_recv(), None);
+    }
+
+    #[test]
+    fn slow_or_oversized_requests_hold_up_no_other() {
+        let path = std::env::temp_dir().join(format!("axe-slow-{}", std::process::id()));
+        let receiver = LaunchReceiver::new(None);
+        listen(
+            &path,
+            receiver.sender.clone(),
+            Arc::clone(&receiver.repaint),
+        )
+        .unwrap();
+        let (port, token) = parse_address(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let _idle = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        let mut oversized = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        oversized.set_read_timeout(Some(HANDOFF_TIMEOUT)).unwrap();
+        oversized.write_all(&vec![b'a'; MAX_LINE as usize]).unwrap();
+
+        forward(port, &token, &LaunchRequest::default()).unwrap();
+        let mut reply = String::new();
+        assert_eq!(oversized.read_to_string(&mut reply).ok(), Some(0));
+
+        assert!(same_token(&token, &token));
+        assert!(!same_token("00", &token));
+        assert!(!same_token(&token.replace(|_| true, "0"), &token));
+    }
+
+    #[test]
+    fn address_file_is_private_and_removed_on_exit() {
+        let path = std::env::temp_dir().join(format!("axe-address-{}", std::process::id()));
+        std::fs::write(&path, "stale").unwrap();
+
+        let mut receiver = LaunchReceiver::new(None);
+        let contents = listen(
+            &path,
+            receiver.sender.clone(),
+            Arc::clone(&receiver.repaint),
+        )
+        .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+        let (_, token) = parse_address(&contents).unwrap();
+        assert_eq!(token.len(), 32);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        receiver.address = Some((path.clone(), contents));
+        drop(receiver);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,415 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::Url;
+
+use crate::app::tab::{DEFAULT_LOOKBACK, Tab};
+use crate::cli::{GlobalArgs, OpenArgs, parse_duration};
+use crate::local_logs::parse_timestamp_millis;
+
+/// Scheme of deep links, e.g.
+/// `axe://open?group=/aws/lambda/api&filter=ERROR&since=15m&tail=1`.
+pub const SCHEME: &str = "axe";
+
+/// A query to open in a new tab, from the command line, a deep link or a
+/// saved-query file. Empty fields are left as in the active tab (profile,
+/// region) or unset.
+///
+/// Saved-query files are JSON objects with the keys of the link, or a link.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LaunchRequest {
+    pub title: String,
+    pub profile: String,
+    pub region: String,
+    pub group: String,
+    /// CloudWatch filter pattern.
+    pub filter: String,
+    /// Local query.
+    pub query: String,
+    /// A lookback such as `15m`, or a start time.
+    pub since: String,
+    /// An end time.
+    pub until: String,
+    pub tail: bool,
+}
+
+/// The range a launch request fetches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchRange {
+    Lookback(Duration),
+    /// `(start, end)` in epoch millis.
+    Absolute(i64, i64),
+}
+
+#[derive(Debug, Error)]
+pub enum LaunchError {
+    #[error("invalid link {link:?}: {reason}")]
+    InvalidLink { link: String, reason: String },
+
+    #[error("unknown link parameter {0:?}")]
+    UnknownParameter(String),
+
+    #[error("failed to read saved query {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("invalid saved query {path}: {source}")]
+    Parse {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("invalid time {0:?}: expected e.g. 15m, 2026-10-18T10:00:00Z or epoch millis")]
+    InvalidTime(String),
+
+    #[error(
+        "a tail follows the latest events: give it a lookback such as 15m, not a start or end time"
+    )]
+    TailWithRange,
+}
+
+impl LaunchRequest {
+    /// The request of the GUI arguments: the link or file, if any, with the
+    /// flags taking precedence.
+    pub fn from_args(global: &GlobalArgs, args: OpenArgs) -> Result<Option<Self>, LaunchError> {
+        let mut request = match &args.target {
+            Some(target) => Self::from_target(target)?,
+            None => Self::default(),
+        };
+        let flags = [
+            (&mut request.profile, global.profile.clone()),
+            (&mut request.region, global.region.clone()),
+            (&mut request.group, args.group),
+            (&mut request.filter, args.filter),
+            (&mut request.query, args.query),
+            (&mut request.since, args.since),
+            (&mut request.until, args.until),
+        ];
+        for (field, flag) in flags {
+            if let Some(value) = flag {
+                *field = value;
+            }
+        }
+        request.tail |= args.tail;
+
+        if request == Self::default() {
+            return Ok(None);
+        }
+        // Report bad times now rather than in the tab.
+        request.range(0)?;
+        Ok(Some(request))
+    }
+
+    /// The request reopening `tab`'s query, e.g. to share it as a link.
+    pub fn for_tab(tab: &Tab) -> Self {
+        let view = &tab.logs_view;
+        let (since, until) = match view.fetch_range {
+            Some((start, end)) => (start.to_string(), end.to_string()),
+            None => (String::new(), String::new()),
+        };
+        Self {
+            title: tab.title.clone(),
+            profile: view.profile.clone(),
+            region: view.region.clone(),
+            group: view.log_group.clone(),
+            filter: view.filter_text.clone(),
+            query: view.query_text.clone(),
+            since,
+            until,
+            tail: view.tail_mode,
+        }
+    }
+
+    /// A deep link, or the path of a saved-query file.
+    pub fn from_target(target: &str) -> Result<Self, LaunchError> {
+        if target.starts_with(&format!("{SCHEME}:")) {
+            Self::from_url(target)
+        } else {
+            Self::load(Path::new(target))
+        }
+    }
+
+    pub fn from_url(link: &str) -> Result<Self, LaunchError> {
+        let invalid = |reason: &str| LaunchError::InvalidLink {
+            link: link.to_string(),
+            reason: reason.to_string(),
+        };
+        let url = Url::parse(link.trim()).map_err(|e| invalid(&e.to_string()))?;
+        if url.scheme() != SCHEME {
+            return Err(invalid("not an axe:// link"));
+        }
+        if !matches!(url.host_str(), Some("open") | None) {
+            return Err(invalid("expected axe://open?…"));
+        }
+
+        let mut request = Self::default();
+        for (key, value) in url.query_pairs() {
+            let value = value.into_owned();
+            match key.as_ref() {
+                "title" => request.title = value,
+                "profile" => request.profile = value,
+                "region" => request.region = value,
+                "group" => request.group = value,
+                "filter" => request.filter = value,
+                "query" => request.query = value,
+                "since" => request.since = value,
+                "until" => request.until = value,
+                "tail" => request.tail = !matches!(value.as_str(), "0" | "false" | "no"),
+                other => return Err(LaunchError::UnknownParameter(other.to_string())),
+            }
+        }
+        Ok(request)
+    }
+
+    /// Read a saved-query file: JSON, or a single link.
+    pub fn load(path: &Path) -> Result<Self, LaunchError> {
+        let display = path.display().to_string();
+        let text = std::fs::read_to_string(path).map_err(|source| LaunchError::Read {
+            path: display.clone(),
+            source,
+        })?;
+        let text = text.trim();
+        if text.starts_with(&format!("{SCHEME}:")) {
+            return Self::from_url(text);
+        }
+        serde_json::from_str(text).map_err(|source| LaunchError::Parse {
+            path: display,
+            source,
+        })
+    }
+
+    /// The deep link opening this request; empty fields are left out.
+    pub fn to_url(&self) -> String {
+        let mut url = Url::parse(&format!("{SCHEME}://open")).expect("valid base link");
+        {
+            let mut query = url.query_pairs_mut();
+            let fields = [
+                ("title", &self.title),
+                ("profile", &self.profile),
+                ("region", &self.region),
+                ("group", &self.group),
+                ("filter", &self.filter),
+                ("query", &self.query),
+                ("since", &self.since),
+                ("until", &self.until),
+            ];
+            for (key, value) in fields {
+                if !value.is_empty() {
+                    query.append_pair(key, value);
+                }
+            }
+            if self.tail {
+                query.append_pair("tail", "1");
+            }
+        }
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+        url.to_string()
+    }
+
+    /// The range to fetch as of `now` (epoch millis); `None` for the default
+    /// lookback. A tail takes a lookback only.
+    pub fn range(&self, now: i64) -> Result<Option<LaunchRange>, LaunchError> {
+        let since = self.since.trim();
+        let until = match self.until.trim() {
+            "" => None,
+            text => Some(parse_instant(text)?),
+        };
+        let range = match (since, until) {
+            ("", None) => return Ok(None),
+            ("", Some(end)) => {
+                LaunchRange::Absolute(end - DEFAULT_LOOKBACK.as_millis() as i64, end)
+            }
+            (since, until) => match parse_duration(since) {
+                Ok(lookback) => match until {
+                    None => LaunchRange::Lookback(lookback),
+                    Some(end) => LaunchRange::Absolute(end - lookback.as_millis() as i64, end),
+                },
+                Err(_) => LaunchRange::Absolute(parse_instant(since)?, until.unwrap_or(now)),
+            },
+        };
+        if self.tail && matches!(range, LaunchRange::Absolute(..)) {
+            return Err(LaunchError::TailWithRange);
+        }
+        Ok(Some(range))
+    }
+}
+
+/// Epoch millis, an RFC 3339 time or a naive UTC time.
+fn parse_instant(text: &str) -> Result<i64, LaunchError> {
+    if !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) {
+        return text
+            .parse()
+            .map_err(|_| LaunchError::InvalidTime(text.to_string()));
+    }
+    parse_timestamp_millis(text).ok_or_else(|| LaunchError::InvalidTime(text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    use crate::cli::Cli;
+
+    #[test]
+    fn links_round_trip() {
+        let request = LaunchRequest {
+            group: "/aws/lambda/api".to_string(),
+            filter: "\"request failed\"".to_string(),
+            query: "status>=500 AND level:error".to_string(),
+            since: "15m".to_string(),
+            tail: true,
+            ..LaunchRequest::default()
+        };
+        let link = request.to_url();
+        assert!(link.starts_with("axe://open?group=%2Faws%2Flambda%2Fapi&filter="));
+        assert_eq!(LaunchRequest::from_url(&link).unwrap(), request);
+        assert_eq!(LaunchRequest::default().to_url(), "axe://open");
+    }
+
+    #[test]
+    fn tabs_share_their_query_and_range() {
+        let mut tab = Tab::new(0, "api");
+        tab.logs_view.log_group = "/aws/lambda/api".to_string();
+        tab.logs_view.fetch_range = Some((1_000, 2_000));
+
+        let request = LaunchRequest::from_url(&LaunchRequest::for_tab(&tab).to_url()).unwrap();
+        assert_eq!(request.title, "api");
+        assert_eq!(request.group, "/aws/lambda/api");
+        assert_eq!(
+            request.range(0).unwrap(),
+            Some(LaunchRange::Absolute(1_000, 2_000))
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_and_misspelled_links() {
+        assert!(matches!(
+            LaunchRequest::from_url("https://open?group=a"),
+            Err(LaunchError::InvalidLink { .. })
+        ));
+        assert!(matches!(
+            LaunchRequest::from_url("axe://open?grop=a"),
+            Err(LaunchError::UnknownParameter(key)) if key == "grop"
+        ));
+        let request = LaunchRequest::from_url("axe://open?group=a&tail=0").unwrap();
+        assert!(!request.tail);
+    }
+
+    #[test]
+    fn loads_saved_query_files() {
+        let dir = std::env::temp_dir();
+        let json = dir.join(format!("axe-query-{}.json", std::process::id()));
+        std::fs::write(&json, r#"{"group": "/ecs/workers", "since": "1h"}"#).unwrap();
+        let link = dir.join(format!("axe-query-{}.txt", std::process::id()));
+        std::fs::write(&link, "axe://open?group=/ecs/workers&since=1h\n").unwrap();
+
+        let from_json = LaunchRequest::from_target(json.to_str().unwrap());
+        let from_link = LaunchRequest::from_target(link.to_str().unwrap());
+        let _ = std::fs::remove_file(&json);
+        let _ = std::fs::remove_file(&link);
+
+        let expected = LaunchRequest {
+            group: "/ecs/workers".to_string(),
+            since: "1h".to_string(),
+            ..LaunchRequest::default()
+        };
+        assert_eq!(from_json.unwrap(), expected);
+        assert_eq!(from_link.unwrap(), expected);
+        assert!(matches!(
+            LaunchRequest::from_target("/does/not/exist.json"),
+            Err(LaunchError::Read { .. })
+        ));
+    }
+
+    #[test]
+    fn resolves_ranges() {
+        let request = |since: &str, until: &str| LaunchRequest {
+            since: since.to_string(),
+            until: until.to_string(),
+            ..LaunchRequest::default()
+        };
+        let now = 10_000_000;
+
+        assert_eq!(request("", "").range(now).unwrap(), None);
+        assert_eq!(
+            request("15m", "").range(now).unwrap(),
+            Some(LaunchRange::Lookback(Duration::from_secs(900)))
+        );
+        assert_eq!(
+            request("1m", "5000000").range(now).unwrap(),
+            Some(LaunchRange::Absolute(4_940_000, 5_000_000))
+        );
+        assert_eq!(
+            request("1970-01-01T00:00:01Z", "").range(now).unwrap(),
+            Some(LaunchRange::Absolute(1_000, now))
+        );
+        assert_eq!(
+            request("", "600000").range(now).unwrap(),
+            Some(LaunchRange::Absolute(300_000, 600_000))
+        );
+        assert!(matches!(
+            request("soon", "").range(now),
+            Err(LaunchError::InvalidTime(_))
+        ));
+
+        let tail = |since: &str, until: &str| LaunchRequest {
+            tail: true,
+            ..request(since, until)
+        };
+        assert_eq!(
+            tail("15m", "").range(now).unwrap(),
+            Some(LaunchRange::Lookback(Duration::from_secs(900)))
+        );
+        assert!(matches!(
+            tail("15m", "5000000").range(now),
+            Err(LaunchError::TailWithRange)
+        ));
+        assert!(matches!(
+            tail("1970-01-01T00:00:01Z", "").range(now),
+            Err(LaunchError::TailWithRange)
+        ));
+    }
+
+    #[test]
+    fn flags_override_the_link() {
+        let cli = Cli::try_parse_from([
+            "axe",
+            "axe://open?group=/a&filter=ERROR&region=eu-west-1",
+            "--region",
+            "us-east-1",
+            "--since",
+            "2h",
+            "--tail",
+        ])
+        .unwrap();
+        assert!(cli.command.is_none());
+
+        let request = LaunchRequest::from_args(&cli.global, cli.open)
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.group, "/a");
+        assert_eq!(request.filter, "ERROR");
+        assert_eq!(request.region, "us-east-1");
+        assert_eq!(request.since, "2h");
+        assert!(request.tail);
+
+        let cli = Cli::try_parse_from(["axe"]).unwrap();
+        assert_eq!(
+            LaunchRequest::from_args(&cli.global, cli.open).unwrap(),
+            None
+        );
+
+        let cli = Cli::try_parse_from(["axe", "--group", "/a", "--until", "later"]).unwrap();
+        assert!(LaunchRequest::from_args(&cli.global, cli.open).is_err());
+    }
+}
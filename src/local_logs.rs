@@ -0,0 +1,464 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDateTime};
+use flate2::read::MultiGzDecoder;
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use thiserror::Error;
+
+use crate::aws::LogEntry;
+
+/// Default timestamp prefix for plain text logs: ISO-8601-ish dates with an
+/// optional fraction and offset, e.g. `2024-05-01 12:00:00,123`.
+pub const DEFAULT_TIMESTAMP_REGEX: &str =
+    r"^\[?(\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:[.,]\d+)?(?:Z|[+-]\d{2}:?\d{2})?)\]?\s*";
+
+/// How to interpret the lines of a local file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalFormat {
+    /// Sniff the first non-empty line.
+    Auto,
+    /// One JSON object per line (our own exports, or any structured logs).
+    /// A single JSON document with an `events` array (`aws logs
+    /// filter-log-events` output) is accepted too.
+    JsonLines,
+    /// CloudWatch export to S3: `<RFC3339 timestamp> <message>`.
+    CloudWatchExport,
+    /// `aws logs tail` output: `<timestamp> <stream> <message>`.
+    AwsLogsTail,
+    /// Free text; lines starting with the timestamp regex begin a new entry,
+    /// other lines are appended to the previous one.
+    PlainText,
+}
+
+impl LocalFormat {
+    pub const ALL: [LocalFormat; 5] = [
+        LocalFormat::Auto,
+        LocalFormat::JsonLines,
+        LocalFormat::CloudWatchExport,
+        LocalFormat::AwsLogsTail,
+        LocalFormat::PlainText,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LocalFormat::Auto => "Auto-detect",
+            LocalFormat::JsonLines => "JSON Lines",
+            LocalFormat::CloudWatchExport => "CloudWatch export",
+            LocalFormat::AwsLogsTail => "aws logs tail",
+            LocalFormat::PlainText => "Plain text",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalFileOptions {
+    pub format: LocalFormat,
+    /// Regex matching the timestamp prefix of plain text lines; the first
+    /// capture group (or the whole match) is parsed as the timestamp.
+    pub timestamp_regex: String,
+}
+
+impl Default for LocalFileOptions {
+    fn default() -> Self {
+        Self {
+            format: LocalFormat::Auto,
+            timestamp_regex: DEFAULT_TIMESTAMP_REGEX.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LocalLogError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("invalid timestamp regex: {0}")]
+    InvalidRegex(#[from] regex::Error),
+}
+
+/// Read a local log file (optionally gzip-compressed) into entries sorted by
+/// timestamp. The file path is used as the pseudo log group name.
+pub fn load_local_file(
+    path: &Path,
+    options: &LocalFileOptions,
+) -> Result<Vec<LogEntry>, LocalLogError> {
+    let to_err = |source| LocalLogError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+
+    let mut reader = BufReader::new(File::open(path).map_err(to_err)?);
+    let is_gzip = reader
+        .fill_buf()
+        .map_err(to_err)?
+        .starts_with(&[0x1f, 0x8b]);
+    let reader: Box<dyn BufRead> = if is_gzip {
+        Box::new(BufReader::new(MultiGzDecoder::new(reader)))
+    } else {
+        Box::new(reader)
+    };
+
+    parse_log_lines(reader, options, &source_name(path)).map_err(|e| match e {
+        LocalLogError::Io { source, .. } => to_err(source),
+        other => other,
+    })
+}
+
+/// Pseudo log group name for a local file.
+pub fn source_name(path: &Path) -> String {
+    format!("file://{}", path.display())
+}
+
+/// Parse log lines from any reader. `source` becomes the entries' group name.
+pub fn parse_log_lines<R: Read>(
+    reader: R,
+    options: &LocalFileOptions,
+    source: &str,
+) -> Result<Vec<LogEntry>, LocalLogError> {
+    // Invalid UTF-8 (e.g. binary garbage or Latin-1) is replaced, not fatal.
+    let mut bytes = Vec::new();
+    BufReader::new(reader)
+        .read_to_end(&mut bytes)
+        .map_err(|source| LocalLogError::Io {
+            path: String::new(),
+            source,
+        })?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let format = match options.format {
+        LocalFormat::Auto => detect_format(&text),
+        other => other,
+    };
+
+    let mut entries = match format {
+        LocalFormat::JsonLines => parse_json_lines(&text),
+        LocalFormat::CloudWatchExport => parse_timestamped_lines(&text, false),
+        LocalFormat::AwsLogsTail => parse_timestamped_lines(&text, true),
+        LocalFormat::PlainText | LocalFormat::Auto => {
+            let re = Regex::new(&options.timestamp_regex)?;
+            parse_plain_text(&text, &re)
+        }
+    };
+
+    for entry in &mut entries {
+        if entry.log_group_name.is_none() {
+            entry.log_group_name = Some(source.to_string());
+        }
+    }
+    entries.sort_by_key(|e| e.timestamp_millis);
+    Ok(entries)
+}
+
+/// Guess the format from the first non-empty line.
+pub fn detect_format(text: &str) -> LocalFormat {
+    let Some(first) = text.lines().map(str::trim).find(|l| !l.is_empty()) else {
+        return LocalFormat::PlainText;
+    };
+
+    if first.starts_with('{') {
+        return LocalFormat::JsonLines;
+    }
+
+    match first.split_once(' ') {
+        // `aws logs tail` prints offsets as `+00:00`, S3 exports use `Z`.
+        Some((ts, _)) if parse_timestamp_millis(ts).is_some() => {
+            if ts.ends_with("+00:00") {
+                LocalFormat::AwsLogsTail
+            } else {
+                LocalFormat::CloudWatchExport
+            }
+        }
+        _ => LocalFormat::PlainText,
+    }
+}
+
+/// Parse the timestamp formats seen in log files; naive times are UTC.
+pub fn parse_timestamp_millis(s: &str) -> Option<i64> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.timestamp_millis());
+    }
+    if let Ok(dt) = DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f%z") {
+        return Some(dt.timestamp_millis());
+    }
+
+    let normalized = s.replace(',', ".");
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(&normalized, fmt).ok())
+        .map(|dt| dt.and_utc().timestamp_millis())
+}
+
+fn parse_json_lines(text: &str) -> Vec<LogEntry> {
+    // Whole-document JSON such as `{"events": [...]}`.
+    if let Ok(JsonValue::Object(doc)) = serde_json::from_str::<JsonValue>(text)
+        && let Some(JsonValue::Array(events)) = doc.get("events")
+    {
+        return events.iter().map(json_to_entry).collect();
+    }
+
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|line| match serde_json::from_str::<JsonValue>(line) {
+            Ok(v @ JsonValue::Object(_)) => json_to_entry(&v),
+            _ => plain_entry(line),
+        })
+        .collect()
+}
+
+/// Map a JSON object to an entry, understanding our own export shape and
+/// the usual timestamp/message keys of structured loggers.
+fn json_to_entry(v: &JsonValue) -> LogEntry {
+    let str_field = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| v.get(*k).and_then(JsonValue::as_str))
+            .map(str::to_string)
+    };
+
+    let timestamp_millis = ["timestamp", "@timestamp", "time", "ts"]
+        .iter()
+        .find_map(|k| match v.get(*k)? {
+            JsonValue::Number(n) => n.as_i64(),
+            JsonValue::String(s) => parse_timestamp_millis(s),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    // If the object wraps a message, use it; otherwise the object *is* the message.
+    let message = match v.get("message").or_else(|| v.get("@message")) {
+        Some(JsonValue::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => v.to_string(),
+    };
+
+    LogEntry {
+        timestamp_millis,
+        message,
+        log_stream_name: str_field(&["logStreamName", "@logStream"]),
+        log_group_name: str_field(&["logGroupName", "@log"]),
+        event_id: str_field(&["eventId"]),
+        ingestion_time_millis: v.get("ingestionTime").and_then(JsonValue::as_i64),
+    }
+}
+
+fn parse_timestamped_lines(text: &str, has_stream: bool) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((ts, rest)) = line.split_once(' ') else {
+            entries.push(plain_entry(line));
+            continue;
+        };
+        let Some(timestamp_millis) = parse_timestamp_millis(ts) else {
+            // Continuation of a multi-line message.
+            match entries.last_mut() {
+                Some(last) => {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                }
+                None => entries.push(plain_entry(line)),
+            }
+            continue;
+        };
+
+        let (stream, message) = match rest.split_once(' ') {
+            Some((stream, message)) if has_stream => (Some(stream.to_string()), message),
+            _ => (None, rest),
+        };
+        entries.push(LogEntry {
+            timestamp_millis,
+            message: message.to_string(),
+            log_stream_name: stream,
+            ..plain_entry("")
+        });
+    }
+
+    entries
+}
+
+fn parse_plain_text(text: &str, timestamp_re: &Regex) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+
+    for line in text.lines() {
+        let parsed = timestamp_re.captures(line).and_then(|caps| {
+            let whole = caps.get(0)?;
+            let ts = caps.get(1).unwrap_or(whole).as_str();
+            Some((parse_timestamp_millis(ts)?, &line[whole.end()..]))
+        });
+
+        match (parsed, entries.last_mut()) {
+            (Some((timestamp_millis, message)), _) => entries.push(LogEntry {
+                timestamp_millis,
+                message: message.to_string(),
+                ..plain_entry("")
+            }),
+            (None, Some(last)) => {
+                last.message.push('\n');
+                last.message.push_str(line);
+            }
+            (None, None) if !line.trim().is_empty() => entries.push(plain_entry(line)),
+            (None, None) => {}
+        }
+    }
+
+    entries
+}
+
+fn plain_entry(message: &str) -> LogEntry {
+    LogEntry {
+        timestamp_millis: 0,
+        message: message.to_string(),
+        log_stream_name: None,
+        log_group_name: None,
+        event_id: None,
+        ingestion_time_millis: None,
+    }
+}
+
+/// The file behind a pseudo log group created by [`source_name`].
+pub fn local_path_from_group(log_group: &str) -> Option<PathBuf> {
+    log_group.strip_prefix("file://").map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn parse(text: &str, format: LocalFormat) -> Vec<LogEntry> {
+        let options = LocalFileOptions {
+            format,
+            ..LocalFileOptions::default()
+        };
+        parse_log_lines(text.as_bytes(), &options, "file:///tmp/x.log").expect("parses")
+    }
+
+    #[test]
+    fn detect_format_sniffs_first_line() {
+        assert_eq!(detect_format("\n{\"a\":1}\n"), LocalFormat::JsonLines);
+        assert_eq!(
+            detect_format("2024-01-01T00:00:00.000Z hello"),
+            LocalFormat::CloudWatchExport
+        );
+        assert_eq!(
+            detect_format("2024-01-01T00:00:00.123000+00:00 stream hello"),
+            LocalFormat::AwsLogsTail
+        );
+        assert_eq!(detect_format("hello world"), LocalFormat::PlainText);
+    }
+
+    #[test]
+    fn json_lines_round_trips_exports_and_generic_objects() {
+        let text = concat!(
+            r#"{"timestamp":2000,"message":"exported","logStreamName":"s","eventId":"e1","ingestionTime":2100}"#,
+            "\n",
+            r#"{"time":"1970-01-01T00:00:01Z","level":"info","msg":"structured"}"#,
+            "\n",
+        );
+        let entries = parse(text, LocalFormat::Auto);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].timestamp_millis, 1000);
+        assert!(entries[0].message.contains("\"msg\":\"structured\""));
+        assert_eq!(
+            entries[0].log_group_name.as_deref(),
+            Some("file:///tmp/x.log")
+        );
+
+        assert_eq!(entries[1].message, "exported");
+        assert_eq!(entries[1].log_stream_name.as_deref(), Some("s"));
+        assert_eq!(entries[1].event_id.as_deref(), Some("e1"));
+        assert_eq!(entries[1].ingestion_time_millis, Some(2100));
+    }
+
+    #[test]
+    fn json_document_with_events_array_is_accepted() {
+        let text = r#"{"events":[{"timestamp":5,"message":"a","logStreamName":"s1"}]}"#;
+        let entries = parse(text, LocalFormat::JsonLines);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message, "a");
+    }
+
+    #[test]
+    fn aws_logs_tail_lines_split_stream_and_message() {
+        let text = "2024-01-01T00:00:00.500000+00:00 my/stream ERROR boom\n  at foo\n";
+        let entries = parse(text, LocalFormat::Auto);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].timestamp_millis, 1_704_067_200_500);
+        assert_eq!(entries[0].log_stream_name.as_deref(), Some("my/stream"));
+        assert_eq!(entries[0].message, "ERROR boom\n  at foo");
+    }
+
+    #[test]
+    fn plain_text_uses_regex_and_folds_continuation_lines() {
+        let text =
+            "2024-01-01 00:00:01,250 INFO start\n2024-01-01 00:00:00 ERROR fail\nTraceback:\n  x\n";
+        let entries = parse(text, LocalFormat::PlainText);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "ERROR fail\nTraceback:\n  x");
+        assert_eq!(entries[1].timestamp_millis, 1_704_067_201_250);
+        assert_eq!(entries[1].message, "INFO start");
+    }
+
+    #[test]
+    fn invalid_utf8_is_replaced_instead_of_failing() {
+        let bytes = b"2024-01-01 10:00:00 caf\xe9 opened\n2024-01-01 10:00:01 ok\n";
+        let entries = parse_log_lines(&bytes[..], &LocalFileOptions::default(), "file://x")
+            .expect("lossy decoding");
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].message.contains("caf\u{fffd} opened"));
+    }
+
+    #[test]
+    fn invalid_timestamp_regex_is_reported() {
+        let options = LocalFileOptions {
+            format: LocalFormat::PlainText,
+            timestamp_regex: "(".to_string(),
+        };
+        let err = parse_log_lines("x".as_bytes(), &options, "f").unwrap_err();
+        assert!(err.to_string().contains("invalid timestamp regex"));
+    }
+
+    #[test]
+    fn source_name_round_trips_through_local_path() {
+        let path = Path::new("/var/log/app.log");
+        assert_eq!(
+            local_path_from_group(&source_name(path)).as_deref(),
+            Some(path)
+        );
+        assert_eq!(local_path_from_group("/aws/lambda/foo"), None);
+    }
+
+    #[test]
+    fn load_local_file_reads_gzip() {
+        let path = std::env::temp_dir().join(format!("axe-local-{}.log.gz", std::process::id()));
+        {
+            let file = File::create(&path).expect("create");
+            let mut gz = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            gz.write_all(b"2024-01-01T00:00:00.000Z hello\n")
+                .expect("write");
+            gz.finish().expect("finish");
+        }
+
+        let entries = load_local_file(&path, &LocalFileOptions::default());
+        let _ = std::fs::remove_file(&path);
+        let entries = entries.expect("loads");
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message, "hello");
+        assert_eq!(entries[0].log_group_name, Some(source_name(&path)));
+    }
+}
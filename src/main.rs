@@ -1,24 +1,59 @@
+use clap::Parser;
+#[cfg(feature = "gui")]
 use eframe::{NativeOptions, egui};
 
 mod app;
 mod aws;
+mod cli;
+#[cfg(feature = "gui")]
+mod instance;
+#[cfg(feature = "gui")]
+mod launch;
+#[cfg(feature = "gui")]
+mod local_logs;
+#[cfg(feature = "gui")]
 mod tray;
 mod worker;
 
+#[cfg(feature = "gui")]
 use crate::app::App;
+use crate::cli::Cli;
+#[cfg(feature = "gui")]
+use crate::instance::{APP_NAME, Instance, LaunchReceiver, claim};
+#[cfg(feature = "gui")]
+use crate::launch::LaunchRequest;
+#[cfg(feature = "gui")]
 use crate::tray::{TrayConfig, TrayEventReceiver, TrayHandle};
+#[cfg(feature = "gui")]
 use crate::worker::{WorkerHandle, spawn_worker};
 
+#[cfg(feature = "gui")]
 struct AppShared {
-    #[allow(dead_code)]
     tray_handle: TrayHandle,
-    #[allow(dead_code)]
     tray_events: TrayEventReceiver,
-    #[allow(dead_code)]
     worker_handle: WorkerHandle,
+    launches: LaunchReceiver,
 }
 
+#[cfg(feature = "gui")]
 fn main() -> eframe::Result<()> {
+    let cli = Cli::parse();
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(cli.global, command));
+    }
+
+    let request = match LaunchRequest::from_args(&cli.global, cli.open) {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("axe: {e}");
+            std::process::exit(2);
+        }
+    };
+    let launches = match claim(request.as_ref()) {
+        Instance::Start(launches) => launches,
+        Instance::Forwarded => return Ok(()),
+    };
+
     let worker_handle = spawn_worker();
     let tray_config = TrayConfig::default();
     let (tray_handle, tray_events) = TrayHandle::spawn(tray_config)
@@ -27,7 +62,8 @@ fn main() -> eframe::Result<()> {
     let shared = AppShared {
         tray_handle,
         tray_events,
-        worker_handle: worker_handle.clone(),
+        worker_handle,
+        launches,
     };
 
     let native_options = NativeOptions {
@@ -38,10 +74,27 @@ fn main() -> eframe::Result<()> {
     };
 
     eframe::run_native(
-        "Lumberjack Axe",
+        APP_NAME,
         native_options,
         Box::new(move |cc| {
-            Ok(Box::new(App::new(cc, shared.worker_handle.clone())) as Box<dyn eframe::App>)
+            Ok(Box::new(App::new(
+                cc,
+                shared.worker_handle,
+                shared.tray_handle,
+                shared.tray_events,
+                shared.launches,
+            )) as Box<dyn eframe::App>)
         }),
     )
 }
+
+/// Built without the GUI: only the subcommands run.
+#[cfg(not(feature = "gui"))]
+fn main() {
+    let cli = Cli::parse();
+    let Some(command) = cli.command else {
+        eprintln!("axe: built without the GUI; see `--help` for the commands");
+        std::process::exit(2);
+    };
+    std::process::exit(cli::run(cli.global, command));
+}
@@ -1,16 +1,30 @@
-use crossbeam_channel::{Receiver, unbounded};
+mod icons;
+
+use crossbeam_channel::{Receiver, Sender, unbounded};
+use eframe::egui;
 use std::fmt;
+use std::sync::{Arc, OnceLock};
 use tray_icon::{
-    ClickType, Icon, TrayIcon, TrayIconBuilder, TrayIconEvent,
-    menu::{Menu, MenuItem},
+    ClickType, TrayIcon, TrayIconBuilder, TrayIconEvent,
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
 };
 
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
+use icons::IconSet;
+pub use icons::TrayIconKind;
+
+/// Menu item IDs; favorites are `favorite:<index>`.
+const SHOW_HIDE_ID: &str = "show_hide";
+const TAIL_ID: &str = "tail";
+const QUIT_ID: &str = "quit";
+const FAVORITE_PREFIX: &str = "favorite:";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TrayEvent {
     ToggleWindow,
-    ShowWindow,
-    HideWindow,
+    /// Pause the tailing tabs, or resume the paused ones.
+    ToggleTail,
+    /// Open the favorite at this index of the menu's favorites.
+    OpenFavorite(usize),
     QuitRequested,
 }
 
@@ -18,13 +32,74 @@ impl fmt::Display for TrayEvent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TrayEvent::ToggleWindow => write!(f, "ToggleWindow"),
-            TrayEvent::ShowWindow => write!(f, "ShowWindow"),
-            TrayEvent::HideWindow => write!(f, "HideWindow"),
+            TrayEvent::ToggleTail => write!(f, "ToggleTail"),
+            TrayEvent::OpenFavorite(idx) => write!(f, "OpenFavorite({idx})"),
             TrayEvent::QuitRequested => write!(f, "QuitRequested"),
         }
     }
 }
 
+impl TrayEvent {
+    /// The event of a clicked menu item.
+    fn from_menu_id(id: &str) -> Option<Self> {
+        match id {
+            SHOW_HIDE_ID => Some(TrayEvent::ToggleWindow),
+            TAIL_ID => Some(TrayEvent::ToggleTail),
+            QUIT_ID => Some(TrayEvent::QuitRequested),
+            _ => id
+                .strip_prefix(FAVORITE_PREFIX)?
+                .parse()
+                .ok()
+                .map(TrayEvent::OpenFavorite),
+        }
+    }
+}
+
+/// Tail state across all tabs, as offered by the menu.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TailState {
+    /// No tab is tailing.
+    #[default]
+    Idle,
+    Tailing,
+    /// Tailing was paused from the menu.
+    Paused,
+}
+
+/// What the tray shows; the app pushes it every frame and the tray is only
+/// touched when it changes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrayState {
+    pub window_visible: bool,
+    pub tail: TailState,
+    /// Labels of the most recently used favorites.
+    pub favorites: Vec<String>,
+    pub icon: TrayIconKind,
+    /// Status line shown as the tooltip instead of `TrayConfig::tooltip`.
+    pub status: Option<String>,
+}
+
+impl TrayState {
+    fn tooltip(&self, base: &str) -> String {
+        self.status.clone().unwrap_or_else(|| base.to_string())
+    }
+
+    fn show_hide_label(&self) -> &'static str {
+        if self.window_visible {
+            "Hide window"
+        } else {
+            "Show window"
+        }
+    }
+
+    fn tail_label(&self) -> &'static str {
+        match self.tail {
+            TailState::Paused => "Resume tail",
+            TailState::Idle | TailState::Tailing => "Pause tail",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TrayConfig {
     pub tooltip: String,
@@ -38,60 +113,169 @@ impl Default for TrayConfig {
     }
 }
 
-#[allow(dead_code)]
+/// Menu items updated as the app state changes.
+struct TrayMenu {
+    show_hide: MenuItem,
+    tail: MenuItem,
+    favorites: Submenu,
+    favorite_items: Vec<MenuItem>,
+    state: TrayState,
+}
+
 pub struct TrayHandle {
     inner: Option<TrayIcon>,
+    menu: Option<TrayMenu>,
+    icons: Option<IconSet>,
+    tooltip: String,
+    /// Set once the UI exists, so tray clicks wake up a hidden window.
+    repaint: Arc<OnceLock<egui::Context>>,
 }
 
 impl TrayHandle {
     pub fn spawn(config: TrayConfig) -> Result<(Self, TrayEventReceiver), TrayError> {
-        // Channel from the tray callback to the rest of the app.
+        // Channel from the tray callbacks to the rest of the app.
         let (sender, receiver) = unbounded::<TrayEvent>();
+        let repaint = Arc::new(OnceLock::new());
+
+        let state = TrayState {
+            window_visible: true,
+            ..TrayState::default()
+        };
+        let show_hide = MenuItem::with_id(SHOW_HIDE_ID, state.show_hide_label(), true, None);
+        let tail = MenuItem::with_id(TAIL_ID, state.tail_label(), false, None);
+        let favorites = Submenu::new("Favorites", false);
+        let quit = MenuItem::with_id(QUIT_ID, "Quit", true, None);
 
-        // Minimal context menu for now: just a Quit item for future use.
         let menu = Menu::new();
-        let _ = MenuItem::new("Quit", true, None);
+        menu.append_items(&[
+            &show_hide,
+            &tail,
+            &favorites,
+            &PredefinedMenuItem::separator(),
+            &quit,
+        ])
+        .map_err(|e| TrayError::InitFailed(e.to_string()))?;
 
         let mut builder = TrayIconBuilder::new();
-        builder = builder.with_tooltip(config.tooltip);
+        builder = builder.with_tooltip(&config.tooltip);
         builder = builder.with_menu(Box::new(menu));
 
-        if let Some(icon) = load_axe_icon() {
+        let mut icons = IconSet::load();
+        if let Some(icon) = icons.as_mut().and_then(|i| i.icon(state.icon)) {
             builder = builder.with_icon(icon);
         }
 
-        tray_icon::TrayIconEvent::set_event_handler(Some(Box::new(move |event: TrayIconEvent| {
+        let icon_sender = sender.clone();
+        let icon_repaint = Arc::clone(&repaint);
+        TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
             if event.click_type == ClickType::Left {
-                let _ = sender.send(TrayEvent::ToggleWindow);
+                send_event(&icon_sender, &icon_repaint, TrayEvent::ToggleWindow);
+            }
+        }));
+
+        let menu_repaint = Arc::clone(&repaint);
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            if let Some(tray_event) = TrayEvent::from_menu_id(&event.id.0) {
+                send_event(&sender, &menu_repaint, tray_event);
             }
-        })));
+        }));
 
         let icon = builder
             .build()
             .map_err(|e| TrayError::InitFailed(e.to_string()))?;
 
-        let handle = TrayHandle { inner: Some(icon) };
+        let handle = TrayHandle {
+            inner: Some(icon),
+            menu: Some(TrayMenu {
+                show_hide,
+                tail,
+                favorites,
+                favorite_items: Vec::new(),
+                state,
+            }),
+            icons,
+            tooltip: config.tooltip,
+            repaint,
+        };
         let receiver = TrayEventReceiver::new(receiver);
 
         Ok((handle, receiver))
     }
 
     pub fn dummy() -> Self {
-        TrayHandle { inner: None }
+        TrayHandle {
+            inner: None,
+            menu: None,
+            icons: None,
+            tooltip: String::new(),
+            repaint: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Whether a tray icon is shown, i.e. the window can be hidden to it.
+    pub fn is_available(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    pub fn set_repaint_context(&self, ctx: &egui::Context) {
+        let _ = self.repaint.set(ctx.clone());
+    }
+
+    /// Bring the menu, icon and tooltip in line with `state`.
+    pub fn update(&mut self, state: &TrayState) {
+        let Some(menu) = self.menu.as_mut() else {
+            return;
+        };
+        if menu.state == *state {
+            return;
+        }
+
+        if let Some(tray) = &self.inner {
+            if menu.state.icon != state.icon
+                && let Some(icon) = self.icons.as_mut().and_then(|i| i.icon(state.icon))
+            {
+                let _ = tray.set_icon(Some(icon));
+            }
+            if menu.state.status != state.status {
+                let _ = tray.set_tooltip(Some(state.tooltip(&self.tooltip)));
+            }
+        }
+
+        menu.show_hide.set_text(state.show_hide_label());
+        menu.tail.set_text(state.tail_label());
+        menu.tail.set_enabled(state.tail != TailState::Idle);
+
+        if menu.state.favorites != state.favorites {
+            for item in menu.favorite_items.drain(..) {
+                let _ = menu.favorites.remove(&item);
+            }
+            for (idx, label) in state.favorites.iter().enumerate() {
+                let item = MenuItem::with_id(format!("{FAVORITE_PREFIX}{idx}"), label, true, None);
+                let _ = menu.favorites.append(&item);
+                menu.favorite_items.push(item);
+            }
+            menu.favorites.set_enabled(!state.favorites.is_empty());
+        }
+
+        menu.state = state.clone();
+    }
+}
+
+fn send_event(sender: &Sender<TrayEvent>, repaint: &OnceLock<egui::Context>, event: TrayEvent) {
+    let _ = sender.send(event);
+    if let Some(ctx) = repaint.get() {
+        ctx.request_repaint();
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum TrayError {
-    Unsupported,
     InitFailed(String),
 }
 
 impl fmt::Display for TrayError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TrayError::Unsupported => write!(f, "tray not supported on this platform"),
             TrayError::InitFailed(reason) => {
                 write!(f, "failed to initialize tray: {reason}")
             }
@@ -101,7 +285,6 @@ impl fmt::Display for TrayError {
 
 impl std::error::Error for TrayError {}
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub struct TrayEventReceiver {
     inner: Option<Receiver<TrayEvent>>,
@@ -115,34 +298,10 @@ impl TrayEventReceiver {
     pub fn closed() -> Self {
         Self { inner: None }
     }
-}
 
-fn load_axe_icon() -> Option<Icon> {
-    let path = "assets/axe.png";
-    match image::open(path) {
-        Ok(img) => {
-            // Resize to a small square icon for the tray.
-            let img = img
-                .resize_exact(32, 32, image::imageops::Lanczos3)
-                .into_rgba8();
-            let (width, height) = img.dimensions();
-            let rgba = img.into_raw();
-
-            match Icon::from_rgba(rgba, width, height) {
-                Ok(icon) => {
-                    println!("[axe] Loaded tray icon from {path} ({width}x{height})");
-                    Some(icon)
-                }
-                Err(e) => {
-                    eprintln!("[axe] Failed to create Icon from {path}: {e}");
-                    None
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("[axe] Failed to open tray icon at {path}: {e}");
-            None
-        }
+    /// The next pending event, if any.
+    pub fn try_recv(&self) -> Option<TrayEvent> {
+        self.inner.as_ref()?.try_recv().ok()
     }
 }
 
@@ -153,9 +312,31 @@ mod tests {
     #[test]
     fn tray_event_display_matches_variant_names() {
         assert_eq!(TrayEvent::ToggleWindow.to_string(), "ToggleWindow");
-        assert_eq!(TrayEvent::ShowWindow.to_string(), "ShowWindow");
-        assert_eq!(TrayEvent::HideWindow.to_string(), "HideWindow");
+        assert_eq!(TrayEvent::ToggleTail.to_string(), "ToggleTail");
         assert_eq!(TrayEvent::QuitRequested.to_string(), "QuitRequested");
+        assert_eq!(TrayEvent::OpenFavorite(2).to_string(), "OpenFavorite(2)");
+    }
+
+    #[test]
+    fn menu_ids_map_to_events() {
+        assert_eq!(
+            TrayEvent::from_menu_id(SHOW_HIDE_ID),
+            Some(TrayEvent::ToggleWindow)
+        );
+        assert_eq!(
+            TrayEvent::from_menu_id(TAIL_ID),
+            Some(TrayEvent::ToggleTail)
+        );
+        assert_eq!(
+            TrayEvent::from_menu_id(QUIT_ID),
+            Some(TrayEvent::QuitRequested)
+        );
+        assert_eq!(
+            TrayEvent::from_menu_id("favorite:3"),
+            Some(TrayEvent::OpenFavorite(3))
+        );
+        assert_eq!(TrayEvent::from_menu_id("favorite:x"), None);
+        assert_eq!(TrayEvent::from_menu_id("other"), None);
     }
 
     #[test]
@@ -166,25 +347,38 @@ mod tests {
 
     #[test]
     fn tray_error_display_is_human_readable() {
-        let e1 = TrayError::Unsupported;
-        assert!(e1.to_string().contains("tray not supported"));
-
-        let e2 = TrayError::InitFailed("boom".into());
-        assert!(e2.to_string().contains("failed to initialize tray"));
-        assert!(e2.to_string().contains("boom"));
+        let e = TrayError::InitFailed("boom".into());
+        assert!(e.to_string().contains("failed to initialize tray"));
+        assert!(e.to_string().contains("boom"));
     }
 
     #[test]
     fn closed_tray_event_receiver_has_no_inner_receiver() {
         let rx = TrayEventReceiver::closed();
-        let _ = rx;
+        assert_eq!(rx.try_recv(), None);
     }
 
     #[test]
     fn tray_event_receiver_new_constructs() {
         let (tx, inner_rx) = crossbeam_channel::unbounded::<TrayEvent>();
+        tx.send(TrayEvent::ToggleWindow).unwrap();
         drop(tx); // close the sender
         let rx = TrayEventReceiver::new(inner_rx);
-        let _ = rx;
+        assert_eq!(rx.try_recv(), Some(TrayEvent::ToggleWindow));
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn menu_labels_follow_state() {
+        let mut state = TrayState::default();
+        assert_eq!(state.show_hide_label(), "Show window");
+        state.window_visible = true;
+        assert_eq!(state.show_hide_label(), "Hide window");
+        state.tail = TailState::Paused;
+        assert_eq!(state.tail_label(), "Resume tail");
+
+        assert_eq!(state.tooltip("Axe"), "Axe");
+        state.status = Some("Tailing /aws/lambda/foo".to_string());
+        assert_eq!(state.tooltip("Axe"), "Tailing /aws/lambda/foo");
     }
 }
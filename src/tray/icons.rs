@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use tray_icon::Icon;
+
+/// The axe image scaled down to `ICON_SIZE` from `assets/axe.png`, embedded
+/// so the icon does not depend on the working directory.
+const AXE_PNG: &[u8] = include_bytes!("../../assets/axe-tray.png");
+
+/// Width and height of the tray icon.
+const ICON_SIZE: usize = 32;
+
+/// Alert counts above this show as `+`.
+const MAX_BADGE_COUNT: usize = 9;
+
+const GREEN: [u8; 4] = [0x2e, 0xcc, 0x40, 0xff];
+const AMBER: [u8; 4] = [0xff, 0xb0, 0x20, 0xff];
+const RED: [u8; 4] = [0xe0, 0x30, 0x30, 0xff];
+const WHITE: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+const OUTLINE: [u8; 4] = [0x20, 0x20, 0x20, 0xff];
+
+/// Icon variants for the app state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TrayIconKind {
+    #[default]
+    Idle,
+    /// A green dot: some tab is tailing.
+    Tailing,
+    /// An amber `!`: a request failed.
+    Error,
+    /// Red-tinted, with the number of alert rules firing.
+    Alert(usize),
+}
+
+impl TrayIconKind {
+    /// Alert counts beyond the badge look the same, so share one icon.
+    fn normalized(self) -> Self {
+        match self {
+            TrayIconKind::Alert(n) => TrayIconKind::Alert(n.min(MAX_BADGE_COUNT + 1)),
+            other => other,
+        }
+    }
+}
+
+/// The variants, rendered on first use.
+pub struct IconSet {
+    base: Vec<u8>,
+    cache: HashMap<TrayIconKind, Icon>,
+}
+
+impl IconSet {
+    pub fn load() -> Option<Self> {
+        match image::load_from_memory(AXE_PNG) {
+            Ok(img) => {
+                let size = ICON_SIZE as u32;
+                let img = if img.width() == size && img.height() == size {
+                    img
+                } else {
+                    img.resize_exact(size, size, image::imageops::Lanczos3)
+                };
+                Some(Self {
+                    base: img.into_rgba8().into_raw(),
+                    cache: HashMap::new(),
+                })
+            }
+            Err(e) => {
+                eprintln!("[axe] Failed to decode the embedded tray icon: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn icon(&mut self, kind: TrayIconKind) -> Option<Icon> {
+        let kind = kind.normalized();
+        if let Some(icon) = self.cache.get(&kind) {
+            return Some(icon.clone());
+        }
+        let size = ICON_SIZE as u32;
+        match Icon::from_rgba(render(&self.base, kind), size, size) {
+            Ok(icon) => {
+                self.cache.insert(kind, icon.clone());
+                Some(icon)
+            }
+            Err(e) => {
+                eprintln!("[axe] Failed to create the {kind:?} tray icon: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// `base` (RGBA, `ICON_SIZE` square) with the badge of `kind`.
+fn render(base: &[u8], kind: TrayIconKind) -> Vec<u8> {
+    let mut rgba = base.to_vec();
+    match kind {
+        TrayIconKind::Idle => {}
+        TrayIconKind::Tailing => {
+            fill_circle(&mut rgba, 25, 25, 7, OUTLINE);
+            fill_circle(&mut rgba, 25, 25, 6, GREEN);
+        }
+        TrayIconKind::Error => {
+            fill_circle(&mut rgba, 24, 24, 8, OUTLINE);
+            fill_circle(&mut rgba, 24, 24, 7, AMBER);
+            draw_text(&mut rgba, "!", 24, 24, OUTLINE);
+        }
+        TrayIconKind::Alert(count) => {
+            tint(&mut rgba, RED);
+            let label = if count > MAX_BADGE_COUNT {
+                "+".to_string()
+            } else {
+                count.to_string()
+            };
+            fill_circle(&mut rgba, 23, 8, 9, WHITE);
+            fill_circle(&mut rgba, 23, 8, 8, RED);
+            draw_text(&mut rgba, &label, 23, 8, WHITE);
+        }
+    }
+    rgba
+}
+
+/// Blend the color of every pixel halfway towards `color`, keeping alpha.
+fn tint(rgba: &mut [u8], color: [u8; 4]) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        for (channel, tint) in pixel.iter_mut().zip(&color[..3]) {
+            *channel = ((u16::from(*channel) + u16::from(*tint)) / 2) as u8;
+        }
+    }
+}
+
+fn set_pixel(rgba: &mut [u8], x: i32, y: i32, color: [u8; 4]) {
+    let size = ICON_SIZE as i32;
+    if (0..size).contains(&x) && (0..size).contains(&y) {
+        let offset = (y * size + x) as usize * 4;
+        rgba[offset..offset + 4].copy_from_slice(&color);
+    }
+}
+
+fn fill_circle(rgba: &mut [u8], cx: i32, cy: i32, r: i32, color: [u8; 4]) {
+    for y in cy - r..=cy + r {
+        for x in cx - r..=cx + r {
+            let (dx, dy) = (x - cx, y - cy);
+            if dx * dx + dy * dy <= r * r {
+                set_pixel(rgba, x, y, color);
+            }
+        }
+    }
+}
+
+/// 3x5 glyphs, one row per byte (bit 2 is the left column).
+fn glyph(c: char) -> [u8; 5] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b011, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        _ => [0; 5],
+    }
+}
+
+/// Draw `text` at double scale, centered on `(cx, cy)`.
+fn draw_text(rgba: &mut [u8], text: &str, cx: i32, cy: i32, color: [u8; 4]) {
+    const SCALE: i32 = 2;
+    let count = text.chars().count() as i32;
+    let width = (count * 4 - 1) * SCALE;
+    let left = cx - width / 2;
+    let top = cy - 5 * SCALE / 2;
+
+    for (i, c) in text.chars().enumerate() {
+        let x0 = left + i as i32 * 4 * SCALE;
+        for (row, bits) in glyph(c).into_iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..SCALE {
+                    for dx in 0..SCALE {
+                        set_pixel(
+                            rgba,
+                            x0 + col * SCALE + dx,
+                            top + row as i32 * SCALE + dy,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(rgba: &[u8], x: usize, y: usize) -> [u8; 4] {
+        let offset = (y * ICON_SIZE + x) * 4;
+        rgba[offset..offset + 4].try_into().unwrap()
+    }
+
+    fn blank() -> Vec<u8> {
+        vec![0; ICON_SIZE * ICON_SIZE * 4]
+    }
+
+    #[test]
+    fn embedded_icon_decodes() {
+        let img = image::load_from_memory(AXE_PNG).expect("embedded PNG decodes");
+        assert_eq!((img.width(), img.height()), (32, 32), "ships pre-scaled");
+        let mut icons = IconSet::load().expect("embedded PNG decodes");
+        assert_eq!(icons.base.len(), ICON_SIZE * ICON_SIZE * 4);
+        assert!(icons.icon(TrayIconKind::Alert(3)).is_some());
+    }
+
+    #[test]
+    fn variants_draw_their_badges() {
+        let base = blank();
+        assert_eq!(render(&base, TrayIconKind::Idle), base);
+        assert_eq!(pixel(&render(&base, TrayIconKind::Tailing), 25, 25), GREEN);
+        assert_eq!(pixel(&render(&base, TrayIconKind::Error), 24, 29), AMBER);
+
+        let alert = render(&base, TrayIconKind::Alert(1));
+        // Outside the badge: tinted, alpha kept.
+        assert_eq!(pixel(&alert, 2, 30), [0x70, 0x18, 0x18, 0]);
+        // The badge and the top of the `1` stem.
+        assert_eq!(pixel(&alert, 16, 8), RED);
+        assert_eq!(pixel(&alert, 23, 3), WHITE);
+    }
+
+    #[test]
+    fn large_alert_counts_share_the_plus_badge() {
+        assert_eq!(
+            TrayIconKind::Alert(25).normalized(),
+            TrayIconKind::Alert(10)
+        );
+        let base = blank();
+        assert_eq!(
+            render(&base, TrayIconKind::Alert(10)),
+            render(&base, TrayIconKind::Alert(25))
+        );
+        assert_ne!(
+            render(&base, TrayIconKind::Alert(9)),
+            render(&base, TrayIconKind::Alert(10))
+        );
+    }
+}
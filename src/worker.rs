@@ -1,19 +1,47 @@
+use std::collections::HashMap;
+#[cfg(feature = "gui")]
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 
-use std::sync::mpsc::{Receiver, Sender};
+use std::sync::mpsc::Sender;
 
-use crate::aws::{AwsLogError, FetchLogsParams, LogEntry};
+use tokio::sync::Notify;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+#[cfg(feature = "gui")]
+use tokio::sync::watch;
+use tokio::task::AbortHandle;
+#[cfg(feature = "gui")]
+use tokio::time::MissedTickBehavior;
+
+#[cfg(feature = "gui")]
+use crate::app::alerts::{AlertEvent, AlertRule, AlertSetup};
+#[cfg(feature = "gui")]
+use crate::app::export::{ExportError, ExportOptions};
+use crate::aws::{
+    AwsLogError, FetchLogsParams, InsightsQueryParams, InsightsRow, LogEntry, LogStreamInfo,
+    Progress,
+};
+#[cfg(feature = "gui")]
+use crate::aws::{SearchLogGroupsParams, SearchResults};
+#[cfg(feature = "gui")]
+use crate::local_logs::{LocalFileOptions, LocalLogError};
 
 pub enum WorkerRequest {
     /// Fetch recent logs for given params, sending result on the provided channel.
+    /// Pages and retries are sent on `progress` as they happen.
     FetchRecentLogs {
         profile: Option<String>,
         region: Option<String>,
         log_group: String,
         filter_pattern: Option<String>,
         lookback: Duration,
+        /// Absolute `(start, end)` range in epoch millis; overrides `lookback`.
+        absolute_range: Option<(i64, i64)>,
         limit: i32,
         respond_to: Sender<Result<Vec<LogEntry>, AwsLogError>>,
+        progress: Option<Sender<Progress>>,
     },
 
     /// List log groups for given profile/region, sending result on the provided channel.
@@ -23,28 +51,229 @@ pub enum WorkerRequest {
         limit: i32,
         respond_to: Sender<Result<Vec<String>, AwsLogError>>,
     },
+
+    /// List the streams of a log group, most recently written first.
+    ListLogStreams {
+        profile: Option<String>,
+        region: Option<String>,
+        log_group: String,
+        limit: i32,
+        respond_to: Sender<Result<Vec<LogStreamInfo>, AwsLogError>>,
+    },
+
+    /// Run a Logs Insights query over an absolute range, sending the result
+    /// rows on the provided channel once it completes. Status changes are
+    /// sent on `progress` while it runs.
+    RunInsightsQuery {
+        profile: Option<String>,
+        region: Option<String>,
+        log_groups: Vec<String>,
+        query: String,
+        range: (i64, i64),
+        limit: i32,
+        respond_to: Sender<Result<Vec<InsightsRow>, AwsLogError>>,
+        progress: Option<Sender<Progress>>,
+    },
+
+    /// Search several log groups for a term within an absolute range, sending
+    /// the merged, time-ordered matches and per-group failures on the
+    /// provided channel.
+    #[cfg(feature = "gui")]
+    SearchLogGroups {
+        profile: Option<String>,
+        region: Option<String>,
+        log_groups: Vec<String>,
+        term: String,
+        range: (i64, i64),
+        limit: i32,
+        respond_to: Sender<SearchResults>,
+        progress: Option<Sender<Progress>>,
+    },
+
+    /// Fetch the last `lookback` now and then every `interval` until
+    /// cancelled, sending each refresh on the provided channel. The worker
+    /// keeps the time, so tailing goes on while the window is idle or hidden.
+    /// The start of each refresh, its pages and retries are sent on
+    /// `progress`.
+    #[cfg(feature = "gui")]
+    TailLogs {
+        profile: Option<String>,
+        region: Option<String>,
+        log_group: String,
+        filter_pattern: Option<String>,
+        lookback: Duration,
+        interval: Duration,
+        limit: i32,
+        respond_to: Sender<Result<Vec<LogEntry>, AwsLogError>>,
+        progress: Option<Sender<Progress>>,
+    },
+
+    /// Re-read a local log file every `interval` until cancelled, sending
+    /// the entries on the provided channel each time.
+    #[cfg(feature = "gui")]
+    TailLocalFile {
+        path: PathBuf,
+        options: LocalFileOptions,
+        interval: Duration,
+        respond_to: Sender<Result<Vec<LogEntry>, LocalLogError>>,
+    },
+
+    /// Read a local log file, sending the parsed entries on the provided channel.
+    #[cfg(feature = "gui")]
+    LoadLocalFile {
+        path: PathBuf,
+        options: LocalFileOptions,
+        respond_to: Sender<Result<Vec<LogEntry>, LocalLogError>>,
+    },
+
+    /// Write entries to a file, sending the number written on the provided channel.
+    #[cfg(feature = "gui")]
+    ExportEntries {
+        path: PathBuf,
+        entries: Vec<LogEntry>,
+        options: ExportOptions,
+        respond_to: Sender<Result<usize, ExportError>>,
+    },
+
+    /// Replace the alert rules evaluated in the background, starting the
+    /// monitor on first use. Statuses are sent on `events` (from now on) as
+    /// they come; cancelling the latest of these requests stops the monitor.
+    #[cfg(feature = "gui")]
+    SetAlertRules {
+        rules: Vec<AlertRule>,
+        events: Sender<AlertEvent>,
+    },
+}
+
+/// Requests run at the same time by default; more wait their turn.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Lets up to `limit` requests run at once; the others wait their turn.
+/// Unlike a semaphore the limit can shrink while requests wait: it holds for
+/// every request started after the change.
+pub struct Limiter {
+    /// `(limit, running)`.
+    state: Mutex<(usize, usize)>,
+    freed: Notify,
+}
+
+impl Limiter {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            state: Mutex::new((limit, 0)),
+            freed: Notify::new(),
+        }
+    }
+
+    /// Wait until fewer than `limit` requests run, and count this one in
+    /// until the permit is dropped.
+    pub async fn acquire(&self) -> Permit<'_> {
+        loop {
+            // Created before checking, so a release in between is not missed.
+            let freed = self.freed.notified();
+            {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                if state.1 < state.0 {
+                    state.1 += 1;
+                    return Permit { limiter: self };
+                }
+            }
+            freed.await;
+        }
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn set_limit(&self, limit: usize) {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).0 = limit;
+        self.freed.notify_waiters();
+    }
 }
 
+/// A running request, counted by its `Limiter` until dropped.
+pub struct Permit<'a> {
+    limiter: &'a Limiter,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.1 -= 1;
+        drop(state);
+        self.limiter.freed.notify_waiters();
+    }
+}
+
+/// Identifies a request sent to the worker, e.g. to cancel it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestId(u64);
+
+enum Message {
+    Run(RequestId, WorkerRequest),
+    #[cfg(feature = "gui")]
+    Cancel(RequestId),
+    #[cfg(feature = "gui")]
+    SetConcurrency(usize),
+}
+
+/// Woken whenever a response is sent, so the UI picks it up without waiting
+/// for input.
+pub(crate) type Repaint = Arc<OnceLock<egui::Context>>;
+
 /// Handle for sending work to the worker.
 #[derive(Clone)]
 pub struct WorkerHandle {
-    sender: Sender<WorkerRequest>,
+    sender: UnboundedSender<Message>,
+    next_id: Arc<AtomicU64>,
+    #[cfg(feature = "gui")]
+    repaint: Repaint,
 }
 
 impl WorkerHandle {
-    pub fn send(&self, req: WorkerRequest) {
+    pub fn send(&self, req: WorkerRequest) -> RequestId {
+        let id = RequestId(self.next_id.fetch_add(1, Ordering::Relaxed));
         // Best-effort send; if worker is gone, we just ignore.
-        let _ = self.sender.send(req);
+        let _ = self.sender.send(Message::Run(id, req));
+        id
+    }
+
+    /// Drop a queued request, or abort it if running. Its response channel
+    /// is closed without an answer; a finished request is unaffected.
+    #[cfg(feature = "gui")]
+    pub fn cancel(&self, id: RequestId) {
+        let _ = self.sender.send(Message::Cancel(id));
+    }
+
+    /// Change how many requests run at once. Running requests finish
+    /// first when it shrinks.
+    #[cfg(feature = "gui")]
+    pub fn set_concurrency(&self, concurrency: usize) {
+        let _ = self.sender.send(Message::SetConcurrency(concurrency));
     }
+
+    /// Repaint the UI whenever a response is delivered.
+    #[cfg(feature = "gui")]
+    pub fn set_repaint_context(&self, ctx: &egui::Context) {
+        let _ = self.repaint.set(ctx.clone());
+    }
+}
+
+/// Spawn the worker thread with the default concurrency.
+#[cfg(feature = "gui")]
+pub fn spawn_worker() -> WorkerHandle {
+    spawn_worker_with_concurrency(DEFAULT_CONCURRENCY)
 }
 
 /// Spawn the worker thread and return a handle for sending it requests.
 ///
-/// The worker runs a single-threaded Tokio runtime (current_thread), mirroring aws_test.
-pub fn spawn_worker() -> WorkerHandle {
+/// The worker runs a single-threaded Tokio runtime (current_thread). Each
+/// request is its own task, and up to `concurrency` of them run at once;
+/// file I/O runs on the blocking pool so it does not hold up the others.
+pub fn spawn_worker_with_concurrency(concurrency: usize) -> WorkerHandle {
     use std::thread;
 
-    let (tx, rx): (Sender<WorkerRequest>, Receiver<WorkerRequest>) = std::sync::mpsc::channel();
+    let (tx, rx) = unbounded_channel();
+    let repaint = Repaint::default();
+    let loop_repaint = Arc::clone(&repaint);
 
     thread::spawn(move || {
         // Build a current_thread runtime, like #[tokio::main(flavor = "current_thread")].
@@ -54,19 +283,242 @@ pub fn spawn_worker() -> WorkerHandle {
             .expect("Failed to build Tokio runtime for worker");
 
         rt.block_on(async move {
-            worker_loop(rx).await;
+            worker_loop(rx, concurrency, loop_repaint).await;
         });
     });
 
-    WorkerHandle { sender: tx }
+    WorkerHandle {
+        sender: tx,
+        next_id: Arc::new(AtomicU64::new(0)),
+        #[cfg(feature = "gui")]
+        repaint,
+    }
 }
 
-async fn worker_loop(rx: Receiver<WorkerRequest>) {
-    use crate::aws::{fetch_recent_logs, list_log_groups};
+async fn worker_loop(mut rx: UnboundedReceiver<Message>, concurrency: usize, repaint: Repaint) {
+    #[cfg(feature = "gui")]
+    use crate::app::alerts::monitor_alerts;
 
-    while let Ok(req) = rx.recv() {
-        match req {
-            WorkerRequest::FetchRecentLogs {
+    let permits = Arc::new(Limiter::new(concurrency));
+    // The monitor's setup, and the request that now owns it.
+    #[cfg(feature = "gui")]
+    let mut alert_monitor: Option<(RequestId, watch::Sender<AlertSetup>)> = None;
+    // Tasks that may still be queued or running, to abort on cancel.
+    let mut tasks: HashMap<RequestId, AbortHandle> = HashMap::new();
+
+    while let Some(message) = rx.recv().await {
+        tasks.retain(|_, task| !task.is_finished());
+        match message {
+            // The monitor runs until cancelled; later rules are handed to it,
+            // and it is cancelled through the latest request.
+            #[cfg(feature = "gui")]
+            Message::Run(id, WorkerRequest::SetAlertRules { rules, events }) => {
+                match &mut alert_monitor {
+                    Some((owner, setup)) if !setup.is_closed() => {
+                        setup.send_replace((rules, events));
+                        if let Some(task) = tasks.remove(owner) {
+                            tasks.insert(id, task);
+                        }
+                        *owner = id;
+                    }
+                    _ => {
+                        let (setup, receiver) = watch::channel((rules, events));
+                        let monitor =
+                            monitor_alerts(receiver, Arc::clone(&repaint), Arc::clone(&permits));
+                        tasks.insert(id, tokio::spawn(monitor).abort_handle());
+                        alert_monitor = Some((id, setup));
+                    }
+                }
+            }
+            // Tails run until cancelled, taking a permit per refresh only.
+            #[cfg(feature = "gui")]
+            Message::Run(
+                id,
+                request @ (WorkerRequest::TailLogs { .. } | WorkerRequest::TailLocalFile { .. }),
+            ) => {
+                let task = tokio::spawn(tail(request, Arc::clone(&permits), Arc::clone(&repaint)));
+                tasks.insert(id, task.abort_handle());
+            }
+            Message::Run(id, request) => {
+                let permits = Arc::clone(&permits);
+                let repaint = Arc::clone(&repaint);
+                let task = tokio::spawn(async move {
+                    handle_request(request, &permits, &repaint).await;
+                    wake(&repaint);
+                });
+                tasks.insert(id, task.abort_handle());
+            }
+            // Aborting drops the request's future, and its response channel.
+            #[cfg(feature = "gui")]
+            Message::Cancel(id) => {
+                if let Some(task) = tasks.remove(&id) {
+                    task.abort();
+                }
+            }
+            #[cfg(feature = "gui")]
+            Message::SetConcurrency(concurrency) => permits.set_limit(concurrency),
+        }
+    }
+}
+
+async fn handle_request(req: WorkerRequest, permits: &Limiter, repaint: &Repaint) {
+    #[cfg(feature = "gui")]
+    use crate::app::export::export_to_path;
+    #[cfg(feature = "gui")]
+    use crate::aws::search_log_groups;
+    use crate::aws::{fetch_recent_logs, list_log_groups, list_log_streams, run_insights_query};
+    #[cfg(feature = "gui")]
+    use crate::local_logs::load_local_file;
+
+    // A search takes a permit per log group instead.
+    let _permit = match req {
+        #[cfg(feature = "gui")]
+        WorkerRequest::SearchLogGroups { .. } => None,
+        _ => Some(permits.acquire().await),
+    };
+    match req {
+        WorkerRequest::FetchRecentLogs {
+            profile,
+            region,
+            log_group,
+            filter_pattern,
+            lookback,
+            absolute_range,
+            limit,
+            respond_to,
+            progress,
+        } => {
+            let progress = progress.as_ref().map(|to| ProgressSender::new(to, repaint));
+            let params = FetchLogsParams {
+                profile: profile.as_deref(),
+                region: region.as_deref(),
+                log_group: &log_group,
+                filter_pattern: filter_pattern.as_deref(),
+                lookback,
+                absolute_range,
+                limit,
+                progress: progress.as_ref(),
+            };
+            let result = fetch_recent_logs(params).await;
+            let _ = respond_to.send(result);
+        }
+        WorkerRequest::ListLogGroups {
+            profile,
+            region,
+            limit,
+            respond_to,
+        } => {
+            let profile_opt = profile.as_deref();
+            let region_opt = region.as_deref();
+            let result = list_log_groups(profile_opt, region_opt, limit).await;
+            let _ = respond_to.send(result);
+        }
+        WorkerRequest::ListLogStreams {
+            profile,
+            region,
+            log_group,
+            limit,
+            respond_to,
+        } => {
+            let result =
+                list_log_streams(profile.as_deref(), region.as_deref(), &log_group, limit).await;
+            let _ = respond_to.send(result);
+        }
+        WorkerRequest::RunInsightsQuery {
+            profile,
+            region,
+            log_groups,
+            query,
+            range,
+            limit,
+            respond_to,
+            progress,
+        } => {
+            let progress = progress.as_ref().map(|to| ProgressSender::new(to, repaint));
+            let params = InsightsQueryParams {
+                profile: profile.as_deref(),
+                region: region.as_deref(),
+                log_groups: &log_groups,
+                query: &query,
+                range,
+                limit,
+                progress: progress.as_ref(),
+            };
+            let result = run_insights_query(params).await;
+            let _ = respond_to.send(result);
+        }
+        #[cfg(feature = "gui")]
+        WorkerRequest::SearchLogGroups {
+            profile,
+            region,
+            log_groups,
+            term,
+            range,
+            limit,
+            respond_to,
+            progress,
+        } => {
+            let progress = progress.as_ref().map(|to| ProgressSender::new(to, repaint));
+            let params = SearchLogGroupsParams {
+                profile: profile.as_deref(),
+                region: region.as_deref(),
+                log_groups: &log_groups,
+                term: &term,
+                range,
+                limit,
+                progress: progress.as_ref(),
+                permits,
+            };
+            let result = search_log_groups(params).await;
+            let _ = respond_to.send(result);
+        }
+        #[cfg(feature = "gui")]
+        WorkerRequest::LoadLocalFile {
+            path,
+            options,
+            respond_to,
+        } => {
+            let result = blocking(move || load_local_file(&path, &options)).await;
+            let _ = respond_to.send(result);
+        }
+        #[cfg(feature = "gui")]
+        WorkerRequest::ExportEntries {
+            path,
+            entries,
+            options,
+            respond_to,
+        } => {
+            let result = blocking(move || export_to_path(&path, &entries, &options)).await;
+            let _ = respond_to.send(result);
+        }
+        #[cfg(feature = "gui")]
+        // Handled by the loop.
+        WorkerRequest::SetAlertRules { .. }
+        | WorkerRequest::TailLogs { .. }
+        | WorkerRequest::TailLocalFile { .. } => {}
+    }
+}
+
+/// Refresh a tail every interval until cancelled or its receiver is gone.
+#[cfg(feature = "gui")]
+async fn tail(req: WorkerRequest, permits: Arc<Limiter>, repaint: Repaint) {
+    use crate::aws::fetch_recent_logs;
+    use crate::local_logs::load_local_file;
+
+    let interval = match &req {
+        WorkerRequest::TailLogs { interval, .. }
+        | WorkerRequest::TailLocalFile { interval, .. } => *interval,
+        _ => return,
+    };
+    let mut ticks = tokio::time::interval(interval);
+    // A slow refresh delays the next one rather than bunching them up.
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticks.tick().await;
+        let _permit = permits.acquire().await;
+        let delivered = match &req {
+            WorkerRequest::TailLogs {
                 profile,
                 region,
                 log_group,
@@ -74,33 +526,80 @@ async fn worker_loop(rx: Receiver<WorkerRequest>) {
                 lookback,
                 limit,
                 respond_to,
+                progress,
+                ..
             } => {
+                let progress = progress
+                    .as_ref()
+                    .map(|to| ProgressSender::new(to, &repaint));
+                if let Some(progress) = &progress {
+                    progress.send(Progress::Refreshing);
+                }
                 let params = FetchLogsParams {
                     profile: profile.as_deref(),
                     region: region.as_deref(),
-                    log_group: &log_group,
+                    log_group,
                     filter_pattern: filter_pattern.as_deref(),
-                    lookback,
-                    limit,
+                    lookback: *lookback,
+                    limit: *limit,
+                    progress: progress.as_ref(),
+                    ..FetchLogsParams::default()
                 };
-                let result = fetch_recent_logs(params).await;
-                let _ = respond_to.send(result);
+                respond_to.send(fetch_recent_logs(params).await).is_ok()
             }
-            WorkerRequest::ListLogGroups {
-                profile,
-                region,
-                limit,
+            WorkerRequest::TailLocalFile {
+                path,
+                options,
                 respond_to,
+                ..
             } => {
-                let profile_opt = profile.as_deref();
-                let region_opt = region.as_deref();
-                let result = list_log_groups(profile_opt, region_opt, limit).await;
-                let _ = respond_to.send(result);
+                let (path, options) = (path.clone(), options.clone());
+                let result = blocking(move || load_local_file(&path, &options)).await;
+                respond_to.send(result).is_ok()
             }
+            _ => return,
+        };
+        if !delivered {
+            return;
+        }
+        wake(&repaint);
+    }
+}
+
+/// Sends a request's progress, waking the UI for every event so pages show
+/// while the window is idle.
+pub struct ProgressSender<'a> {
+    to: &'a Sender<Progress>,
+    repaint: &'a Repaint,
+}
+
+impl<'a> ProgressSender<'a> {
+    pub fn new(to: &'a Sender<Progress>, repaint: &'a Repaint) -> Self {
+        Self { to, repaint }
+    }
+
+    pub fn send(&self, event: Progress) {
+        // Nobody listening is fine: the result still gets through.
+        if self.to.send(event).is_ok() {
+            wake(self.repaint);
         }
     }
 }
 
+pub(crate) fn wake(repaint: &OnceLock<egui::Context>) {
+    if let Some(ctx) = repaint.get() {
+        ctx.request_repaint();
+    }
+}
+
+/// Run blocking file I/O off the runtime thread.
+#[cfg(feature = "gui")]
+pub(crate) async fn blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    tokio::task::spawn_blocking(f)
+        .await
+        .expect("blocking worker task panicked")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,8 +615,10 @@ mod tests {
             log_group: "/aws/ecs/containerinsights/Form-production/performance".to_string(),
             filter_pattern: Some("ERROR".to_string()),
             lookback: Duration::from_secs(300),
+            absolute_range: None,
             limit: 1000,
             respond_to: tx,
+            progress: None,
         };
 
         match req {
@@ -147,6 +648,313 @@ mod tests {
         }
     }
 
+    #[test]
+    fn worker_request_run_insights_query_can_be_constructed() {
+        let (tx, _rx) = std::sync::mpsc::channel::<Result<Vec<InsightsRow>, AwsLogError>>();
+
+        let req = WorkerRequest::RunInsightsQuery {
+            profile: None,
+            region: Some("eu-west-1".to_string()),
+            log_groups: vec!["/aws/lambda/api".to_string()],
+            query: "stats count(*) by bin(5m)".to_string(),
+            range: (0, 300_000),
+            limit: 100,
+            respond_to: tx,
+            progress: None,
+        };
+
+        match req {
+            WorkerRequest::RunInsightsQuery { .. } => {
+                // OK
+            }
+            _ => panic!("Expected RunInsightsQuery variant"),
+        }
+    }
+
+    #[test]
+    fn progress_wakes_the_ui() {
+        let ctx = egui::Context::default();
+        let repaint = Repaint::default();
+        let _ = repaint.set(ctx.clone());
+        let (tx, rx) = std::sync::mpsc::channel();
+        let progress = ProgressSender::new(&tx, &repaint);
+
+        progress.send(Progress::Refreshing);
+        assert!(matches!(rx.try_recv(), Ok(Progress::Refreshing)));
+        assert!(ctx.has_requested_repaint());
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn worker_exports_entries_to_file() {
+        let worker = spawn_worker();
+        let path = std::env::temp_dir().join(format!("axe-export-{}.jsonl", std::process::id()));
+        let (tx, rx) = std::sync::mpsc::channel::<Result<usize, ExportError>>();
+
+        worker.send(WorkerRequest::ExportEntries {
+            path: path.clone(),
+            entries: vec![LogEntry {
+                timestamp_millis: 1,
+                message: "hello".to_string(),
+                log_stream_name: None,
+                log_group_name: None,
+                event_id: None,
+                ingestion_time_millis: None,
+            }],
+            options: ExportOptions::default(),
+            respond_to: tx,
+        });
+
+        let written = rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("worker responds")
+            .expect("export succeeds");
+        assert_eq!(written, 1);
+
+        let contents = std::fs::read_to_string(&path).expect("file written");
+        let _ = std::fs::remove_file(&path);
+        assert!(contents.contains("\"message\":\"hello\""));
+    }
+
+    #[cfg(feature = "gui")]
+    fn load_missing_file(
+        respond_to: Sender<Result<Vec<LogEntry>, LocalLogError>>,
+    ) -> WorkerRequest {
+        WorkerRequest::LoadLocalFile {
+            path: PathBuf::from("/does/not/exist.log"),
+            options: LocalFileOptions::default(),
+            respond_to,
+        }
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn cancelled_requests_are_dropped_without_a_response() {
+        use std::sync::mpsc::RecvTimeoutError;
+
+        // No permits: every request stays queued until cancelled.
+        let worker = spawn_worker_with_concurrency(0);
+        let (tx, cancelled_rx) = std::sync::mpsc::channel();
+        let id = worker.send(load_missing_file(tx));
+        let (tx, queued_rx) = std::sync::mpsc::channel();
+        worker.send(load_missing_file(tx));
+
+        worker.cancel(id);
+        assert!(matches!(
+            cancelled_rx.recv_timeout(Duration::from_secs(5)),
+            Err(RecvTimeoutError::Disconnected)
+        ));
+        assert!(matches!(
+            queued_rx.recv_timeout(Duration::from_millis(100)),
+            Err(RecvTimeoutError::Timeout)
+        ));
+    }
+
+    #[cfg(all(unix, feature = "gui"))]
+    type LoadResponse = std::sync::mpsc::Receiver<Result<Vec<LogEntry>, LocalLogError>>;
+
+    /// Requests that read a FIFO run until the test writes to it: opening
+    /// the write end returns once the worker has started reading.
+    #[cfg(all(unix, feature = "gui"))]
+    struct Fifos {
+        dir: PathBuf,
+        /// Requests started and not yet finished, and the most at once.
+        running: Arc<Mutex<(usize, usize)>>,
+        started: std::sync::mpsc::Receiver<usize>,
+        releases: Vec<Sender<()>>,
+    }
+
+    #[cfg(all(unix, feature = "gui"))]
+    impl Fifos {
+        fn send(worker: &WorkerHandle, name: &str, count: usize) -> (Self, Vec<LoadResponse>) {
+            let dir = std::env::temp_dir().join(format!("axe-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            let running = Arc::new(Mutex::new((0, 0)));
+            let (started_tx, started) = std::sync::mpsc::channel();
+            let mut releases = Vec::new();
+            let mut responses = Vec::new();
+            for idx in 0..count {
+                let path = dir.join(idx.to_string());
+                let made = std::process::Command::new("mkfifo").arg(&path).status();
+                assert!(made.is_ok_and(|s| s.success()), "mkfifo");
+
+                let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+                let (running, started_tx) = (Arc::clone(&running), started_tx.clone());
+                let fifo = path.clone();
+                std::thread::spawn(move || {
+                    let writer = std::fs::OpenOptions::new().write(true).open(&fifo).unwrap();
+                    {
+                        let mut running = running.lock().unwrap();
+                        running.0 += 1;
+                        running.1 = running.1.max(running.0);
+                    }
+                    let _ = started_tx.send(idx);
+                    let _ = release_rx.recv();
+                    running.lock().unwrap().0 -= 1;
+                    drop(writer);
+                });
+                releases.push(release_tx);
+
+                let (tx, rx) = std::sync::mpsc::channel();
+                worker.send(WorkerRequest::LoadLocalFile {
+                    path,
+                    options: LocalFileOptions::default(),
+                    respond_to: tx,
+                });
+                responses.push(rx);
+            }
+            let fifos = Self {
+                dir,
+                running,
+                started,
+                releases,
+            };
+            (fifos, responses)
+        }
+
+        fn next_started(&self) -> usize {
+            self.started
+                .recv_timeout(Duration::from_secs(5))
+                .expect("a request starts")
+        }
+    }
+
+    #[cfg(all(unix, feature = "gui"))]
+    impl Drop for Fifos {
+        fn drop(&mut self) {
+            self.releases.clear();
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[cfg(all(unix, feature = "gui"))]
+    #[test]
+    fn slow_local_alert_rules_do_not_hold_up_other_requests() {
+        let dir = std::env::temp_dir().join(format!("axe-slow-rule-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let fifo = dir.join("rule.log");
+        let made = std::process::Command::new("mkfifo").arg(&fifo).status();
+        assert!(made.is_ok_and(|s| s.success()), "mkfifo");
+
+        // The rule's read lasts until the write end is closed.
+        let (opened_tx, opened) = std::sync::mpsc::channel();
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let writer_path = fifo.clone();
+        std::thread::spawn(move || {
+            let writer = std::fs::OpenOptions::new()
+                .write(true)
+                .open(&writer_path)
+                .unwrap();
+            let _ = opened_tx.send(());
+            let _ = released.recv();
+            drop(writer);
+        });
+
+        let worker = spawn_worker_with_concurrency(2);
+        let mut rule = AlertRule::new(1);
+        rule.log_group = format!("file://{}", fifo.display());
+        let (events, _statuses) = std::sync::mpsc::channel();
+        worker.send(WorkerRequest::SetAlertRules {
+            rules: vec![rule],
+            events,
+        });
+        opened
+            .recv_timeout(Duration::from_secs(5))
+            .expect("the rule reads its file");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        worker.send(WorkerRequest::ExportEntries {
+            path: dir.join("out.jsonl"),
+            entries: Vec::new(),
+            options: ExportOptions::default(),
+            respond_to: tx,
+        });
+        let exported = rx.recv_timeout(Duration::from_secs(5));
+        drop(release);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(matches!(exported, Ok(Ok(0))), "got {exported:?}");
+    }
+
+    #[cfg(all(unix, feature = "gui"))]
+    #[test]
+    fn requests_beyond_the_limit_wait_their_turn() {
+        let worker = spawn_worker_with_concurrency(2);
+        let (fifos, responses) = Fifos::send(&worker, "fifos-limit", 5);
+
+        for _ in 0..5 {
+            let idx = fifos.next_started();
+            // Give a request over the limit time to start too.
+            std::thread::sleep(Duration::from_millis(50));
+            assert!(fifos.running.lock().unwrap().0 <= 2);
+            fifos.releases[idx].send(()).unwrap();
+            assert!(responses[idx].recv_timeout(Duration::from_secs(5)).is_ok());
+        }
+        assert_eq!(fifos.running.lock().unwrap().1, 2);
+    }
+
+    #[cfg(all(unix, feature = "gui"))]
+    #[test]
+    fn concurrency_can_be_changed_while_running() {
+        let worker = spawn_worker_with_concurrency(1);
+        worker.set_concurrency(3);
+        let (fifos, responses) = Fifos::send(&worker, "fifos-resize", 4);
+
+        let first: Vec<_> = (0..3).map(|_| fifos.next_started()).collect();
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(fifos.running.lock().unwrap().0, 3);
+
+        // Shrinking lets the running requests finish; the last one waits
+        // until only one is left running.
+        worker.set_concurrency(1);
+        std::thread::sleep(Duration::from_millis(50));
+        for &idx in &first[..2] {
+            fifos.releases[idx].send(()).unwrap();
+            assert!(responses[idx].recv_timeout(Duration::from_secs(5)).is_ok());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(fifos.running.lock().unwrap().0, 1);
+
+        fifos.releases[first[2]].send(()).unwrap();
+        let last = fifos.next_started();
+        assert!(!first.contains(&last));
+        fifos.releases[last].send(()).unwrap();
+        assert!(responses[last].recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[cfg(feature = "gui")]
+    #[test]
+    fn tails_refresh_on_their_own_timer_until_cancelled() {
+        use std::sync::mpsc::RecvTimeoutError;
+
+        // One permit: the tail must not keep it between refreshes.
+        let worker = spawn_worker_with_concurrency(1);
+        let (tx, tail_rx) = std::sync::mpsc::channel();
+        let id = worker.send(WorkerRequest::TailLocalFile {
+            path: PathBuf::from("/does/not/exist.log"),
+            options: LocalFileOptions::default(),
+            interval: Duration::from_millis(20),
+            respond_to: tx,
+        });
+        for _ in 0..3 {
+            assert!(matches!(
+                tail_rx.recv_timeout(Duration::from_secs(5)),
+                Ok(Err(LocalLogError::Io { .. }))
+            ));
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        worker.send(load_missing_file(tx));
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+
+        worker.cancel(id);
+        let stopped = std::iter::repeat_with(|| tail_rx.recv_timeout(Duration::from_secs(5)))
+            .find(|r| r.is_err());
+        assert!(matches!(stopped, Some(Err(RecvTimeoutError::Disconnected))));
+    }
+
+    #[cfg(feature = "gui")]
     #[test]
     fn spawn_worker_returns_handle_and_send_does_not_panic() {
         let worker = spawn_worker();